@@ -0,0 +1,80 @@
+//! Compares the first-byte dispatch of `FileKind::match_types`
+//! with a plain linear scan over `SIGNATURE_KIND`.
+//!
+//! Run with: `cargo bench --bench dispatch`
+
+use magical_rs::magical::magic::FileKind;
+use magical_rs::magical::signatures::SIGNATURE_KIND;
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+const ROUNDS: usize = 20_000;
+
+fn linear_scan(bytes: &[u8]) -> Option<FileKind> {
+    SIGNATURE_KIND
+        .iter()
+        .find(|magic| magic.matches(bytes))
+        .map(|magic| magic.kind)
+}
+
+/// Small objects: every built-in signature plus pseudo-random buffers,
+/// most of which match nothing (the worst case of a linear scan).
+fn corpus() -> Vec<Vec<u8>> {
+    let mut corpus = Vec::new();
+    let mut seed: u64 = 0x5EED_5EED;
+
+    for magic in SIGNATURE_KIND {
+        for signature in magic.signatures {
+            for &offset in magic.offsets {
+                let mut bytes = vec![0x20; offset + signature.len() + 64];
+                bytes[offset..offset + signature.len()].copy_from_slice(signature);
+                corpus.push(bytes);
+            }
+        }
+    }
+
+    for _ in 0..512 {
+        let bytes = (0..256)
+            .map(|_| {
+                seed = seed
+                    .wrapping_mul(6_364_136_223_846_793_005)
+                    .wrapping_add(1_442_695_040_888_963_407);
+                (seed >> 56) as u8
+            })
+            .collect();
+        corpus.push(bytes);
+    }
+
+    corpus
+}
+
+fn measure(name: &str, corpus: &[Vec<u8>], detect: fn(&[u8]) -> Option<FileKind>) -> Duration {
+    let start = Instant::now();
+
+    for _ in 0..ROUNDS {
+        for bytes in corpus {
+            black_box(detect(black_box(bytes)));
+        }
+    }
+
+    let elapsed = start.elapsed();
+    let calls = ROUNDS * corpus.len();
+
+    let per_call = elapsed / u32::try_from(calls).unwrap_or(u32::MAX);
+
+    println!("{name:<12} {calls} calls in {elapsed:?} ({per_call:?}/call)");
+
+    elapsed
+}
+
+fn main() {
+    let corpus = corpus();
+
+    let linear = measure("linear scan", &corpus, linear_scan);
+    let dispatch = measure("dispatch", &corpus, FileKind::match_types);
+
+    println!(
+        "speedup: {:.2}x",
+        linear.as_secs_f64() / dispatch.as_secs_f64()
+    );
+}
@@ -1,4 +1,5 @@
 use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom, match_types_custom};
+use magical_rs::magical::match_rules::Strength;
 use v_2_0_0::magic_custom_macro;
 
 pub mod v_2_0_0 {
@@ -34,6 +35,7 @@ fn my_custom_magic() {
         offsets: &[0],
         max_bytes_read: 69,
         kind: CuteGirlKind::ShoujoFile,
+        strength: Strength::Auto,
         rules: CustomMatchRules::Default,
     };
 
@@ -78,6 +80,7 @@ fn my_custom_magic_with_fn() {
         offsets: &[],
         max_bytes_read: 69,
         kind: CuteGirlKind::MikotoChanFile,
+        strength: Strength::Auto,
         rules: CustomMatchRules::WithFn(detect_mikoto_chan),
     };
 
@@ -113,6 +116,7 @@ fn my_magic_detect() {
             offsets: &[],
             max_bytes_read: 69,
             kind: CuteGirlKind::MikotoChanFile,
+            strength: Strength::Auto,
             rules: CustomMatchRules::WithFn(detect_mikoto_chan),
         },
         MagicCustom {
@@ -120,6 +124,7 @@ fn my_magic_detect() {
             offsets: &[0],
             max_bytes_read: 69,
             kind: CuteGirlKind::ShoujoFile,
+            strength: Strength::Auto,
             rules: CustomMatchRules::Default,
         },
     ];
@@ -11,6 +11,7 @@
 use core::slice;
 use magical_rs::magical::magic_custom::match_types_custom;
 use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
+use magical_rs::magical::match_rules::Strength;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 enum MagicKind {
@@ -41,6 +42,7 @@ fn main() {
         offsets: &[],
         max_bytes_read: 200,
         kind: MagicKind::MoeMoe,
+        strength: Strength::Auto,
         rules: CustomMatchRules::WithFnUnsafe {
             func: is_shoujo_girl,
         },
@@ -0,0 +1,381 @@
+//! `magical`, a small `file(1)`-like command-line tool built on `magical_rs`.
+//!
+//! Build or install it with the `cli` feature:
+//! ```bash
+//! cargo install magical_rs --features cli
+//! ```
+//!
+//! Then:
+//! ```bash
+//! magical tests/1.png tests/2.iso
+//! magical --json --all-matches some/directory
+//! cat tests/1.png | magical --mime -
+//! ```
+
+use std::fmt::Write as _;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process::ExitCode;
+
+use magical_rs::magical::bytes_read::{
+    read_header, read_header_from, read_tail_from, with_bytes_read, with_tail_bytes_read,
+};
+use magical_rs::magical::magic::{Detection, FileKind};
+
+const USAGE: &str = "\
+Usage: magical [OPTIONS] <PATH>...
+
+Detects file types from their signatures.
+Directories are walked recursively, `-` reads from the standard input.
+
+Options:
+  -j, --json          Print one JSON object per file
+  -i, --mime          Print MIME types instead of descriptions
+  -b, --brief         Do not prepend file names to output lines
+  -a, --all-matches   Print every matching type, not only the first one
+  -h, --help          Print this help
+  -V, --version       Print the version
+";
+
+/// MIME type reported for files no signature matched, like `file(1)`.
+const UNKNOWN_MIME: &str = "application/octet-stream";
+/// MIME type reported for empty files, like `file(1)`.
+const EMPTY_MIME: &str = "inode/x-empty";
+
+#[derive(Clone, Copy, Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct Options {
+    json: bool,
+    mime: bool,
+    brief: bool,
+    all_matches: bool,
+}
+
+/// What was found in one file.
+struct Detected {
+    detection: Detection,
+    /// Every matching kind, only filled with `--all-matches`.
+    matches: Vec<FileKind>,
+}
+
+enum Command {
+    Run(Options, Vec<String>),
+    Help,
+    Version,
+}
+
+fn parse_args(args: impl Iterator<Item = String>) -> Result<Command, String> {
+    let mut options = Options::default();
+    let mut paths = Vec::new();
+    let mut only_paths = false;
+
+    for arg in args {
+        if only_paths || arg == "-" || !arg.starts_with('-') {
+            paths.push(arg);
+            continue;
+        }
+
+        let flags: Vec<String> = match arg.strip_prefix("--") {
+            Some("") => {
+                only_paths = true;
+                continue;
+            }
+            Some(long) => vec![long.to_owned()],
+            None => arg.chars().skip(1).map(String::from).collect(),
+        };
+
+        for flag in flags {
+            match flag.as_str() {
+                "j" | "json" => options.json = true,
+                "i" | "mime" => options.mime = true,
+                "b" | "brief" => options.brief = true,
+                "a" | "all-matches" => options.all_matches = true,
+                "h" | "help" => return Ok(Command::Help),
+                "V" | "version" => return Ok(Command::Version),
+                _ => return Err(format!("unknown option: {arg}")),
+            }
+        }
+    }
+
+    if paths.is_empty() {
+        return Err("no input paths".to_owned());
+    }
+
+    Ok(Command::Run(options, paths))
+}
+
+fn detect(head: &[u8], tail: &[u8], options: Options) -> Detected {
+    let detection = if head.is_empty() {
+        Detection::Empty
+    } else {
+        FileKind::match_types_with_tail(head, tail).map_or(Detection::Unknown, Detection::Known)
+    };
+
+    let mut matches = Vec::new();
+
+    if options.all_matches {
+        matches = FileKind::match_all_types(head);
+        matches.dedup();
+
+        /* Trailer matches are not part of `match_all_types`. */
+        if let Detection::Known(kind) = detection
+            && !matches.contains(&kind)
+        {
+            matches.insert(0, kind);
+        }
+    }
+
+    Detected { detection, matches }
+}
+
+fn detect_file(path: &str, options: Options) -> io::Result<Detected> {
+    let header = read_header(path, with_bytes_read())?;
+
+    /* Small files are read whole, their head is also their tail. */
+    if header.truncated {
+        let tail = read_tail_from(File::open(path)?, with_tail_bytes_read())?;
+
+        Ok(detect(&header.bytes, &tail, options))
+    } else {
+        Ok(detect(&header.bytes, &header.bytes, options))
+    }
+}
+
+fn detect_stdin(options: Options) -> io::Result<Detected> {
+    let head = read_header_from(io::stdin().lock(), with_bytes_read())?;
+
+    /* Standard input cannot seek, only the head is available. */
+    Ok(detect(&head, &[], options))
+}
+
+fn describe(kind: FileKind) -> String {
+    let extension = kind
+        .extensions()
+        .first()
+        .map_or_else(String::new, |extension| format!(", .{extension}"));
+
+    format!(
+        "{} ({kind:?}, {}{extension})",
+        kind.description(),
+        kind.mime_type()
+    )
+}
+
+fn text_line(result: &io::Result<Detected>, options: Options) -> String {
+    let detected = match result {
+        Ok(detected) => detected,
+        Err(error) => return format!("cannot open ({error})"),
+    };
+
+    match detected.detection {
+        Detection::Empty if options.mime => EMPTY_MIME.to_owned(),
+        Detection::Empty => "empty".to_owned(),
+        Detection::Unknown if options.mime => UNKNOWN_MIME.to_owned(),
+        Detection::Unknown => "data".to_owned(),
+        Detection::Known(kind) => {
+            let kinds = if options.all_matches {
+                detected.matches.as_slice()
+            } else {
+                core::slice::from_ref(&kind)
+            };
+
+            kinds
+                .iter()
+                .map(|&kind| {
+                    if options.mime {
+                        kind.mime_type().to_owned()
+                    } else {
+                        describe(kind)
+                    }
+                })
+                .collect::<Vec<_>>()
+                .join("\n- ")
+        }
+    }
+}
+
+fn json_string(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len() + 2);
+    escaped.push('"');
+
+    for char in value.chars() {
+        match char {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            char if u32::from(char) < 0x20 => {
+                let _ = write!(escaped, "\\u{:04x}", u32::from(char));
+            }
+            char => escaped.push(char),
+        }
+    }
+
+    escaped.push('"');
+    escaped
+}
+
+fn json_kind(kind: FileKind) -> String {
+    let extension = kind
+        .extensions()
+        .first()
+        .map_or_else(|| "null".to_owned(), |extension| json_string(extension));
+
+    format!(
+        "\"kind\":{},\"mime\":{},\"extension\":{extension},\"description\":{}",
+        json_string(&format!("{kind:?}")),
+        json_string(kind.mime_type()),
+        json_string(kind.description())
+    )
+}
+
+fn json_line(name: &str, result: &io::Result<Detected>, options: Options) -> String {
+    let path = json_string(name);
+
+    let detected = match result {
+        Ok(detected) => detected,
+        Err(error) => {
+            return format!(
+                "{{\"path\":{path},\"status\":\"error\",\"error\":{}}}",
+                json_string(&error.to_string())
+            );
+        }
+    };
+
+    let mut line = match detected.detection {
+        Detection::Known(kind) => {
+            format!("{{\"path\":{path},\"status\":\"known\",{}", json_kind(kind))
+        }
+        Detection::Unknown => format!(
+            "{{\"path\":{path},\"status\":\"unknown\",\"kind\":null,\"mime\":\"{UNKNOWN_MIME}\",\"extension\":null,\"description\":\"data\""
+        ),
+        Detection::Empty => format!(
+            "{{\"path\":{path},\"status\":\"empty\",\"kind\":null,\"mime\":\"{EMPTY_MIME}\",\"extension\":null,\"description\":\"empty\""
+        ),
+    };
+
+    if options.all_matches {
+        let matches = detected
+            .matches
+            .iter()
+            .map(|&kind| format!("{{{}}}", json_kind(kind)))
+            .collect::<Vec<_>>()
+            .join(",");
+
+        let _ = write!(line, ",\"matches\":[{matches}]");
+    }
+
+    line.push('}');
+    line
+}
+
+fn report(
+    out: &mut impl Write,
+    name: &str,
+    result: &io::Result<Detected>,
+    options: Options,
+) -> io::Result<()> {
+    if options.json {
+        writeln!(out, "{}", json_line(name, result, options))
+    } else if options.brief {
+        writeln!(out, "{}", text_line(result, options))
+    } else {
+        writeln!(out, "{name}: {}", text_line(result, options))
+    }
+}
+
+/// Detects `path`, walking it recursively if it is a directory.
+/// Returns `false` if any file could not be read.
+fn visit(out: &mut impl Write, path: &Path, options: Options) -> io::Result<bool> {
+    let name = path.display().to_string();
+
+    if !path.is_dir() {
+        let result = detect_file(&name, options);
+        report(out, &name, &result, options)?;
+
+        return Ok(result.is_ok());
+    }
+
+    let entries = match fs::read_dir(path) {
+        Ok(entries) => entries,
+        Err(error) => {
+            report(out, &name, &Err(error), options)?;
+            return Ok(false);
+        }
+    };
+
+    let mut children = Vec::new();
+    let mut ok = true;
+
+    for entry in entries {
+        match entry {
+            /* Do not follow symbolic links to directories, they may loop. */
+            Ok(entry)
+                if entry.file_type().is_ok_and(|kind| kind.is_symlink())
+                    && entry.path().is_dir() => {}
+            Ok(entry) => children.push(entry.path()),
+            Err(error) => {
+                report(out, &name, &Err(error), options)?;
+                ok = false;
+            }
+        }
+    }
+
+    children.sort();
+
+    for child in children {
+        ok &= visit(out, &child, options)?;
+    }
+
+    Ok(ok)
+}
+
+fn run(options: Options, paths: &[String]) -> io::Result<bool> {
+    let mut out = BufWriter::new(io::stdout().lock());
+    let mut ok = true;
+
+    for path in paths {
+        if path == "-" {
+            let result = detect_stdin(options);
+            report(&mut out, "/dev/stdin", &result, options)?;
+            ok &= result.is_ok();
+        } else {
+            ok &= visit(&mut out, Path::new(path), options)?;
+        }
+    }
+
+    out.flush()?;
+
+    Ok(ok)
+}
+
+fn main() -> ExitCode {
+    let (options, paths) = match parse_args(std::env::args().skip(1)) {
+        Ok(Command::Run(options, paths)) => (options, paths),
+        Ok(Command::Help) => {
+            print!("{USAGE}");
+            return ExitCode::SUCCESS;
+        }
+        Ok(Command::Version) => {
+            println!("magical {}", env!("CARGO_PKG_VERSION"));
+            return ExitCode::SUCCESS;
+        }
+        Err(error) => {
+            eprintln!("magical: {error}\n\n{USAGE}");
+            return ExitCode::from(2);
+        }
+    };
+
+    match run(options, &paths) {
+        Ok(true) => ExitCode::SUCCESS,
+        Ok(false) => ExitCode::FAILURE,
+        /* The reader went away (e.g. `magical dir | head`), nothing left to do. */
+        Err(error) if error.kind() == io::ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(error) => {
+            eprintln!("magical: {error}");
+            ExitCode::FAILURE
+        }
+    }
+}
@@ -191,13 +191,13 @@
 //! | PNG                               | `‰PNG` at offset 0                                                                                    |
 //! | Bitmap (BMP)                      | `BM` at offset 0                                                                                      |
 //! | GZIP                              | `1F 8B` at offset 0                                                                                   |
-//! | BZIP2                             | `BZh` (e.g., `BZh9`) at offset 0                                                                      |
-//! | ZIP / `PkgZip` (JAR, APK, etc.)   | `PK` at offset 0                                                                                      |
+//! | BZIP2                             | `BZh1` to `BZh9` (block size) at offset 0                                                             |
+//! | ZIP / `PkgZip`                    | `PK` at offset 0, or `PK\x05\x06` at end; refined by entry names below                                |
 //! | TAR                               | `ustar` at offset 257                                                                                 |
-//! | MS-DOS Executable (COM/EXE)       | `MZ` at offset 0 (DOS header)                                                                         |
+//! | MS-DOS Executable (COM/EXE)       | `MZ` at offset 0 with a valid DOS header and no PE header; a bare `MZ` is a weak fallback             |
 //! | JPG / JPEG                        | Start with `ÿØÿ` (`FF D8 FF`)                                                                         |
-//! | Java Class File                   | `CAFEBABE` at offset 0                                                                                |
-//! | MP3 (MPEG Audio)                  | Often starts with `ID3` tag or `FF FB` (MPEG-1 Layer 3)                                               |
+//! | Java Class File                   | `CAFEBABE` at offset 0, then a version no fat binary has (see Mach-O)                                 |
+//! | MP3 (MPEG Audio)                  | Layer III frame headers on consecutive frames, after any `ID3` tag, or `TAG` at end                   |
 //! | ISO 9660                          | `CD001` at offset 32769, 34817, or 36865                                                              |
 //! | RPM                               | Binary header after lead; signature in first few hundred bytes                                        |
 //! | `SQLite`                          | `SQLite format 3\0` at offset 0                                                                       |
@@ -227,16 +227,80 @@
 //! | GIF                               | `GIF87a` or `GIF89a` at offset 0                                                                      |
 //! | JPEG2000                          | `\0\0\0\x0C\0\njP\x20\x20\r\n\x87\n` or `\xFF\x4F\xFF\x51` at offset 0                                |
 //! | PDF                               | `%PDF` at offset 0                                                                                    |
-//! | Apple Dis kImage                  | `koly` at offset 1048576 (rare), or `cafe`/`ed2k` in header; often starts with zeros but magic at end |
+//! | Apple Dis kImage                  | `koly` trailer 512 bytes before the end of file (needs the tail, see `match_types_with_tail`)         |
 //! | Cabinet                           | `MSCF` at offset 0                                                                                    |
-//! | Matroska Media Container          | `\x1A\x45\xDF\xA3` at offset 0                                                                        |
+//! | Matroska Media Container          | EBML header (`1A 45 DF A3`) with any `DocType` but `webm`                                             |
 //! | Rich Text Format                  | `{\\rtf` at offset 0                                                                                  |
 //! | Photo Cap Template                | No standard public signature; often `.pct` or `.tpl`; may be proprietary                              |
 //! | Ace Compressed                    | `ACE` followed by version byte at offset 0 (e.g., `ACE\x01`)                                          |
-//! | Flash Video                       | `FLV\x01` at offset 0                                                                                 |
+//! | Flash Video                       | `FLV\x01`, no reserved flag set, body offset of at least 9                                            |
 //! | Unknown                           | Fallback when no signature matches                                                                    |
-//! | VMDK File                         | `0x4B, 0x44, 0x4D`                                            at off set `0`                          |
+//! | VMDK File                         | `KDM` (sparse extent) or `# Disk DescriptorFile` (text descriptor) at offset 0                        |
 //! | Google Chrome Extension           | `0x43, 0x72, 0x32, 0x34`                            at off set `0`                                    |
+//! | Office Open XML (DOCX/XLSX/PPTX)  | ZIP with `word/`, `xl/` or `ppt/` entries                                                             |
+//! | `OpenDocument` (ODT/ODS/ODP)      | ZIP whose first, stored entry `mimetype` names the document type                                      |
+//! | EPUB                              | ZIP whose first, stored entry `mimetype` is `application/epub+zip`                                    |
+//! | JAR                               | ZIP with a `META-INF/MANIFEST.MF` entry                                                               |
+//! | APK                               | ZIP with `AndroidManifest.xml` or `classes.dex` entries                                               |
+//! | XPI (Mozilla extension)           | ZIP with `install.rdf`, `chrome.manifest` or `META-INF/mozilla.*` entries                             |
+//! | `NuGet` package                   | ZIP with a `.nuspec` entry at its root                                                                |
+//! | VSIX (Visual Studio extension)    | ZIP with an `extension.vsixmanifest` entry                                                            |
+//! | MP4 / M4V / M4A                   | `ftyp` box at offset 4, brands such as `isom`, `mp42`, `M4V `, `M4A `                                 |
+//! | `QuickTime` (MOV)                 | `ftyp` box at offset 4 with the `qt  ` brand                                                          |
+//! | HEIC / HEIF / AVIF                | `ftyp` box at offset 4, brands `heic`, `mif1`, `avif`..., compatible brands included                  |
+//! | 3GPP / 3GPP2                      | `ftyp` box at offset 4, brands `3gp*`, `3g2*`                                                         |
+//! | WAV / AVI / ANI / RMI / QCP / CDR | `RIFF`, `RIFX` or `RF64` container, told apart by the form type; chunk size must hold it              |
+//! | Mach-O                            | `FEEDFACE`/`FEEDFACF` in either byte order at offset 0                                                |
+//! | Mach-O universal binary           | `CAFEBABE` (or `CAFEBABF`) with 1 to 20 architectures at offset 4                                     |
+//! | PE32 / PE32+ / DLL                | `PE\0\0` at `e_lfanew`, optional header magic `0x10B` or `0x20B`, `IMAGE_FILE_DLL` characteristic     |
+//! | .NET assembly / Windows driver    | PE with a CLR runtime header directory, or with the native subsystem                                  |
+//! | XZ                                | `FD 37 7A 58 5A 00` at offset 0                                                                       |
+//! | Zstandard                         | `28 B5 2F FD` at offset 0, possibly after skippable frames (`50`–`5F 2A 4D 18`)                       |
+//! | LZ4                               | `04 22 4D 18` (frame) or `02 21 4C 18` (legacy), possibly after skippable frames                      |
+//! | 7-Zip                             | `37 7A BC AF 27 1C` at offset 0                                                                       |
+//! | Lzip                              | `LZIP` at offset 0                                                                                    |
+//! | LZMA                              | No magic: properties byte, dictionary size and uncompressed size checked (weak match)                 |
+//! | Unix compress                     | `1F 9D` and a code width of 9 to 16 bits                                                              |
+//! | Lzop                              | `89 4C 5A 4F 00 0D 0A 1A 0A` at offset 0                                                              |
+//! | Cpio                              | `070707`, `070701` or `070702` (ASCII), or `C7 71` / `71 C7` (binary, weak match)                     |
+//! | FLAC                              | `fLaC` and a `STREAMINFO` block, possibly after an `ID3v2` tag                                        |
+//! | AIFF / AIFF-C                     | `FORM` at offset 0, `AIFF` or `AIFC` at offset 8                                                      |
+//! | MIDI                              | `MThd` and a header length of 6                                                                       |
+//! | AAC (ADTS)                        | ADTS frame headers (sync, sample rate, channels, length) on consecutive frames                        |
+//! | Opus                              | `OggS` page whose first packet starts with `OpusHead`                                                 |
+//! | Vorbis                            | `OggS` page whose first packet starts with `\x01vorbis`                                               |
+//! | Speex                             | `OggS` page whose first packet starts with `Speex   `                                                 |
+//! | Monkey's Audio                    | `MAC ` at offset 0                                                                                    |
+//! | `WavPack`                         | `wvpk` at offset 0                                                                                    |
+//! | AMR                               | `#!AMR\n` or `#!AMR-WB\n` at offset 0                                                                 |
+//! | `WebM`                            | EBML header with `DocType` `webm`                                                                     |
+//! | MPEG-TS                           | `0x47` sync byte every 188, 192 (M2TS) or 204 bytes                                                   |
+//! | MPEG-PS                           | `00 00 01 BA` pack header with MPEG-1 or MPEG-2 marker bits                                           |
+//! | ASF (WMV / WMA)                   | `30 26 B2 75 8E 66 CF 11 A6 D9 00 AA 00 62 CE 6C` (header object GUID) at offset 0                    |
+//! | QCOW2                             | `QFI\xFB` and version 2 or 3                                                                          |
+//! | VHD                               | `conectix` at offset 0 (dynamic) or 512 bytes before the end (fixed)                                  |
+//! | VHDX                              | `vhdxfile` at offset 0                                                                                |
+//! | VDI                               | `7F 10 DA BE` at offset 64                                                                            |
+//! | GPT disk                          | `EFI PART` at offset 512 or 4096 (LBA 1)                                                              |
+//! | MBR disk                          | `55 AA` at offset 510 and a sane partition table                                                      |
+//! | ext2 / ext3 / ext4                | `53 EF` at offset 1080, told apart by the superblock feature flags                                    |
+//! | FAT12 / FAT16 / FAT32             | BIOS parameter block and `55 AA`, told apart by the number of clusters                                |
+//! | exFAT                             | `EXFAT   ` at offset 3                                                                                |
+//! | NTFS                              | `NTFS    ` at offset 3                                                                                |
+//! | `SquashFS`                        | `hsqs` (little-endian) or `sqsh` (big-endian) at offset 0                                             |
+//! | Btrfs                             | `_BHRfS_M` at offset 65600                                                                            |
+//! | XFS                               | `XFSB` at offset 0                                                                                    |
+//! | UDF                               | `NSR02` or `NSR03` in the sectors after 32768, checked before ISO 9660                                |
+//! | Parquet                           | `PAR1` at offset 0 and as the last 4 bytes                                                            |
+//! | Arrow IPC / Feather v2            | `ARROW1\0\0` at offset 0                                                                              |
+//! | Feather v1                        | `FEA1` at offset 0                                                                                    |
+//! | ORC                               | `ORC` at offset 0                                                                                     |
+//! | Avro object container             | `Obj\x01` at offset 0                                                                                 |
+//! | HDF5                              | `\x89HDF\r\n\x1A\n` at offset 0, 512, 1024, 2048... up to 65536                                       |
+//! | `NetCDF`                          | `CDF\x01`, `CDF\x02` or `CDF\x05` at offset 0                                                         |
+//! | FITS                              | `SIMPLE  =` and `T` at column 30                                                                      |
+//! | `NumPy` array                     | `\x93NUMPY` at offset 0                                                                               |
+//! | MATLAB MAT-file                   | `MATLAB x.y MAT-file` at offset 0, checked before HDF5 (MAT 7.3)                                      |
 //!
 //! ## License
 //! * `magical_rs` is licensed under the GNU General Public License v3.0.
@@ -251,13 +315,36 @@ pub mod magical {
     pub mod bytes_read;
 
     pub mod ext_fn {
+        pub mod audio;
+        pub mod compression;
+        pub mod data;
+        pub mod disk;
+        pub mod filesystem;
+        pub mod isobmff;
+        pub mod riff;
+        pub mod video;
         pub mod webp;
+        pub mod zip;
     }
 
     pub mod async_dyn_magic;
+    pub mod compiled_rules;
+    pub mod dispatch;
     pub mod dyn_magic;
+    pub mod exe_info;
+    pub mod image_info;
     pub mod magic;
     pub mod magic_custom;
+    #[cfg(feature = "std")]
+    pub mod magic_file;
     pub mod match_rules;
+    pub mod pattern;
+    #[cfg(feature = "std")]
+    pub(crate) mod regex;
+    #[cfg(feature = "std")]
+    pub mod shared_mime;
     pub mod signatures;
+    pub mod validate;
+    #[cfg(feature = "std")]
+    pub(crate) mod xml;
 }
@@ -17,6 +17,27 @@ pub const fn max_bytes(offsets: &[usize], signature: &[u8]) -> usize {
     max_offset + signature.len()
 }
 
+/// Returns the number of bytes to read from the end of a file for end-anchored rules.
+///
+/// The signature must fit between its offset and the end of the file,
+/// so this is simply the largest offset.
+#[must_use]
+#[inline]
+pub const fn max_tail_bytes(offsets: &[usize]) -> usize {
+    let mut max_offset = 0;
+    let mut index = 0;
+
+    while index < offsets.len() {
+        if offsets[index] > max_offset {
+            max_offset = offsets[index];
+        }
+
+        index += 1;
+    }
+
+    max_offset
+}
+
 /// Default bytes to read of file.
 pub const DEFAULT_MAX_BYTES_READ: usize = 2048;
 /// Default offset of file.
@@ -25,10 +46,66 @@ pub const DEFAULT_OFFSET: usize = 0;
 pub const ISO_OFFSETS: &[usize] = &[32769, 34817, 36865];
 /// TAR file offset.
 pub const TAR_OFFSETS: &[usize] = &[257];
+/// `VirtualBox` disk image signature offset, after the text banner.
+pub const VDI_OFFSETS: &[usize] = &[64];
+/// GPT header offset: LBA 1 with 512 and 4096 bytes sectors.
+pub const GPT_OFFSETS: &[usize] = &[512, 4096];
+/// FAT boot sector offset.
+pub const FAT_BOOT_SECTOR_OFFSETS: &[usize] = &[0];
+/// ext2/3/4 superblock offset.
+pub const EXT_SUPERBLOCK_OFFSETS: &[usize] = &[1024];
+/// ext2/3/4 superblock length, up to its feature flags.
+pub const EXT_SUPERBLOCK_LEN: usize = 0x68;
+/// NTFS and exFAT OEM ID offset, right after the jump instruction.
+pub const OEM_ID_OFFSETS: &[usize] = &[3];
+/// Btrfs superblock magic offset, in the superblock at 64 KiB.
+pub const BTRFS_OFFSETS: &[usize] = &[0x10040];
+/// UDF `NSR02`/`NSR03` descriptor offsets, in the sectors following the ISO 9660 descriptors.
+pub const UDF_OFFSETS: &[usize] = &[34817, 36865, 38913, 40961, 43009];
+/// HDF5 superblock offsets: 0, then after a user block of a power of two, 512 bytes or more.
+pub const HDF5_OFFSETS: &[usize] = &[0, 512, 1024, 2048, 4096, 8192, 16384, 32768, 65536];
 /// ISO file max bytes to read.
 pub const ISO_MAX_BYTES_READ: usize = max_bytes(ISO_OFFSETS, b"CD001");
 /// TAR file max bytes to read.
 pub const TAR_MAX_BYTES_READ: usize = max_bytes(TAR_OFFSETS, b"ustar");
+/// `VirtualBox` disk image max bytes to read.
+pub const VDI_MAX_BYTES_READ: usize = max_bytes(VDI_OFFSETS, b"\x7F\x10\xDA\xBE");
+/// GPT max bytes to read.
+pub const GPT_MAX_BYTES_READ: usize = max_bytes(GPT_OFFSETS, b"EFI PART");
+/// FAT max bytes to read.
+pub const FAT_MAX_BYTES_READ: usize = max_bytes(FAT_BOOT_SECTOR_OFFSETS, &[0; 512]);
+/// ext2/3/4 max bytes to read.
+pub const EXT_MAX_BYTES_READ: usize = max_bytes(EXT_SUPERBLOCK_OFFSETS, &[0; EXT_SUPERBLOCK_LEN]);
+/// NTFS max bytes to read.
+pub const NTFS_MAX_BYTES_READ: usize = max_bytes(OEM_ID_OFFSETS, b"NTFS    ");
+/// exFAT max bytes to read.
+pub const EXFAT_MAX_BYTES_READ: usize = max_bytes(OEM_ID_OFFSETS, b"EXFAT   ");
+/// Btrfs max bytes to read.
+pub const BTRFS_MAX_BYTES_READ: usize = max_bytes(BTRFS_OFFSETS, b"_BHRfS_M");
+/// UDF max bytes to read.
+pub const UDF_MAX_BYTES_READ: usize = max_bytes(UDF_OFFSETS, b"NSR02");
+/// HDF5 max bytes to read.
+pub const HDF5_MAX_BYTES_READ: usize = max_bytes(HDF5_OFFSETS, b"\x89HDF\r\n\x1A\n");
+/// Apple Disk Image `koly` trailer offset, counted back from the end of file.
+pub const DMG_TRAILER_OFFSETS: &[usize] = &[512];
+/// Zip end of central directory offset (archive without comment), counted back from the end of file.
+pub const ZIP_EOCD_OFFSETS: &[usize] = &[22];
+/// `ID3v1` tag offset, counted back from the end of file.
+pub const ID3V1_OFFSETS: &[usize] = &[128];
+/// Parquet footer offset (metadata length, then `PAR1`), counted back from the end of file.
+pub const PARQUET_TRAILER_OFFSETS: &[usize] = &[8];
+/// Virtual PC disk footer offset, counted back from the end of file (511 bytes before Virtual PC 2004).
+pub const VHD_FOOTER_OFFSETS: &[usize] = &[512, 511];
+/// Apple Disk Image max bytes to read from the end of file.
+pub const DMG_TRAILER_TAIL_BYTES_READ: usize = max_tail_bytes(DMG_TRAILER_OFFSETS);
+/// Zip end of central directory max bytes to read from the end of file.
+pub const ZIP_EOCD_TAIL_BYTES_READ: usize = max_tail_bytes(ZIP_EOCD_OFFSETS);
+/// `ID3v1` tag max bytes to read from the end of file.
+pub const ID3V1_TAIL_BYTES_READ: usize = max_tail_bytes(ID3V1_OFFSETS);
+/// Parquet trailer max bytes to read from the end of file.
+pub const PARQUET_TRAILER_TAIL_BYTES_READ: usize = max_tail_bytes(PARQUET_TRAILER_OFFSETS);
+/// Virtual PC disk footer max bytes to read from the end of file.
+pub const VHD_FOOTER_TAIL_BYTES_READ: usize = max_tail_bytes(VHD_FOOTER_OFFSETS);
 
 /// Returns the maxium number of bytes needed to read the file headers for all known signature types.
 ///
@@ -83,16 +160,43 @@ pub const TAR_MAX_BYTES_READ: usize = max_bytes(TAR_OFFSETS, b"ustar");
 pub fn with_bytes_read() -> usize {
     SIGNATURE_KIND
         .iter()
+        .filter(|magic| !magic.is_end_anchored())
         .map(|magic| magic.max_bytes_read)
         .max()
         .unwrap_or(DEFAULT_MAX_BYTES_READ)
 }
 
+/// Returns the maximum number of bytes needed from the **end** of a file
+/// for all end-anchored signatures (trailers and footers).
+///
+/// This is the tail counterpart of [`with_bytes_read`], to be used with [`read_file_head_tail`].
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::bytes_read::with_tail_bytes_read;
+///
+/// assert!(with_tail_bytes_read() >= 512);
+/// ```
+#[must_use]
+#[inline]
+pub fn with_tail_bytes_read() -> usize {
+    SIGNATURE_KIND
+        .iter()
+        .filter(|magic| magic.is_end_anchored())
+        .map(|magic| magic.max_bytes_read)
+        .max()
+        .unwrap_or_default()
+}
+
 #[cfg(feature = "std")]
 use {
+    crate::magical::magic::{Detection, FileKind},
+    crate::magical::match_rules::MatchRules,
     std::fs::File,
     std::io,
-    std::io::{BufReader, Read},
+    std::io::{BufReader, Read, Seek, SeekFrom},
+    std::ops::Range,
 };
 /// Reads up to `max_bytes` from beginning of a file.
 ///
@@ -133,8 +237,12 @@ use {
 /// Returns a `Result<Vec<u8, io::Error>`
 ///
 /// * `Ok(Vec<u8>)` - A vector containing the bytes read from file.
+///   It is never padded: for files shorter than `max_bytes`, it holds the whole file,
+///   and an empty file gives an empty vector.
 /// * `Err(io::Error)` - An I/O error if the file could not be append or read.
 ///
+/// Use [`read_header`] to also get the file length.
+///
 /// # Errors
 /// This function returns an error in the following cases:
 ///
@@ -142,25 +250,373 @@ use {
 /// * There is error while reading from the file. (e.g., disk I/O error).
 #[cfg(feature = "std")]
 pub fn read_file_header(file_path: &str, max_bytes: usize) -> Result<Vec<u8>, io::Error> {
+    read_header_from(File::open(file_path)?, max_bytes)
+}
+
+/// The first bytes of a file, along with the length of the whole file.
+///
+/// Returned by [`read_header`].
+#[cfg(feature = "std")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Header {
+    /// The bytes actually read from the beginning of the file.
+    pub bytes: Vec<u8>,
+    /// The length of the whole file, in bytes, as reported by its metadata.
+    ///
+    /// `0` for files whose length is unknown, such as procfs or sysfs entries and devices.
+    pub file_len: u64,
+    /// `true` if the file is longer than [`Header::bytes`].
+    ///
+    /// Without a known length, `true` if the file filled all of `max_bytes`.
+    pub truncated: bool,
+}
+
+#[cfg(feature = "std")]
+impl Header {
+    /// Returns `true` if the file is empty: nothing was read, and nothing was left unread.
+    ///
+    /// [`Header::file_len`] is not trusted for this, procfs entries report `0` and have content.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty() && !self.truncated
+    }
+
+    /// Detects the file type with the built-in rules.
+    ///
+    /// Empty files are reported as [`Detection::Empty`], not [`Detection::Unknown`].
+    #[must_use]
+    #[inline]
+    pub fn detect(&self) -> Detection {
+        if self.is_empty() {
+            Detection::Empty
+        } else {
+            FileKind::detect(&self.bytes)
+        }
+    }
+}
+
+/// Reads up to `max_bytes` from beginning of a file, and reports the file length.
+///
+/// Like [`read_file_header`], the returned bytes are never padded.
+/// [`Header::truncated`] tells whether the file goes on after them.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::bytes_read::{read_header, with_bytes_read};
+/// use magical_rs::magical::magic::{Detection, FileKind};
+///
+/// let header = read_header("tests/1.png", 16).unwrap();
+///
+/// assert_eq!(header.bytes.len(), 16);
+/// assert!(header.truncated);
+/// assert_eq!(header.detect(), Detection::Known(FileKind::Png));
+/// ```
+///
+/// # Errors
+/// This function returns an error in the following cases:
+///
+/// * The file does not exists or cannot be opened. (e.g., due to permission issues).
+/// * There is error while reading from the file. (e.g., disk I/O error).
+#[cfg(feature = "std")]
+pub fn read_header(file_path: &str, max_bytes: usize) -> Result<Header, io::Error> {
     let file = File::open(file_path)?;
-    let mut reader = BufReader::new(file);
+    let metadata = file.metadata()?;
+    let file_len = metadata.len();
+    let bytes = read_header_from(file, max_bytes)?;
 
-    let max_bytes_read = max_bytes;
-    let mut buffer = vec![0u8; max_bytes_read];
+    /* Only regular files report a meaningful length, and procfs files claim `0`. */
+    let truncated = if metadata.is_file() && file_len > 0 {
+        (bytes.len() as u64) < file_len
+    } else {
+        bytes.len() >= max_bytes && max_bytes > 0
+    };
 
-    let mut total_read: usize = 0;
+    Ok(Header {
+        bytes,
+        file_len,
+        truncated,
+    })
+}
 
-    while total_read < max_bytes_read {
-        match reader.read(&mut buffer[total_read..]) {
-            Ok(0) => break, /* EOF */
-            Ok(index) => total_read = total_read.saturating_add(index),
-            Err(error) => return Err(error),
+/// Reads up to `head_bytes` from the beginning and up to `tail_bytes` from the end of a file.
+///
+/// Trailer signatures (ZIP end of central directory, DMG `koly` block, `ID3v1` tags...)
+/// live at the end of a file and cannot be found in its header. This function fetches
+/// both windows so they can be checked with `FileKind::match_types_with_tail`.
+///
+/// Both buffers only contain the bytes actually read. For files smaller than
+/// `head_bytes` or `tail_bytes`, the windows overlap and may contain the whole file.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::bytes_read::{read_file_head_tail, with_bytes_read, with_tail_bytes_read};
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let (head, tail) = read_file_head_tail("tests/1.png", with_bytes_read(), with_tail_bytes_read()).unwrap();
+///
+/// assert_eq!(FileKind::match_types_with_tail(&head, &tail), Some(FileKind::Png));
+/// ```
+///
+/// # Errors
+/// This function returns an error in the following cases:
+///
+/// * The file does not exists or cannot be opened. (e.g., due to permission issues).
+/// * There is error while reading from or seeking in the file. (e.g., disk I/O error).
+#[cfg(feature = "std")]
+pub fn read_file_head_tail(
+    file_path: &str,
+    head_bytes: usize,
+    tail_bytes: usize,
+) -> Result<(Vec<u8>, Vec<u8>), io::Error> {
+    let mut file = File::open(file_path)?;
+
+    let head = read_header_from(&mut file, head_bytes)?;
+    let tail = read_tail_from(&mut file, tail_bytes)?;
+
+    Ok((head, tail))
+}
+
+/// Reads up to `max_bytes` from any reader: an open file, a network stream,
+/// an in-memory cursor, an archive entry...
+///
+/// This is the reader counterpart of [`read_file_header`]. The returned buffer
+/// only contains the bytes actually read, so it is shorter than `max_bytes`
+/// when the stream ends early.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use magical_rs::magical::bytes_read::{read_header_from, with_bytes_read};
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let stream = Cursor::new(b"%PDF-1.7\n%\xE2\xE3\xCF\xD3\n".to_vec());
+/// let header = read_header_from(stream, with_bytes_read()).unwrap();
+///
+/// assert_eq!(header.len(), 15);
+/// assert_eq!(FileKind::match_types(&header), Some(FileKind::PDF));
+/// ```
+///
+/// # Errors
+/// This function returns an error if reading from `reader` fails.
+/// Interrupted reads are retried.
+#[cfg(feature = "std")]
+pub fn read_header_from<R: Read>(reader: R, max_bytes: usize) -> Result<Vec<u8>, io::Error> {
+    let mut buffer = Vec::with_capacity(max_bytes);
+    reader.take(max_bytes as u64).read_to_end(&mut buffer)?;
+
+    Ok(buffer)
+}
+
+/// Reads up to `tail_bytes` from the end of a seekable reader.
+///
+/// The returned buffer only contains the bytes actually read, so it holds
+/// the whole stream when the stream is shorter than `tail_bytes`.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use magical_rs::magical::bytes_read::read_tail_from;
+///
+/// let tail = read_tail_from(Cursor::new(b"Magical Girl".to_vec()), 4).unwrap();
+///
+/// assert_eq!(tail, b"Girl");
+/// ```
+///
+/// # Errors
+/// This function returns an error if seeking in or reading from `reader` fails.
+#[cfg(feature = "std")]
+pub fn read_tail_from<R: Read + Seek>(
+    mut reader: R,
+    tail_bytes: usize,
+) -> Result<Vec<u8>, io::Error> {
+    let len = reader.seek(SeekFrom::End(0))?;
+    reader.seek(SeekFrom::Start(len.saturating_sub(tail_bytes as u64)))?;
+
+    read_header_from(reader, tail_bytes)
+}
+
+/// Reads only the given byte `ranges` of a seekable reader.
+///
+/// Signatures like ISO 9660's `CD001` live far from the beginning of a file
+/// (offset `32769` and above). Instead of reading everything up to the last offset,
+/// this function seeks to every range and reads just that window.
+///
+/// Ranges are absolute positions from the start of the stream. The returned buffer keeps
+/// those positions: bytes outside of `ranges` are left as zero, and the buffer ends
+/// after the last byte actually read, so it can be passed as is to `FileKind::match_types`.
+///
+/// Use [`signature_ranges`] to get the windows needed by the built-in rules.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use magical_rs::magical::bytes_read::read_ranges_from;
+///
+/// let stream = Cursor::new(b"MAHOU_SHOUJO".to_vec());
+/// let bytes = read_ranges_from(stream, &[0..2, 6..9, 40..48]).unwrap();
+///
+/// assert_eq!(bytes, b"MA\0\0\0\0SHO");
+/// ```
+///
+/// # Errors
+/// This function returns an error if seeking in or reading from `reader` fails.
+#[cfg(feature = "std")]
+pub fn read_ranges_from<R: Read + Seek>(
+    mut reader: R,
+    ranges: &[Range<usize>],
+) -> Result<Vec<u8>, io::Error> {
+    let len = ranges
+        .iter()
+        .map(|range| range.end)
+        .max()
+        .unwrap_or_default();
+    let mut buffer = vec![0u8; len];
+    let mut filled = 0;
+
+    for range in ranges.iter().filter(|range| !range.is_empty()) {
+        reader.seek(SeekFrom::Start(range.start as u64))?;
+
+        let window = &mut buffer[range.clone()];
+        let mut total_read = 0;
+
+        while total_read < window.len() {
+            match reader.read(&mut window[total_read..]) {
+                Ok(0) => break, /* EOF */
+                Ok(read) => total_read = total_read.saturating_add(read),
+                Err(error) if error.kind() == io::ErrorKind::Interrupted => {}
+                Err(error) => return Err(error),
+            }
+        }
+
+        if total_read > 0 {
+            filled = filled.max(range.start + total_read);
         }
     }
 
+    buffer.truncate(filled);
+
     Ok(buffer)
 }
 
+/// Returns the byte windows the built-in rules look at, sorted and merged.
+///
+/// Signature rules only need `offset..offset + signature.len()` for each of their offsets,
+/// function rules need everything up to their `max_bytes_read`. End-anchored rules
+/// are skipped, they are read with [`read_tail_from`].
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::bytes_read::{signature_ranges, with_bytes_read};
+///
+/// let ranges = signature_ranges();
+/// let total: usize = ranges.iter().map(|range| range.len()).sum();
+///
+/// assert!(ranges.iter().any(|range| range.contains(&32769)));
+/// assert!(total < with_bytes_read());
+/// ```
+#[must_use]
+#[cfg(feature = "std")]
+pub fn signature_ranges() -> Vec<Range<usize>> {
+    let mut ranges: Vec<Range<usize>> = Vec::new();
+
+    for magic in SIGNATURE_KIND
+        .iter()
+        .filter(|magic| !magic.is_end_anchored())
+    {
+        match &magic.rules {
+            MatchRules::Default => {
+                for signature in magic.signatures {
+                    for &offset in magic.offsets {
+                        ranges.push(offset..offset + signature.len());
+                    }
+                }
+            }
+            MatchRules::Masked(patterns) => {
+                for pattern in *patterns {
+                    for &offset in magic.offsets {
+                        ranges.push(offset..offset + pattern.len());
+                    }
+                }
+            }
+            MatchRules::WithFn(_) => ranges.push(0..magic.max_bytes_read),
+            MatchRules::FromEnd | MatchRules::WithTailFn(_) => {}
+        }
+    }
+
+    ranges.sort_by_key(|range| range.start);
+
+    let mut merged: Vec<Range<usize>> = Vec::with_capacity(ranges.len());
+
+    for range in ranges {
+        match merged.last_mut() {
+            Some(last) if range.start <= last.end => last.end = last.end.max(range.end),
+            _ => merged.push(range),
+        }
+    }
+
+    merged
+}
+
+/// Detects the file type of a seekable reader with the built-in rules.
+///
+/// Only the windows returned by [`signature_ranges`] and the last [`with_tail_bytes_read`]
+/// bytes are read, then both are checked with `FileKind::match_types_with_tail`.
+/// Ranges are read from the start of the stream, whatever its current position.
+/// Function rules only get the first `max_bytes_read` bytes, never the zeros between windows.
+///
+/// # Examples
+///
+/// ```rust
+/// use std::io::Cursor;
+/// use magical_rs::magical::bytes_read::detect_reader;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let png = Cursor::new(vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A, 0x00]);
+///
+/// assert_eq!(detect_reader(png).unwrap(), Some(FileKind::Png));
+/// assert_eq!(detect_reader(Cursor::new(b"moe moe kyun")).unwrap(), None);
+/// ```
+///
+/// # Errors
+/// This function returns an error if seeking in or reading from `reader` fails.
+#[cfg(feature = "std")]
+pub fn detect_reader<R: Read + Seek>(mut reader: R) -> Result<Option<FileKind>, io::Error> {
+    let head = read_ranges_from(&mut reader, &signature_ranges())?;
+    let tail = read_tail_from(&mut reader, with_tail_bytes_read())?;
+
+    Ok(FileKind::match_types_with_tail(&head, &tail))
+}
+
+/// Detects the file type of the file at `file_path` with the built-in rules.
+///
+/// Opens the file and calls [`detect_reader`] on it.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::bytes_read::detect_path;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// assert_eq!(detect_path("tests/2.iso").unwrap(), Some(FileKind::ISO));
+/// ```
+///
+/// # Errors
+/// This function returns an error in the following cases:
+///
+/// * The file does not exists or cannot be opened. (e.g., due to permission issues).
+/// * There is error while reading from or seeking in the file. (e.g., disk I/O error).
+#[cfg(feature = "std")]
+pub fn detect_path(file_path: &str) -> Result<Option<FileKind>, io::Error> {
+    detect_reader(BufReader::new(File::open(file_path)?))
+}
+
 #[test]
 #[cfg(feature = "std")]
 fn test_read_file_header() {
@@ -0,0 +1,237 @@
+#[cfg(feature = "std")]
+use crate::magical::magic_custom::{CustomMatchRules, MagicCustom};
+
+/// A node of a signature trie. `outputs` holds the indices of the rules
+/// whose signature ends exactly at this node.
+#[cfg(feature = "std")]
+#[derive(Default)]
+struct TrieNode {
+    /// Sorted by byte, looked up with a binary search.
+    edges: Vec<(u8, usize)>,
+    outputs: Vec<usize>,
+}
+
+/// A pre-processed set of [`MagicCustom`] rules for large rule sets.
+///
+/// [`match_types_custom`] tries every signature of every rule at every offset, so its cost
+/// grows with the number of rules. `CompiledRules` merges the signatures of all
+/// [`CustomMatchRules::Default`] rules into one trie per distinct offset, so matching
+/// walks the input once per offset and its cost is roughly proportional to the bytes read,
+/// not to the number of rules.
+///
+/// Rules that are not plain signatures (function pointers, `AnyMatches`, `AllMatches`, ...)
+/// cannot be merged. They run as a second stage, and only when they could still
+/// win over the best trie match.
+///
+/// Results are identical to [`match_types_custom`] and [`match_all_types_custom`]:
+/// rules keep their precedence order, and the first matching rule wins.
+///
+/// [`match_types_custom`]: crate::magical::magic_custom::match_types_custom
+/// [`match_all_types_custom`]: crate::magical::magic_custom::match_all_types_custom
+///
+/// ---
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::compiled_rules::CompiledRules;
+/// use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum ShoujoKind {
+///     Shoujo,
+///     MagicalGirl,
+///     Unknown,
+/// }
+///
+/// fn is_magical_girl(bytes: &[u8]) -> bool {
+///     bytes.ends_with(b"Mahou")
+/// }
+///
+/// static RULES: &[MagicCustom<ShoujoKind>] = &[
+///     MagicCustom {
+///         signatures: &[b"Shoujo"],
+///         offsets: &[0],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::Shoujo,
+///         strength: Strength::Auto,
+///         rules: CustomMatchRules::Default,
+///     },
+///     MagicCustom {
+///         signatures: &[],
+///         offsets: &[],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::MagicalGirl,
+///         strength: Strength::Auto,
+///         rules: CustomMatchRules::WithFn(is_magical_girl),
+///     },
+/// ];
+///
+/// let compiled = CompiledRules::new(RULES);
+///
+/// assert_eq!(compiled.match_types(b"Shoujo", ShoujoKind::Unknown), ShoujoKind::Shoujo);
+/// assert_eq!(compiled.match_types(b"Mahou", ShoujoKind::Unknown), ShoujoKind::MagicalGirl);
+/// assert_eq!(compiled.match_types(b"Idol", ShoujoKind::Unknown), ShoujoKind::Unknown);
+/// ```
+///
+/// ---
+///
+/// # Standard Library Context:
+/// The trie is built on the heap, so `CompiledRules` requires the `std` feature.
+#[cfg(feature = "std")]
+pub struct CompiledRules<'r, K> {
+    rules: &'r [MagicCustom<'r, K>],
+    /// Trie root per distinct offset, sorted by offset.
+    roots: Vec<(usize, usize)>,
+    nodes: Vec<TrieNode>,
+    /// Indices of the rules evaluated one by one, in rule order.
+    second_stage: Vec<usize>,
+}
+
+#[cfg(feature = "std")]
+impl<'r, K: Clone> CompiledRules<'r, K> {
+    /// Compiles `rules`. Their order is kept as the precedence order.
+    #[must_use]
+    pub fn new(rules: &'r [MagicCustom<'r, K>]) -> Self {
+        let mut compiled = Self {
+            rules,
+            roots: Vec::new(),
+            nodes: Vec::new(),
+            second_stage: Vec::new(),
+        };
+
+        for (index, rule) in rules.iter().enumerate() {
+            if matches!(rule.rules, CustomMatchRules::Default) {
+                for &offset in rule.offsets {
+                    for signature in rule.signatures {
+                        compiled.insert(offset, signature, index);
+                    }
+                }
+            } else {
+                compiled.second_stage.push(index);
+            }
+        }
+
+        compiled
+    }
+
+    fn root(&mut self, offset: usize) -> usize {
+        match self.roots.binary_search_by_key(&offset, |&(at, _)| at) {
+            Ok(position) => self.roots[position].1,
+            Err(position) => {
+                self.nodes.push(TrieNode::default());
+                self.roots.insert(position, (offset, self.nodes.len() - 1));
+
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn insert(&mut self, offset: usize, signature: &[u8], rule: usize) {
+        let mut node = self.root(offset);
+
+        for &byte in signature {
+            node = match self.nodes[node]
+                .edges
+                .binary_search_by_key(&byte, |&(edge, _)| edge)
+            {
+                Ok(position) => self.nodes[node].edges[position].1,
+                Err(position) => {
+                    self.nodes.push(TrieNode::default());
+
+                    let child = self.nodes.len() - 1;
+                    self.nodes[node].edges.insert(position, (byte, child));
+
+                    child
+                }
+            };
+        }
+
+        self.nodes[node].outputs.push(rule);
+    }
+
+    /// Calls `found` with the index of every signature rule matching `bytes`.
+    fn walk(&self, bytes: &[u8], mut found: impl FnMut(usize)) {
+        for &(offset, root) in &self.roots {
+            let Some(window) = bytes.get(offset..) else {
+                break;
+            };
+
+            let mut node = root;
+            self.nodes[node]
+                .outputs
+                .iter()
+                .for_each(|&rule| found(rule));
+
+            for &byte in window {
+                let edges = &self.nodes[node].edges;
+
+                match edges.binary_search_by_key(&byte, |&(edge, _)| edge) {
+                    Ok(position) => node = edges[position].1,
+                    Err(_) => break,
+                }
+
+                self.nodes[node]
+                    .outputs
+                    .iter()
+                    .for_each(|&rule| found(rule));
+            }
+        }
+    }
+
+    /// Returns the index of the first rule matching `bytes`.
+    fn first_match(&self, bytes: &[u8]) -> Option<usize> {
+        let mut best: Option<usize> = None;
+
+        self.walk(bytes, |rule| {
+            if best.is_none_or(|current| rule < current) {
+                best = Some(rule);
+            }
+        });
+
+        self.second_stage
+            .iter()
+            .take_while(|&&rule| best.is_none_or(|current| rule < current))
+            .find(|&&rule| self.rules[rule].matches_custom(bytes))
+            .copied()
+            .or(best)
+    }
+
+    /// Detects the file type with the compiled rules.
+    ///
+    /// Same semantics as `match_types_custom`: returns the `kind` of the first
+    /// matching rule in rule order, or `fallback` if no rule matches.
+    #[must_use]
+    pub fn match_types(&self, bytes: &[u8], fallback: K) -> K {
+        self.first_match(bytes)
+            .map_or(fallback, |rule| self.rules[rule].kind.clone())
+    }
+
+    /// Returns the `kind` of every matching rule, in rule order.
+    ///
+    /// Same semantics as `match_all_types_custom`.
+    #[must_use]
+    pub fn match_all_types(&self, bytes: &[u8]) -> Vec<K> {
+        let mut matched = vec![false; self.rules.len()];
+
+        self.walk(bytes, |rule| matched[rule] = true);
+
+        for &rule in &self.second_stage {
+            matched[rule] = self.rules[rule].matches_custom(bytes);
+        }
+
+        matched
+            .iter()
+            .zip(self.rules)
+            .filter(|&(&is_match, _)| is_match)
+            .map(|(_, rule)| rule.kind.clone())
+            .collect()
+    }
+
+    /// Returns the rules this set was compiled from.
+    #[must_use]
+    pub const fn rules(&self) -> &'r [MagicCustom<'r, K>] {
+        self.rules
+    }
+}
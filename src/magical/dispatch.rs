@@ -0,0 +1,197 @@
+use crate::magical::match_rules::MatchRules;
+use crate::magical::signatures::{Magic, SIGNATURES};
+
+/// Number of `u64` words needed to hold one bit per built-in rule.
+const RULE_WORDS: usize = SIGNATURES.len().div_ceil(u64::BITS as usize);
+
+/// A set of rule indices into `SIGNATURE_KIND`, one bit per rule.
+type RuleSet = [u64; RULE_WORDS];
+
+/// First-byte dispatch index over the built-in signature table.
+///
+/// For every possible value of the first input byte, the index stores the set of rules
+/// that can possibly match a buffer starting with that byte. Rules that cannot be keyed
+/// by the first byte (function rules, rules with non-zero offsets such as TAR or ISO,
+/// empty signatures, masked signatures starting with a wildcard) are stored once
+/// in `always` and are candidates for every input. End-anchored rules are kept apart
+/// in `end_anchored`, they are only candidates when the end of the file is known.
+///
+/// The index is built at compile time from `SIGNATURE_KIND`, so it costs nothing at run-time
+/// and works in `no_std`. Candidates are always yielded in table order, which keeps
+/// "first match wins" results identical to a linear scan.
+pub struct DispatchIndex {
+    by_first_byte: [RuleSet; 256],
+    always: RuleSet,
+    end_anchored: RuleSet,
+}
+
+impl DispatchIndex {
+    /// Builds the index for `rules` at compile time.
+    ///
+    /// Only for the built-in table: [`Candidates`] yields rules of `SIGNATURES`.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time) if `rules` holds more rules than the index has bits for.
+    #[must_use]
+    const fn build(rules: &[Magic]) -> Self {
+        assert!(rules.len() <= RULE_WORDS * u64::BITS as usize);
+
+        let mut index = Self {
+            by_first_byte: [[0; RULE_WORDS]; 256],
+            always: [0; RULE_WORDS],
+            end_anchored: [0; RULE_WORDS],
+        };
+
+        let mut rule = 0;
+
+        while rule < rules.len() {
+            let word = rule / u64::BITS as usize;
+            let bit = 1u64 << (rule % u64::BITS as usize);
+
+            if rules[rule].is_end_anchored() {
+                index.end_anchored[word] |= bit;
+            } else if is_keyed_by_first_byte(&rules[rule]) {
+                if let MatchRules::Masked(patterns) = rules[rule].rules {
+                    let mut pattern = 0;
+
+                    while pattern < patterns.len() {
+                        if let Some(first) = patterns[pattern].exact_first_byte() {
+                            index.by_first_byte[first as usize][word] |= bit;
+                        }
+
+                        pattern += 1;
+                    }
+                } else {
+                    let signatures = rules[rule].signatures;
+                    let mut signature = 0;
+
+                    while signature < signatures.len() {
+                        let first = signatures[signature][0] as usize;
+                        index.by_first_byte[first][word] |= bit;
+
+                        signature += 1;
+                    }
+                }
+            } else {
+                index.always[word] |= bit;
+            }
+
+            rule += 1;
+        }
+
+        index
+    }
+
+    /// Returns the rules that can possibly match `bytes`, in table order.
+    #[must_use]
+    #[inline]
+    pub const fn candidates(&self, bytes: &[u8]) -> Candidates {
+        let mut words = self.always;
+
+        if let Some(&first) = bytes.first() {
+            let keyed = &self.by_first_byte[first as usize];
+            let mut word = 0;
+
+            while word < RULE_WORDS {
+                words[word] |= keyed[word];
+                word += 1;
+            }
+        }
+
+        Candidates { words, word: 0 }
+    }
+
+    /// Returns the rules that can possibly match a file starting with `head`,
+    /// including end-anchored rules, in table order.
+    #[must_use]
+    #[inline]
+    pub const fn candidates_with_tail(&self, head: &[u8]) -> Candidates {
+        let mut candidates = self.candidates(head);
+        let mut word = 0;
+
+        while word < RULE_WORDS {
+            candidates.words[word] |= self.end_anchored[word];
+            word += 1;
+        }
+
+        candidates
+    }
+}
+
+/// A rule can be keyed by the first byte if it only compares signatures at offset `0`,
+/// and every signature has a fully significant first byte.
+const fn is_keyed_by_first_byte(magic: &Magic) -> bool {
+    if magic.offsets.len() != 1 || magic.offsets[0] != 0 {
+        return false;
+    }
+
+    match magic.rules {
+        MatchRules::Default => {
+            if magic.signatures.is_empty() {
+                return false;
+            }
+
+            let mut signature = 0;
+
+            while signature < magic.signatures.len() {
+                if magic.signatures[signature].is_empty() {
+                    return false;
+                }
+
+                signature += 1;
+            }
+
+            true
+        }
+        MatchRules::Masked(patterns) => {
+            if patterns.is_empty() {
+                return false;
+            }
+
+            let mut pattern = 0;
+
+            while pattern < patterns.len() {
+                if patterns[pattern].exact_first_byte().is_none() {
+                    return false;
+                }
+
+                pattern += 1;
+            }
+
+            true
+        }
+        MatchRules::WithFn(_) | MatchRules::FromEnd | MatchRules::WithTailFn(_) => false,
+    }
+}
+
+/// Iterator over the candidate rules of a [`DispatchIndex`], in table order.
+pub struct Candidates {
+    words: RuleSet,
+    word: usize,
+}
+
+impl Iterator for Candidates {
+    type Item = &'static Magic;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.word < RULE_WORDS {
+            let bits = self.words[self.word];
+
+            if bits != 0 {
+                let rule = self.word * u64::BITS as usize + bits.trailing_zeros() as usize;
+                self.words[self.word] = bits & (bits - 1);
+
+                return Some(&SIGNATURES[rule]);
+            }
+
+            self.word += 1;
+        }
+
+        None
+    }
+}
+
+/// Compile-time dispatch index over `SIGNATURE_KIND`.
+pub static SIGNATURE_INDEX: DispatchIndex = DispatchIndex::build(SIGNATURES);
@@ -0,0 +1,542 @@
+use crate::magical::magic::FileKind;
+
+/// The container of an executable, see [`exe_info`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExeFormat {
+    Elf,
+    /// A Portable Executable: `MZ` stub followed by a `PE\0\0` header.
+    Pe,
+    /// An `MZ` executable without a PE header.
+    MsDos,
+    MachO,
+    /// A Mach-O universal binary, holding one Mach-O file per architecture.
+    MachOFat,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Bitness {
+    Bits16,
+    Bits32,
+    Bits64,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Endianness {
+    Little,
+    Big,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExeType {
+    Executable,
+    /// Shared libraries, DLLs and Mach-O bundles. ELF position independent executables
+    /// are shared objects too, and reported as such.
+    SharedLibrary,
+    /// Object files.
+    Relocatable,
+    Core,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Machine {
+    X86,
+    X86_64,
+    Arm,
+    Aarch64,
+    PowerPc,
+    PowerPc64,
+    Mips,
+    RiscV,
+    S390,
+    Sparc,
+    Ia64,
+    LoongArch,
+    /// The raw machine field: `e_machine`, the COFF `Machine` or the Mach-O `cputype`.
+    Other(u32),
+}
+
+/// The subsystem a Portable Executable runs in.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PeSubsystem {
+    /// Drivers and native system processes.
+    Native,
+    WindowsGui,
+    WindowsCui,
+    Os2Cui,
+    PosixCui,
+    WindowsCeGui,
+    EfiApplication,
+    EfiBootServiceDriver,
+    EfiRuntimeDriver,
+    EfiRom,
+    Xbox,
+    WindowsBootApplication,
+    Other(u16),
+}
+
+/// What the PE header tells beyond the common fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PeInfo {
+    /// `e_lfanew`, the offset of the `PE\0\0` signature.
+    pub header_offset: u32,
+    /// [`None`] if the optional header is missing, as in object files.
+    pub subsystem: Option<PeSubsystem>,
+    /// Whether the CLR runtime header directory is set: a .NET assembly.
+    pub dot_net: bool,
+}
+
+/// What the header of an executable tells, see [`exe_info`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExeInfo {
+    pub format: ExeFormat,
+    /// For fat binaries, the bitness of the first architecture.
+    pub bitness: Option<Bitness>,
+    /// [`None`] for fat binaries, whose architectures may differ.
+    pub endianness: Option<Endianness>,
+    /// For fat binaries, the first architecture.
+    pub machine: Machine,
+    /// [`None`] for fat binaries, and for unknown types.
+    pub file_type: Option<ExeType>,
+    /// The number of architectures, only more than 1 for fat binaries.
+    pub architectures: u32,
+    /// Only set for [`ExeFormat::Pe`].
+    pub pe: Option<PeInfo>,
+}
+
+fn read_u16(bytes: &[u8], offset: usize, endianness: Endianness) -> Option<u16> {
+    let field = bytes.get(offset..offset.checked_add(2)?)?.try_into().ok()?;
+
+    Some(match endianness {
+        Endianness::Little => u16::from_le_bytes(field),
+        Endianness::Big => u16::from_be_bytes(field),
+    })
+}
+
+fn read_u32(bytes: &[u8], offset: usize, endianness: Endianness) -> Option<u32> {
+    let field = bytes.get(offset..offset.checked_add(4)?)?.try_into().ok()?;
+
+    Some(match endianness {
+        Endianness::Little => u32::from_le_bytes(field),
+        Endianness::Big => u32::from_be_bytes(field),
+    })
+}
+
+const ELF_MAGIC: &[u8; 4] = b"\x7FELF";
+
+/// Reads `e_ident`, `e_type` and `e_machine`.
+fn elf_info(bytes: &[u8]) -> Option<ExeInfo> {
+    let bitness = match bytes.get(4)? {
+        1 => Bitness::Bits32,
+        2 => Bitness::Bits64,
+        _ => return None,
+    };
+    let endianness = match bytes.get(5)? {
+        1 => Endianness::Little,
+        2 => Endianness::Big,
+        _ => return None,
+    };
+
+    let file_type = match read_u16(bytes, 16, endianness)? {
+        1 => Some(ExeType::Relocatable),
+        2 => Some(ExeType::Executable),
+        3 => Some(ExeType::SharedLibrary),
+        4 => Some(ExeType::Core),
+        _ => None,
+    };
+
+    let machine = match read_u16(bytes, 18, endianness)? {
+        3 => Machine::X86,
+        62 => Machine::X86_64,
+        40 => Machine::Arm,
+        183 => Machine::Aarch64,
+        20 => Machine::PowerPc,
+        21 => Machine::PowerPc64,
+        8 | 10 => Machine::Mips,
+        243 => Machine::RiscV,
+        22 => Machine::S390,
+        2 | 18 | 43 => Machine::Sparc,
+        50 => Machine::Ia64,
+        258 => Machine::LoongArch,
+        other => Machine::Other(u32::from(other)),
+    };
+
+    Some(ExeInfo {
+        format: ExeFormat::Elf,
+        bitness: Some(bitness),
+        endianness: Some(endianness),
+        machine,
+        file_type,
+        architectures: 1,
+        pe: None,
+    })
+}
+
+const MZ_MAGIC: &[u8; 2] = b"MZ";
+const PE_MAGIC: &[u8; 4] = b"PE\0\0";
+/// `e_lfanew`, in the MZ header.
+const PE_HEADER_OFFSET: usize = 0x3C;
+/// The MZ header up to `e_ovno`, what every DOS executable has.
+const DOS_HEADER_LEN: u16 = 0x1C;
+const DOS_PAGE_LEN: u16 = 512;
+/// Signature and COFF file header.
+const PE_OPTIONAL_HEADER_OFFSET: usize = 24;
+const PE32_MAGIC: u16 = 0x10B;
+const PE32_PLUS_MAGIC: u16 = 0x20B;
+/// `IMAGE_DIRECTORY_ENTRY_COM_DESCRIPTOR`, the CLR runtime header.
+const PE_CLR_DIRECTORY: u32 = 14;
+/// `IMAGE_FILE_DLL` in the COFF characteristics.
+const PE_DLL: u16 = 0x2000;
+/// `IMAGE_FILE_EXECUTABLE_IMAGE` in the COFF characteristics.
+const PE_EXECUTABLE: u16 = 0x0002;
+
+const fn pe_subsystem(value: u16) -> PeSubsystem {
+    match value {
+        1 => PeSubsystem::Native,
+        2 => PeSubsystem::WindowsGui,
+        3 => PeSubsystem::WindowsCui,
+        5 => PeSubsystem::Os2Cui,
+        7 => PeSubsystem::PosixCui,
+        9 => PeSubsystem::WindowsCeGui,
+        10 => PeSubsystem::EfiApplication,
+        11 => PeSubsystem::EfiBootServiceDriver,
+        12 => PeSubsystem::EfiRuntimeDriver,
+        13 => PeSubsystem::EfiRom,
+        14 => PeSubsystem::Xbox,
+        16 => PeSubsystem::WindowsBootApplication,
+        other => PeSubsystem::Other(other),
+    }
+}
+
+/// Checks the MZ header fields every DOS loader relies on: the bytes in the last page,
+/// the page count, the header size and the relocation table offset.
+fn has_dos_header(bytes: &[u8]) -> bool {
+    const LE: Endianness = Endianness::Little;
+
+    let (Some(last_page), Some(pages), Some(paragraphs), Some(relocations)) = (
+        read_u16(bytes, 2, LE),
+        read_u16(bytes, 4, LE),
+        read_u16(bytes, 8, LE),
+        read_u16(bytes, 0x18, LE),
+    ) else {
+        return false;
+    };
+
+    last_page < DOS_PAGE_LEN
+        && pages != 0
+        && u32::from(paragraphs) * 16 >= u32::from(DOS_HEADER_LEN)
+        && relocations >= DOS_HEADER_LEN
+}
+
+/// Follows `e_lfanew` to the `PE\0\0` signature, then reads the COFF header and the
+/// optional header. Without a PE signature, this is a plain MS-DOS executable, if its
+/// MZ header is valid.
+fn pe_info(bytes: &[u8]) -> Option<ExeInfo> {
+    const LE: Endianness = Endianness::Little;
+
+    let header_offset = read_u32(bytes, PE_HEADER_OFFSET, LE)?;
+    let start = header_offset as usize;
+
+    if bytes.get(start..start.checked_add(4)?) != Some(PE_MAGIC) {
+        if !has_dos_header(bytes) {
+            return None;
+        }
+
+        return Some(ExeInfo {
+            format: ExeFormat::MsDos,
+            bitness: Some(Bitness::Bits16),
+            endianness: Some(LE),
+            machine: Machine::X86,
+            file_type: Some(ExeType::Executable),
+            architectures: 1,
+            pe: None,
+        });
+    }
+
+    let machine = match read_u16(bytes, start + 4, LE)? {
+        0x14C => Machine::X86,
+        0x8664 => Machine::X86_64,
+        0x1C0 | 0x1C2 | 0x1C4 => Machine::Arm,
+        0xAA64 => Machine::Aarch64,
+        0x1F0 | 0x1F1 => Machine::PowerPc,
+        0x166 | 0x169 => Machine::Mips,
+        0x5032 | 0x5064 | 0x5128 => Machine::RiscV,
+        0x200 => Machine::Ia64,
+        0x6232 | 0x6264 => Machine::LoongArch,
+        other => Machine::Other(u32::from(other)),
+    };
+    let optional_len = read_u16(bytes, start + 20, LE)?;
+    let characteristics = read_u16(bytes, start + 22, LE)?;
+
+    let file_type = if characteristics & PE_DLL != 0 {
+        ExeType::SharedLibrary
+    } else if characteristics & PE_EXECUTABLE != 0 {
+        ExeType::Executable
+    } else {
+        ExeType::Relocatable
+    };
+
+    let mut pe = PeInfo {
+        header_offset,
+        subsystem: None,
+        dot_net: false,
+    };
+    let mut bitness = None;
+
+    if optional_len != 0 {
+        let optional = start + PE_OPTIONAL_HEADER_OFFSET;
+
+        /* Where `NumberOfRvaAndSizes` is, the data directories follow it. */
+        let directory_count = match read_u16(bytes, optional, LE)? {
+            PE32_MAGIC => {
+                bitness = Some(Bitness::Bits32);
+                optional + 92
+            }
+            PE32_PLUS_MAGIC => {
+                bitness = Some(Bitness::Bits64);
+                optional + 108
+            }
+            _ => return None,
+        };
+
+        pe.subsystem = Some(pe_subsystem(read_u16(bytes, optional + 68, LE)?));
+
+        if read_u32(bytes, directory_count, LE)? > PE_CLR_DIRECTORY {
+            let clr = directory_count + 4 + PE_CLR_DIRECTORY as usize * 8;
+            pe.dot_net = read_u32(bytes, clr, LE)? != 0;
+        }
+    }
+
+    Some(ExeInfo {
+        format: ExeFormat::Pe,
+        bitness,
+        endianness: Some(LE),
+        machine,
+        file_type: Some(file_type),
+        architectures: 1,
+        pe: Some(pe),
+    })
+}
+
+const MACH_O_MAGICS: [[u8; 4]; 4] = [
+    [0xFE, 0xED, 0xFA, 0xCE],
+    [0xFE, 0xED, 0xFA, 0xCF],
+    [0xCE, 0xFA, 0xED, 0xFE],
+    [0xCF, 0xFA, 0xED, 0xFE],
+];
+const FAT_MAGIC: &[u8; 4] = b"\xCA\xFE\xBA\xBE";
+const FAT_MAGIC_64: &[u8; 4] = b"\xCA\xFE\xBA\xBF";
+/// `CPU_ARCH_ABI64` in `cputype`.
+const CPU_ARCH_ABI64: u32 = 0x0100_0000;
+/// Java class files share the fat magic, then have their minor and major versions where fat
+/// binaries have their architecture count. Class major versions start at 45, and no fat
+/// binary holds that many architectures.
+const MAX_FAT_ARCHITECTURES: u32 = 20;
+
+const fn mach_o_machine(cputype: u32) -> Machine {
+    match cputype {
+        7 => Machine::X86,
+        0x0100_0007 => Machine::X86_64,
+        12 => Machine::Arm,
+        0x0100_000C | 0x0200_000C => Machine::Aarch64,
+        18 => Machine::PowerPc,
+        0x0100_0012 => Machine::PowerPc64,
+        other => Machine::Other(other),
+    }
+}
+
+/// Reads the Mach-O header: `cputype` and `filetype`.
+fn mach_o_info(bytes: &[u8]) -> Option<ExeInfo> {
+    let magic = bytes.get(0..4)?;
+
+    let (bitness, endianness) = match magic {
+        [0xFE, 0xED, 0xFA, 0xCE] => (Bitness::Bits32, Endianness::Big),
+        [0xFE, 0xED, 0xFA, 0xCF] => (Bitness::Bits64, Endianness::Big),
+        [0xCE, 0xFA, 0xED, 0xFE] => (Bitness::Bits32, Endianness::Little),
+        [0xCF, 0xFA, 0xED, 0xFE] => (Bitness::Bits64, Endianness::Little),
+        _ => return None,
+    };
+
+    let machine = mach_o_machine(read_u32(bytes, 4, endianness)?);
+    let file_type = match read_u32(bytes, 12, endianness)? {
+        1 => Some(ExeType::Relocatable),
+        2 => Some(ExeType::Executable),
+        4 => Some(ExeType::Core),
+        6 | 8 | 9 => Some(ExeType::SharedLibrary),
+        _ => None,
+    };
+
+    Some(ExeInfo {
+        format: ExeFormat::MachO,
+        bitness: Some(bitness),
+        endianness: Some(endianness),
+        machine,
+        file_type,
+        architectures: 1,
+        pe: None,
+    })
+}
+
+/// Reads the fat header and its first architecture.
+fn mach_o_fat_info(bytes: &[u8]) -> Option<ExeInfo> {
+    const BE: Endianness = Endianness::Big;
+
+    let architectures = read_u32(bytes, 4, BE)?;
+
+    if !(1..=MAX_FAT_ARCHITECTURES).contains(&architectures) {
+        return None;
+    }
+
+    let cputype = read_u32(bytes, 8, BE)?;
+    let bitness = if cputype & CPU_ARCH_ABI64 == 0 {
+        Bitness::Bits32
+    } else {
+        Bitness::Bits64
+    };
+
+    Some(ExeInfo {
+        format: ExeFormat::MachOFat,
+        bitness: Some(bitness),
+        endianness: None,
+        machine: mach_o_machine(cputype),
+        file_type: None,
+        architectures,
+        pe: None,
+    })
+}
+
+/// Reads the header of an ELF, PE (or MS-DOS), Mach-O or Mach-O fat executable.
+///
+/// Only headers are parsed: format, bitness, endianness, machine and file type, plus the
+/// subsystem and .NET flag of PE files. The PE header may be a few hundred bytes in,
+/// reading [`DEFAULT_MAX_BYTES_READ`] bytes is enough for common files.
+///
+/// [`DEFAULT_MAX_BYTES_READ`]: crate::magical::bytes_read::DEFAULT_MAX_BYTES_READ
+///
+/// Returns [`None`] if `bytes` is not an executable, or ends before the needed fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::exe_info::{Bitness, ExeFormat, ExeType, Machine, exe_info};
+///
+/// let elf = b"\x7FELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x03\0\x3E\0\x01\0\0\0";
+/// let info = exe_info(elf).unwrap();
+///
+/// assert_eq!(info.format, ExeFormat::Elf);
+/// assert_eq!(info.bitness, Some(Bitness::Bits64));
+/// assert_eq!(info.machine, Machine::X86_64);
+/// assert_eq!(info.file_type, Some(ExeType::SharedLibrary));
+/// assert_eq!(exe_info(b"\xCA\xFE\xBA\xBE\0\0\0\x34"), None);
+/// ```
+#[must_use]
+pub fn exe_info(bytes: &[u8]) -> Option<ExeInfo> {
+    let magic = bytes.get(0..4)?;
+
+    if magic == ELF_MAGIC {
+        elf_info(bytes)
+    } else if magic.starts_with(MZ_MAGIC) {
+        pe_info(bytes)
+    } else if magic == FAT_MAGIC || magic == FAT_MAGIC_64 {
+        mach_o_fat_info(bytes)
+    } else if MACH_O_MAGICS.iter().any(|known| magic == known) {
+        mach_o_info(bytes)
+    } else {
+        None
+    }
+}
+
+#[must_use]
+pub fn is_mach_o(bytes: &[u8]) -> bool {
+    bytes
+        .get(0..4)
+        .is_some_and(|magic| MACH_O_MAGICS.iter().any(|known| magic == known))
+}
+
+#[must_use]
+pub fn is_mach_o_fat(bytes: &[u8]) -> bool {
+    (bytes.starts_with(FAT_MAGIC) || bytes.starts_with(FAT_MAGIC_64))
+        && mach_o_fat_info(bytes).is_some()
+}
+
+/// A Java class file: `CAFEBABE`, then versions no fat binary can have.
+#[must_use]
+pub fn is_java_class(bytes: &[u8]) -> bool {
+    bytes.starts_with(FAT_MAGIC)
+        && read_u32(bytes, 4, Endianness::Big)
+            .is_some_and(|version| version > MAX_FAT_ARCHITECTURES)
+}
+
+/// Tells Portable Executables apart: .NET assemblies first, then drivers (native subsystem),
+/// DLLs, and PE32+ or PE32 files.
+///
+/// Returns [`FileKind::MSDOS`] for an MS-DOS executable with a valid MZ header and no PE
+/// header, and [`None`] for anything else.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::exe_info::pe_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let mut dos = b"MZ\x90\0\x03\0\0\0\x04\0".to_vec();
+/// dos.resize(0x40, 0);
+/// dos[0x18] = 0x40;
+///
+/// assert_eq!(pe_kind(&dos), Some(FileKind::MSDOS));
+/// assert_eq!(pe_kind(b"MZ is not always an executable, is it? Not at all, no no no no."), None);
+/// ```
+#[must_use]
+pub fn pe_kind(bytes: &[u8]) -> Option<FileKind> {
+    if !bytes.starts_with(MZ_MAGIC) {
+        return None;
+    }
+
+    let info = pe_info(bytes)?;
+
+    let Some(pe) = info.pe else {
+        return Some(FileKind::MSDOS);
+    };
+
+    if pe.dot_net {
+        Some(FileKind::DotNetAssembly)
+    } else if pe.subsystem == Some(PeSubsystem::Native) {
+        Some(FileKind::WindowsDriver)
+    } else if info.file_type == Some(ExeType::SharedLibrary) {
+        Some(FileKind::Dll)
+    } else {
+        match info.bitness? {
+            Bitness::Bits64 => Some(FileKind::Pe32Plus),
+            Bitness::Bits32 | Bitness::Bits16 => Some(FileKind::Pe32),
+        }
+    }
+}
+
+#[must_use]
+pub fn is_ms_dos(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::MSDOS)
+}
+
+#[must_use]
+pub fn is_pe32(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::Pe32)
+}
+
+#[must_use]
+pub fn is_pe32_plus(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::Pe32Plus)
+}
+
+#[must_use]
+pub fn is_dot_net_assembly(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::DotNetAssembly)
+}
+
+#[must_use]
+pub fn is_windows_driver(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::WindowsDriver)
+}
+
+#[must_use]
+pub fn is_dll(bytes: &[u8]) -> bool {
+    pe_kind(bytes) == Some(FileKind::Dll)
+}
@@ -0,0 +1,298 @@
+const ID3V2: &[u8; 3] = b"ID3";
+/// `ID3`, version, revision, flags and the syncsafe tag size.
+const ID3V2_HEADER_LEN: usize = 10;
+/// A footer, a copy of the header, follows the tag.
+const ID3V2_FOOTER_FLAG: u8 = 0x10;
+
+const FLAC: &[u8; 4] = b"fLaC";
+/// The `STREAMINFO` block, always first, without the "last block" bit.
+const FLAC_STREAMINFO: u8 = 0;
+
+const OGG_PAGE: &[u8; 4] = b"OggS";
+/// Set in the header type of the first page of a logical stream.
+const OGG_BEGINNING_OF_STREAM: u8 = 0x02;
+/// Up to the segment count, the segment table follows.
+const OGG_PAGE_HEADER_LEN: usize = 27;
+const OPUS_HEAD: &[u8] = b"OpusHead";
+const VORBIS_IDENTIFICATION: &[u8] = b"\x01vorbis";
+const SPEEX_HEADER: &[u8] = b"Speex   ";
+
+const MPEG_HEADER_LEN: usize = 4;
+const ADTS_HEADER_LEN: usize = 7;
+/// Consecutive frames to see before calling a stream MPEG audio or ADTS, when the bytes
+/// read go that far.
+const MIN_FRAMES: usize = 3;
+
+/// Layer III bitrates in kbit/s, by bitrate index. 0 (free format) and 15 are invalid.
+const MPEG1_LAYER3_BITRATES: [u32; 15] = [
+    0, 32, 40, 48, 56, 64, 80, 96, 112, 128, 160, 192, 224, 256, 320,
+];
+/// MPEG-2 and MPEG-2.5 share their layer III bitrates.
+const MPEG2_LAYER3_BITRATES: [u32; 15] =
+    [0, 8, 16, 24, 32, 40, 48, 56, 64, 80, 96, 112, 128, 144, 160];
+const MPEG1_SAMPLE_RATES: [u32; 3] = [44100, 48000, 32000];
+/// ADTS sampling frequency indexes 13 to 15 are reserved or explicit, neither valid here.
+const ADTS_SAMPLE_RATE_INDEXES: u8 = 13;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum MpegVersion {
+    Mpeg1,
+    Mpeg2,
+    Mpeg25,
+}
+
+/// The fields of an MPEG audio frame header that must stay the same along a stream.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+struct MpegFrame {
+    version: MpegVersion,
+    sample_rate: u32,
+    len: usize,
+}
+
+/// Reads an MPEG-1, 2 or 2.5 layer III frame header.
+fn mpeg_frame(header: &[u8]) -> Option<MpegFrame> {
+    let &[sync, flags, rates, _] = header.get(..MPEG_HEADER_LEN)? else {
+        return None;
+    };
+
+    /* 11 bits of frame sync, then the version and the layer (`01` is layer III). */
+    if sync != 0xFF || flags & 0xE0 != 0xE0 || flags & 0x06 != 0x02 {
+        return None;
+    }
+
+    let version = match (flags >> 3) & 0x03 {
+        0 => MpegVersion::Mpeg25,
+        2 => MpegVersion::Mpeg2,
+        3 => MpegVersion::Mpeg1,
+        _ => return None,
+    };
+
+    let bitrates = if version == MpegVersion::Mpeg1 {
+        &MPEG1_LAYER3_BITRATES
+    } else {
+        &MPEG2_LAYER3_BITRATES
+    };
+    let bitrate = *bitrates
+        .get(usize::from(rates >> 4))
+        .filter(|&&rate| rate != 0)?;
+
+    let sample_rate = *MPEG1_SAMPLE_RATES.get(usize::from((rates >> 2) & 0x03))?;
+    let (sample_rate, samples_per_byte) = match version {
+        MpegVersion::Mpeg1 => (sample_rate, 144),
+        MpegVersion::Mpeg2 => (sample_rate / 2, 72),
+        MpegVersion::Mpeg25 => (sample_rate / 4, 72),
+    };
+
+    let padding = u32::from((rates >> 1) & 0x01);
+    let len = samples_per_byte * bitrate * 1000 / sample_rate + padding;
+
+    Some(MpegFrame {
+        version,
+        sample_rate,
+        len: len as usize,
+    })
+}
+
+/// Reads an ADTS header and returns the length of its frame.
+fn adts_frame(header: &[u8]) -> Option<usize> {
+    let header = header.get(..ADTS_HEADER_LEN)?;
+
+    /* 12 bits of sync, then the MPEG version bit and a layer of `00`. */
+    if header[0] != 0xFF || header[1] & 0xF6 != 0xF0 {
+        return None;
+    }
+
+    let sample_rate_index = (header[2] >> 2) & 0x0F;
+    let channels = ((header[2] & 0x01) << 2) | (header[3] >> 6);
+    let len = (usize::from(header[3] & 0x03) << 11)
+        | (usize::from(header[4]) << 3)
+        | usize::from(header[5] >> 5);
+    let header_len = if header[1] & 0x01 == 0 {
+        ADTS_HEADER_LEN + 2
+    } else {
+        ADTS_HEADER_LEN
+    };
+
+    (sample_rate_index < ADTS_SAMPLE_RATE_INDEXES && channels != 0 && len > header_len)
+        .then_some(len)
+}
+
+/// Follows frames from `offset`, reading the length of each one with `frame`.
+///
+/// A stream is accepted after [`MIN_FRAMES`] valid frames, or when `bytes` ends before the
+/// next full header.
+fn consecutive_frames(
+    bytes: &[u8],
+    mut offset: usize,
+    header_len: usize,
+    frame: impl Fn(&[u8]) -> Option<usize>,
+) -> bool {
+    for _ in 0..MIN_FRAMES {
+        let Some(header) = bytes.get(offset..offset + header_len) else {
+            return true;
+        };
+
+        match frame(header) {
+            Some(len) => offset += len,
+            None => return false,
+        }
+    }
+
+    true
+}
+
+/// Returns the length of the `ID3v2` tag at the start of `bytes`, footer included, or
+/// [`None`] if there is no such tag.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::audio::id3v2_len;
+///
+/// assert_eq!(id3v2_len(b"ID3\x04\0\0\0\0\x01\x7F"), Some(265));
+/// assert_eq!(id3v2_len(b"ID3\x04\0\0\0\0\x01\x80"), None);
+/// assert_eq!(id3v2_len(b"\xFF\xFB\x90\x64"), None);
+/// ```
+#[must_use]
+pub fn id3v2_len(bytes: &[u8]) -> Option<usize> {
+    let header = bytes.get(..ID3V2_HEADER_LEN)?;
+
+    if &header[..3] != ID3V2 || header[3] == 0xFF || header[4] == 0xFF {
+        return None;
+    }
+
+    /* Syncsafe: 7 bits per byte, the top bit always clear. */
+    let mut size = 0;
+
+    for &byte in &header[6..] {
+        if byte & 0x80 != 0 {
+            return None;
+        }
+
+        size = (size << 7) | usize::from(byte);
+    }
+
+    let footer = if header[5] & ID3V2_FOOTER_FLAG == 0 {
+        0
+    } else {
+        ID3V2_HEADER_LEN
+    };
+
+    Some(ID3V2_HEADER_LEN + size + footer)
+}
+
+/// Skips the `ID3v2` tags at the start of `bytes` and the zero padding after them.
+///
+/// Returns the offset of the audio data, and whether any tag was found. A tag ending past
+/// `bytes` (cover art easily takes more than the bytes read) stops there: nothing after
+/// it, zeros included, was read as part of the file.
+fn skip_id3v2(bytes: &[u8]) -> (usize, bool) {
+    let mut offset = 0;
+
+    while let Some(len) = bytes.get(offset..).and_then(id3v2_len) {
+        offset = offset.saturating_add(len);
+
+        if offset >= bytes.len() {
+            return (offset, true);
+        }
+    }
+
+    let tagged = offset != 0;
+
+    if tagged {
+        while bytes.get(offset) == Some(&0) {
+            offset += 1;
+        }
+    }
+
+    (offset, tagged)
+}
+
+/// An MPEG audio layer III stream, after any `ID3v2` tag.
+///
+/// The frame headers (version, layer, bitrate, sample rate) are checked on consecutive
+/// frames as far as `bytes` goes. A tag running past `bytes` is taken as MP3, the format
+/// it is almost always found on.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::audio::is_mp3;
+///
+/// let mut mp3 = b"ID3\x03\0\0\0\0\0\x02\0\0".to_vec();
+/// mp3.extend_from_slice(b"\xFF\xFB\x90\x64");
+///
+/// assert!(is_mp3(&mp3));
+/// assert!(!is_mp3(b"\xFF\xFB\xF0\x64"));
+/// ```
+#[must_use]
+pub fn is_mp3(bytes: &[u8]) -> bool {
+    let (offset, tagged) = skip_id3v2(bytes);
+
+    if offset >= bytes.len() {
+        return tagged;
+    }
+
+    let Some(first) = bytes.get(offset..).and_then(mpeg_frame) else {
+        return false;
+    };
+
+    consecutive_frames(bytes, offset, MPEG_HEADER_LEN, |header| {
+        mpeg_frame(header)
+            .filter(|frame| {
+                frame.version == first.version && frame.sample_rate == first.sample_rate
+            })
+            .map(|frame| frame.len)
+    })
+}
+
+/// AAC in ADTS frames, after any `ID3v2` tag.
+#[must_use]
+pub fn is_aac(bytes: &[u8]) -> bool {
+    let (offset, _) = skip_id3v2(bytes);
+
+    bytes.get(offset..).and_then(adts_frame).is_some()
+        && consecutive_frames(bytes, offset, ADTS_HEADER_LEN, adts_frame)
+}
+
+/// `fLaC` and its `STREAMINFO` block, after any `ID3v2` tag.
+#[must_use]
+pub fn is_flac(bytes: &[u8]) -> bool {
+    let (offset, _) = skip_id3v2(bytes);
+
+    bytes.get(offset..).is_some_and(|bytes| {
+        bytes.starts_with(FLAC)
+            && bytes
+                .get(4)
+                .is_some_and(|&block| block & 0x7F == FLAC_STREAMINFO)
+    })
+}
+
+/// Returns the payload of the first packet of an Ogg stream.
+fn ogg_first_packet(bytes: &[u8]) -> Option<&[u8]> {
+    let header = bytes.get(..OGG_PAGE_HEADER_LEN)?;
+
+    if &header[..4] != OGG_PAGE || header[4] != 0 || header[5] & OGG_BEGINNING_OF_STREAM == 0 {
+        return None;
+    }
+
+    bytes.get(OGG_PAGE_HEADER_LEN + usize::from(header[26])..)
+}
+
+/// An Ogg stream starting with an Opus identification header.
+#[must_use]
+pub fn is_opus(bytes: &[u8]) -> bool {
+    ogg_first_packet(bytes).is_some_and(|packet| packet.starts_with(OPUS_HEAD))
+}
+
+/// An Ogg stream starting with a Vorbis identification header.
+#[must_use]
+pub fn is_vorbis(bytes: &[u8]) -> bool {
+    ogg_first_packet(bytes).is_some_and(|packet| packet.starts_with(VORBIS_IDENTIFICATION))
+}
+
+/// An Ogg stream starting with a Speex header.
+#[must_use]
+pub fn is_speex(bytes: &[u8]) -> bool {
+    ogg_first_packet(bytes).is_some_and(|packet| packet.starts_with(SPEEX_HEADER))
+}
@@ -0,0 +1,154 @@
+use crate::magical::magic::FileKind;
+
+const ZSTD_FRAME: &[u8; 4] = b"\x28\xB5\x2F\xFD";
+const LZ4_FRAME: &[u8; 4] = b"\x04\x22\x4D\x18";
+const LZ4_LEGACY_FRAME: &[u8; 4] = b"\x02\x21\x4C\x18";
+/// Skippable frames, shared by zstd and LZ4: `0x184D2A50` to `0x184D2A5F`, little-endian.
+const SKIPPABLE_FRAME: &[u8; 3] = b"\x2A\x4D\x18";
+/// Magic and frame size.
+const SKIPPABLE_FRAME_HEADER_LEN: usize = 8;
+
+const UNIX_COMPRESS: &[u8; 2] = b"\x1F\x9D";
+/// Flags byte: maximum code width in the low 5 bits, bits 5 and 6 unused.
+const UNIX_COMPRESS_BITS: u8 = 0x1F;
+const UNIX_COMPRESS_UNUSED: u8 = 0x60;
+
+/// `lc`, `lp` and `pb` are packed as `(pb * 5 + lp) * 9 + lc`, each below 9, 5 and 5.
+const LZMA_MAX_PROPERTIES: u8 = 9 * 5 * 5;
+/// `.lzma` files up to 256 GiB, the limit `xz` uses to recognize them.
+const LZMA_MAX_KNOWN_SIZE: u64 = 1 << 38;
+const LZMA_HEADER_LEN: usize = 13;
+
+/// What follows the skippable frames at the start of a zstd or LZ4 stream.
+enum FirstFrame {
+    /// A real frame, of the given kind.
+    Found(FileKind),
+    /// `bytes` ends inside the skippable frames, after a complete header.
+    Truncated,
+    /// Anything else, including an unknown magic after skippable frames.
+    Unknown,
+}
+
+/// Skips the skippable frames at the start of a zstd or LZ4 stream, and reads the magic of
+/// the first real frame.
+fn frame_kind(bytes: &[u8]) -> FirstFrame {
+    /* Only a complete skippable frame header makes a stream worth guessing about. */
+    let ran_out = |offset| {
+        if offset == 0 {
+            FirstFrame::Unknown
+        } else {
+            FirstFrame::Truncated
+        }
+    };
+    let mut offset = 0;
+
+    loop {
+        let Some(frame) = bytes.get(offset..) else {
+            return ran_out(offset);
+        };
+        let Some(magic) = frame.get(..4) else {
+            return ran_out(offset);
+        };
+
+        if magic == ZSTD_FRAME {
+            return FirstFrame::Found(FileKind::Zstd);
+        }
+
+        if magic == LZ4_FRAME || magic == LZ4_LEGACY_FRAME {
+            return FirstFrame::Found(FileKind::Lz4);
+        }
+
+        if magic[0] & 0xF0 != 0x50 || &magic[1..] != SKIPPABLE_FRAME {
+            return FirstFrame::Unknown;
+        }
+
+        let Some(len) = frame
+            .get(4..SKIPPABLE_FRAME_HEADER_LEN)
+            .and_then(|len| len.try_into().ok())
+            .map(u32::from_le_bytes)
+        else {
+            return ran_out(offset);
+        };
+
+        let Some(next) = offset
+            .checked_add(SKIPPABLE_FRAME_HEADER_LEN)
+            .and_then(|offset| offset.checked_add(usize::try_from(len).ok()?))
+        else {
+            return FirstFrame::Truncated;
+        };
+
+        offset = next;
+    }
+}
+
+/// A zstd frame, possibly after skippable frames (the seekable format puts its index in one).
+/// Skippable frames running past `bytes` are taken as zstd, the most common use of them.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::compression::is_zstd;
+///
+/// assert!(is_zstd(b"\x28\xB5\x2F\xFD\x04\x58"));
+/// assert!(is_zstd(b"\x50\x2A\x4D\x18\x02\0\0\0\0\0\x28\xB5\x2F\xFD"));
+/// assert!(is_zstd(b"\x50\x2A\x4D\x18\x20\0\0\0\0\0"));
+/// assert!(!is_zstd(b"\x50\x2A\x4D\x18\x02\0\0\0\0\0\x04\x22\x4D\x18"));
+/// assert!(!is_zstd(b"\x50\x2A\x4D\x18\x02\0\0\0\0\0garbage!"));
+/// ```
+#[must_use]
+pub fn is_zstd(bytes: &[u8]) -> bool {
+    match frame_kind(bytes) {
+        FirstFrame::Found(kind) => kind == FileKind::Zstd,
+        FirstFrame::Truncated => true,
+        FirstFrame::Unknown => false,
+    }
+}
+
+/// An LZ4 frame (or legacy frame), possibly after skippable frames.
+#[must_use]
+pub fn is_lz4(bytes: &[u8]) -> bool {
+    matches!(frame_kind(bytes), FirstFrame::Found(FileKind::Lz4))
+}
+
+/// `1F 9D`, then flags with a code width of 9 to 16 bits.
+#[must_use]
+pub fn is_unix_compress(bytes: &[u8]) -> bool {
+    bytes.starts_with(UNIX_COMPRESS)
+        && bytes.get(2).is_some_and(|&flags| {
+            flags & UNIX_COMPRESS_UNUSED == 0 && (9..=16).contains(&(flags & UNIX_COMPRESS_BITS))
+        })
+}
+
+/// A legacy `.lzma` file (LZMA-Alone), which has no magic: the properties byte, a dictionary
+/// size of `2^n` or `2^n + 2^(n - 1)`, and a sensible uncompressed size (or "unknown").
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::compression::is_lzma;
+///
+/// assert!(is_lzma(b"\x5D\0\0\x80\0\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"));
+/// assert!(!is_lzma(b"\x5D\0\0\x81\0\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"));
+/// ```
+#[must_use]
+pub fn is_lzma(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(..LZMA_HEADER_LEN) else {
+        return false;
+    };
+
+    let dictionary = u32::from_le_bytes([header[1], header[2], header[3], header[4]]);
+    let size = u64::from_le_bytes([
+        header[5], header[6], header[7], header[8], header[9], header[10], header[11], header[12],
+    ]);
+
+    /* `2^n + 2^(n - 1)` is its lowest set bit plus twice that bit. */
+    let lowest = dictionary & dictionary.wrapping_neg();
+    let valid_dictionary = dictionary.is_power_of_two()
+        || dictionary == u32::MAX
+        || dictionary.checked_sub(lowest) == lowest.checked_mul(2);
+
+    header[0] < LZMA_MAX_PROPERTIES
+        && dictionary >= 1 << 12
+        && valid_dictionary
+        && (size == u64::MAX || size < LZMA_MAX_KNOWN_SIZE)
+}
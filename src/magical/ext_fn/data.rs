@@ -0,0 +1,24 @@
+const PARQUET: &[u8; 4] = b"PAR1";
+
+/// Apache Parquet: `PAR1` at the start of the file, and at its very end after the length of
+/// the file metadata.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::data::is_parquet;
+///
+/// let parquet = b"PAR1\x15\x04\x15\x10\x1C\x15\x04\x2A\x01\0\0PAR1";
+///
+/// assert!(is_parquet(parquet, parquet));
+/// assert!(!is_parquet(b"PAR1 some text that is not parquet", b"not parquet"));
+/// assert!(!is_parquet(b"\x15\x04", b"\x2A\x01\0\0PAR1"));
+/// ```
+#[must_use]
+pub fn is_parquet(head: &[u8], tail: &[u8]) -> bool {
+    let Some((length, magic)) = tail.last_chunk::<8>().map(|footer| footer.split_at(4)) else {
+        return false;
+    };
+
+    head.starts_with(PARQUET) && magic == PARQUET && length != [0; 4]
+}
@@ -0,0 +1,94 @@
+const QCOW: &[u8; 4] = b"QFI\xFB";
+
+/// The partition table of a master boot record, and its `55 AA` boot signature.
+const MBR_PARTITION_TABLE: usize = 446;
+const MBR_PARTITION_ENTRY_LEN: usize = 16;
+const MBR_PARTITIONS: usize = 4;
+const MBR_BOOT_SIGNATURE: &[u8; 2] = b"\x55\xAA";
+const MBR_LEN: usize = 512;
+/// The only partition of a protective MBR, in front of a GPT.
+const GPT_PROTECTIVE_PARTITION: u8 = 0xEE;
+
+/// Returns the version of a QEMU copy-on-write image: 1 for `qcow`, 2 or 3 for `qcow2`.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::disk::qcow_version;
+///
+/// assert_eq!(qcow_version(b"QFI\xFB\0\0\0\x03"), Some(3));
+/// assert_eq!(qcow_version(b"QFI\xFB\0\0\0\x09"), None);
+/// ```
+#[must_use]
+pub fn qcow_version(bytes: &[u8]) -> Option<u32> {
+    let version = bytes
+        .get(4..8)
+        .filter(|_| bytes.starts_with(QCOW))?
+        .try_into()
+        .ok()
+        .map(u32::from_be_bytes)?;
+
+    (1..=3).contains(&version).then_some(version)
+}
+
+/// A `qcow2` image, version 2 or 3.
+#[must_use]
+pub fn is_qcow2(bytes: &[u8]) -> bool {
+    matches!(qcow_version(bytes), Some(2 | 3))
+}
+
+/// A master boot record: the `55 AA` boot signature and a partition table whose entries are
+/// either empty or active/inactive partitions of non-zero length.
+///
+/// Protective MBRs, which only exist in front of a GPT, do not count.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::disk::is_mbr;
+///
+/// let mut mbr = [0; 512];
+/// mbr[446..462].copy_from_slice(b"\x80\x20\x21\0\x83\xFE\xFF\xFF\0\x08\0\0\0\xF8\x7F\0");
+/// mbr[510..].copy_from_slice(b"\x55\xAA");
+///
+/// assert!(is_mbr(&mbr));
+///
+/// mbr[446] = 0x12;
+/// assert!(!is_mbr(&mbr));
+/// ```
+#[must_use]
+pub fn is_mbr(bytes: &[u8]) -> bool {
+    let Some(sector) = bytes.get(..MBR_LEN) else {
+        return false;
+    };
+
+    if !sector.ends_with(MBR_BOOT_SIGNATURE) {
+        return false;
+    }
+
+    let entries = sector[MBR_PARTITION_TABLE..]
+        .chunks_exact(MBR_PARTITION_ENTRY_LEN)
+        .take(MBR_PARTITIONS);
+    let mut partitions = 0;
+
+    for entry in entries {
+        let status = entry[0];
+        let partition_type = entry[4];
+        let sectors = u32::from_le_bytes([entry[12], entry[13], entry[14], entry[15]]);
+
+        if partition_type == 0 {
+            continue;
+        }
+
+        if (status != 0x00 && status != 0x80)
+            || sectors == 0
+            || partition_type == GPT_PROTECTIVE_PARTITION
+        {
+            return false;
+        }
+
+        partitions += 1;
+    }
+
+    partitions > 0
+}
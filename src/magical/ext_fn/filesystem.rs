@@ -0,0 +1,171 @@
+use crate::magical::bytes_read::{EXT_SUPERBLOCK_LEN, EXT_SUPERBLOCK_OFFSETS};
+use crate::magical::magic::FileKind;
+
+const EXT_MAGIC: u16 = 0xEF53;
+/// Block sizes go from 1 KiB (`0`) to 64 KiB (`6`).
+const EXT_MAX_LOG_BLOCK_SIZE: u32 = 6;
+const EXT_COMPAT_HAS_JOURNAL: u32 = 0x0004;
+/// `filetype`, `recover` and `meta_bg`: everything ext3 supports.
+const EXT3_INCOMPAT: u32 = 0x0016;
+/// `sparse_super`, `large_file` and `btree_dir`.
+const EXT3_RO_COMPAT: u32 = 0x0007;
+
+/// The jump to the boot code, then the BIOS parameter block.
+const FAT_BOOT_SECTOR_LEN: usize = 512;
+const FAT_BOOT_SIGNATURE: &[u8; 2] = b"\x55\xAA";
+/// The largest cluster counts of FAT12 and FAT16, which is what tells them apart.
+const FAT12_MAX_CLUSTERS: u32 = 4084;
+const FAT16_MAX_CLUSTERS: u32 = 65524;
+const FAT_DIRECTORY_ENTRY_LEN: u32 = 32;
+
+fn u16_le(bytes: &[u8], offset: usize) -> u32 {
+    u32::from(u16::from_le_bytes([bytes[offset], bytes[offset + 1]]))
+}
+
+fn u32_le(bytes: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes([
+        bytes[offset],
+        bytes[offset + 1],
+        bytes[offset + 2],
+        bytes[offset + 3],
+    ])
+}
+
+/// Tells ext2, ext3 and ext4 apart by the feature flags of the superblock, like `blkid`:
+/// ext4 uses a feature ext3 lacks, ext3 has a journal, ext2 has neither.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::filesystem::ext_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let mut image = vec![0; 2048];
+/// image[1080..1082].copy_from_slice(&[0x53, 0xEF]);
+/// assert_eq!(ext_kind(&image), Some(FileKind::Ext2));
+///
+/// image[1116] = 0x04; // has_journal
+/// assert_eq!(ext_kind(&image), Some(FileKind::Ext3));
+///
+/// image[1120] = 0x40; // extents
+/// assert_eq!(ext_kind(&image), Some(FileKind::Ext4));
+/// ```
+#[must_use]
+pub fn ext_kind(bytes: &[u8]) -> Option<FileKind> {
+    let superblock = EXT_SUPERBLOCK_OFFSETS
+        .iter()
+        .find_map(|&offset| bytes.get(offset..offset + EXT_SUPERBLOCK_LEN))?;
+
+    if u16_le(superblock, 0x38) != u32::from(EXT_MAGIC)
+        || u32_le(superblock, 0x18) > EXT_MAX_LOG_BLOCK_SIZE
+    {
+        return None;
+    }
+
+    let compat = u32_le(superblock, 0x5C);
+    let incompat = u32_le(superblock, 0x60);
+    let ro_compat = u32_le(superblock, 0x64);
+
+    if incompat & !EXT3_INCOMPAT != 0 || ro_compat & !EXT3_RO_COMPAT != 0 {
+        Some(FileKind::Ext4)
+    } else if compat & EXT_COMPAT_HAS_JOURNAL != 0 {
+        Some(FileKind::Ext3)
+    } else {
+        Some(FileKind::Ext2)
+    }
+}
+
+#[must_use]
+pub fn is_ext2(bytes: &[u8]) -> bool {
+    ext_kind(bytes) == Some(FileKind::Ext2)
+}
+
+#[must_use]
+pub fn is_ext3(bytes: &[u8]) -> bool {
+    ext_kind(bytes) == Some(FileKind::Ext3)
+}
+
+#[must_use]
+pub fn is_ext4(bytes: &[u8]) -> bool {
+    ext_kind(bytes) == Some(FileKind::Ext4)
+}
+
+/// Reads the BIOS parameter block of a FAT boot sector, and tells FAT12, FAT16 and FAT32
+/// apart by their number of clusters, as the specification does.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::filesystem::fat_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// // A 1.44 MB floppy.
+/// let mut floppy = b"\xEB\x3C\x90MSDOS5.0\0\x02\x01\x01\0\x02\xE0\0\x40\x0B\xF0\x09\0".to_vec();
+/// floppy.resize(510, 0);
+/// floppy.extend_from_slice(b"\x55\xAA");
+///
+/// assert_eq!(fat_kind(&floppy), Some(FileKind::Fat12));
+/// assert_eq!(fat_kind(&floppy[..511]), None);
+/// ```
+#[must_use]
+pub fn fat_kind(bytes: &[u8]) -> Option<FileKind> {
+    let sector = bytes.get(..FAT_BOOT_SECTOR_LEN)?;
+
+    let jump = matches!(sector, [0xEB, _, 0x90, ..] | [0xE9, ..]);
+    let bytes_per_sector = u16_le(sector, 11);
+    let sectors_per_cluster = u32::from(sector[13]);
+    let reserved_sectors = u16_le(sector, 14);
+    let fats = u32::from(sector[16]);
+
+    if !jump
+        || !sector.ends_with(FAT_BOOT_SIGNATURE)
+        || !matches!(bytes_per_sector, 512 | 1024 | 2048 | 4096)
+        || !sectors_per_cluster.is_power_of_two()
+        || reserved_sectors == 0
+        || !matches!(fats, 1 | 2)
+    {
+        return None;
+    }
+
+    let root_entries = u16_le(sector, 17);
+    let total_sectors = match u16_le(sector, 19) {
+        0 => u32_le(sector, 32),
+        sectors => sectors,
+    };
+    let fat_sectors = match u16_le(sector, 22) {
+        0 => u32_le(sector, 36),
+        sectors => sectors,
+    };
+    let root_sectors = (root_entries * FAT_DIRECTORY_ENTRY_LEN).div_ceil(bytes_per_sector);
+
+    let data_sectors = total_sectors
+        .checked_sub(reserved_sectors)?
+        .checked_sub(fats.checked_mul(fat_sectors)?)?
+        .checked_sub(root_sectors)?;
+    let clusters = data_sectors / sectors_per_cluster;
+
+    if fat_sectors == 0 || clusters == 0 {
+        None
+    } else if clusters <= FAT12_MAX_CLUSTERS {
+        Some(FileKind::Fat12)
+    } else if clusters <= FAT16_MAX_CLUSTERS {
+        Some(FileKind::Fat16)
+    } else {
+        Some(FileKind::Fat32)
+    }
+}
+
+#[must_use]
+pub fn is_fat12(bytes: &[u8]) -> bool {
+    fat_kind(bytes) == Some(FileKind::Fat12)
+}
+
+#[must_use]
+pub fn is_fat16(bytes: &[u8]) -> bool {
+    fat_kind(bytes) == Some(FileKind::Fat16)
+}
+
+#[must_use]
+pub fn is_fat32(bytes: &[u8]) -> bool {
+    fat_kind(bytes) == Some(FileKind::Fat32)
+}
@@ -0,0 +1,141 @@
+use crate::magical::magic::FileKind;
+
+const FTYP: &[u8; 4] = b"ftyp";
+/// Box size, box type, major brand and minor version.
+const FTYP_HEADER_LEN: usize = 16;
+/// Real `ftyp` boxes list a handful of brands, anything bigger is not one.
+const MAX_FTYP_LEN: usize = 4096;
+
+/// Maps a brand to the kind it implies.
+///
+/// `Mp4` and `Heif` are generic: they are only kept if no other brand is more specific.
+const fn brand_kind(brand: &[u8]) -> Option<FileKind> {
+    match brand {
+        b"avif" | b"avis" => Some(FileKind::Avif),
+        b"heic" | b"heix" | b"heim" | b"heis" | b"hevc" | b"hevx" | b"hevm" | b"hevs" => {
+            Some(FileKind::Heic)
+        }
+        b"mif1" | b"msf1" | b"mif2" | b"miaf" => Some(FileKind::Heif),
+        b"qt  " => Some(FileKind::QuickTime),
+        b"M4A " | b"M4B " | b"M4P " => Some(FileKind::M4a),
+        b"M4V " | b"M4VH" | b"M4VP" => Some(FileKind::M4v),
+        [b'3', b'g', b'2', _] | b"KDDI" => Some(FileKind::ThreeGpp2),
+        [b'3', b'g', b'p' | b'e' | b'g' | b's' | b'r', _] => Some(FileKind::ThreeGpp),
+        b"isom" | b"iso2" | b"iso3" | b"iso4" | b"iso5" | b"iso6" | b"iso7" | b"iso8" | b"iso9"
+        | b"mp41" | b"mp42" | b"mp71" | b"avc1" | b"dash" | b"MSNV" | b"NDAS" | b"NDSC"
+        | b"NDSH" | b"NDSM" | b"NDSP" | b"NDSS" | b"NDXC" | b"NDXH" | b"NDXM" | b"NDXP"
+        | b"NDXS" | b"F4V " | b"F4P " | b"mmp4" | b"XAVC" => Some(FileKind::Mp4),
+        _ => None,
+    }
+}
+
+const fn is_generic(kind: FileKind) -> bool {
+    matches!(kind, FileKind::Mp4 | FileKind::Heif)
+}
+
+/// Reads the `ftyp` box at the start of an ISO base media file (MP4, `QuickTime`, HEIF, 3GPP...)
+/// and maps its brands to a [`FileKind`].
+///
+/// The major brand wins if it is specific (`heic`, `qt  `, `M4A `...). Otherwise, the first
+/// specific compatible brand wins, so an `mif1` file listing `avif` is an AVIF image.
+/// Generic brands (`isom`, `mp42`, `mif1`...) are used as a fallback.
+///
+/// Returns [`None`] if `bytes` does not start with an `ftyp` box, or if no brand is known.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::isobmff::isobmff_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let heic = b"\x00\x00\x00\x18ftypheic\x00\x00\x00\x00mif1heic";
+/// let avif = b"\x00\x00\x00\x18ftypmif1\x00\x00\x00\x00mif1avif";
+/// let mp4 = b"\x00\x00\x00\x18ftypisom\x00\x00\x02\x00isomiso2";
+///
+/// assert_eq!(isobmff_kind(heic), Some(FileKind::Heic));
+/// assert_eq!(isobmff_kind(avif), Some(FileKind::Avif));
+/// assert_eq!(isobmff_kind(mp4), Some(FileKind::Mp4));
+/// assert_eq!(isobmff_kind(b"\x00\x00\x00\x18moov"), None);
+/// ```
+#[must_use]
+pub fn isobmff_kind(bytes: &[u8]) -> Option<FileKind> {
+    if bytes.get(4..8) != Some(FTYP) {
+        return None;
+    }
+
+    let box_len = match bytes.get(0..4) {
+        Some([a, b, c, d]) => u32::from_be_bytes([*a, *b, *c, *d]) as usize,
+        _ => return None,
+    };
+
+    if !(FTYP_HEADER_LEN..=MAX_FTYP_LEN).contains(&box_len) || box_len % 4 != 0 {
+        return None;
+    }
+
+    let major = bytes.get(8..12).and_then(brand_kind);
+
+    if let Some(kind) = major
+        && !is_generic(kind)
+    {
+        return Some(kind);
+    }
+
+    /* The box may be cut short by the end of the header. */
+    let brands = &bytes[FTYP_HEADER_LEN.min(bytes.len())..box_len.min(bytes.len())];
+    let mut generic = major;
+
+    for brand in brands.chunks_exact(4) {
+        match brand_kind(brand) {
+            Some(kind) if !is_generic(kind) => return Some(kind),
+            Some(kind) if generic.is_none() => generic = Some(kind),
+            _ => {}
+        }
+    }
+
+    generic
+}
+
+#[must_use]
+pub fn is_mp4(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::Mp4)
+}
+
+#[must_use]
+pub fn is_m4v(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::M4v)
+}
+
+#[must_use]
+pub fn is_m4a(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::M4a)
+}
+
+#[must_use]
+pub fn is_quicktime(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::QuickTime)
+}
+
+#[must_use]
+pub fn is_heic(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::Heic)
+}
+
+#[must_use]
+pub fn is_heif(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::Heif)
+}
+
+#[must_use]
+pub fn is_avif(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::Avif)
+}
+
+#[must_use]
+pub fn is_3gpp(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::ThreeGpp)
+}
+
+#[must_use]
+pub fn is_3gpp2(bytes: &[u8]) -> bool {
+    isobmff_kind(bytes) == Some(FileKind::ThreeGpp2)
+}
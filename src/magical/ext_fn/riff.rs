@@ -0,0 +1,212 @@
+use crate::magical::magic::FileKind;
+
+const RIFF: &[u8; 4] = b"RIFF";
+const RIFX: &[u8; 4] = b"RIFX";
+const RF64: &[u8; 4] = b"RF64";
+const DS64: &[u8; 4] = b"ds64";
+
+/// Chunk id and chunk size.
+const CHUNK_HEADER_LEN: usize = 8;
+const FORM_TYPE_LEN: usize = 4;
+/// RF64 files put `0xFFFFFFFF` in the 32 bits size and the real one in the `ds64` chunk.
+const RF64_SIZE_IN_DS64: u32 = u32::MAX;
+/// `ds64` holds the 64 bits RIFF size, data size and sample count.
+const MIN_DS64_LEN: u32 = 24;
+
+/// The container flavour, from the id of the outer chunk.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RiffContainer {
+    /// `RIFF`, little-endian sizes.
+    Riff,
+    /// `RIFX`, big-endian sizes.
+    Rifx,
+    /// `RF64`, the 64 bits extension used by broadcast WAV files.
+    Rf64,
+}
+
+/// The header of a RIFF, RIFX or RF64 file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiffHeader {
+    pub container: RiffContainer,
+    /// The four bytes after the outer chunk header: `WAVE`, `AVI `, `WEBP`...
+    pub form_type: [u8; 4],
+    /// The length of the whole file declared by the outer chunk, its 8 bytes header included.
+    pub declared_len: u64,
+}
+
+impl RiffHeader {
+    /// Reads the outer chunk header at the start of `bytes`.
+    ///
+    /// Returns [`None`] if `bytes` does not start with a RIFF, RIFX or RF64 chunk, or if the
+    /// declared chunk size cannot hold the form type and at least one chunk.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::ext_fn::riff::{RiffContainer, RiffHeader};
+    ///
+    /// let header = RiffHeader::parse(b"RIFF\x24\x08\0\0WAVEfmt ").unwrap();
+    ///
+    /// assert_eq!(header.container, RiffContainer::Riff);
+    /// assert_eq!(&header.form_type, b"WAVE");
+    /// assert_eq!(header.declared_len, 0x82C);
+    /// assert!(RiffHeader::parse(b"RIFF\x04\0\0\0WAVE").is_none());
+    /// ```
+    #[must_use]
+    pub fn parse(bytes: &[u8]) -> Option<Self> {
+        let (Some(id), Some(size), Some(form_type)) =
+            (bytes.get(0..4), bytes.get(4..8), bytes.get(8..12))
+        else {
+            return None;
+        };
+
+        let size: [u8; 4] = size.try_into().ok()?;
+        let form_type: [u8; 4] = form_type.try_into().ok()?;
+
+        let (container, size) = match id {
+            id if id == RIFF => (RiffContainer::Riff, u64::from(u32::from_le_bytes(size))),
+            id if id == RIFX => (RiffContainer::Rifx, u64::from(u32::from_be_bytes(size))),
+            id if id == RF64 => {
+                let size = u32::from_le_bytes(size);
+
+                if size == RF64_SIZE_IN_DS64 {
+                    (RiffContainer::Rf64, ds64_riff_size(bytes)?)
+                } else {
+                    (RiffContainer::Rf64, u64::from(size))
+                }
+            }
+            _ => return None,
+        };
+
+        if size <= FORM_TYPE_LEN as u64 {
+            return None;
+        }
+
+        Some(Self {
+            container,
+            form_type,
+            declared_len: size.checked_add(CHUNK_HEADER_LEN as u64)?,
+        })
+    }
+
+    /// Whether a file of `len` bytes is shorter than the header declares.
+    ///
+    /// Trailing bytes past the declared length are not an error: some writers pad files,
+    /// or append tags after the RIFF chunk.
+    #[must_use]
+    pub const fn is_truncated(&self, len: u64) -> bool {
+        len < self.declared_len
+    }
+
+    /// Maps the form type to a [`FileKind`].
+    ///
+    /// WebP only exists as a little-endian `RIFF` file.
+    #[must_use]
+    pub fn kind(&self) -> Option<FileKind> {
+        match &self.form_type {
+            b"WAVE" => Some(FileKind::Wav),
+            b"AVI " => Some(FileKind::Avi),
+            b"ACON" => Some(FileKind::AniCursor),
+            b"RMID" => Some(FileKind::Rmi),
+            b"QLCM" => Some(FileKind::Qcp),
+            b"WEBP" if self.container == RiffContainer::Riff => Some(FileKind::WEBP),
+            [b'C' | b'c', b'D' | b'd', b'R' | b'r', _] => Some(FileKind::CorelDraw),
+            _ => None,
+        }
+    }
+}
+
+/// Reads the 64 bits RIFF size from the `ds64` chunk, which must follow the form type.
+fn ds64_riff_size(bytes: &[u8]) -> Option<u64> {
+    let start = CHUNK_HEADER_LEN + FORM_TYPE_LEN;
+
+    if bytes.get(start..start + 4)? != DS64 {
+        return None;
+    }
+
+    let len = u32::from_le_bytes(bytes.get(start + 4..start + 8)?.try_into().ok()?);
+
+    if len < MIN_DS64_LEN {
+        return None;
+    }
+
+    Some(u64::from_le_bytes(
+        bytes.get(start + 8..start + 16)?.try_into().ok()?,
+    ))
+}
+
+/// Tells RIFF based formats apart by their form type.
+///
+/// Accepts `RIFF`, big-endian `RIFX` and 64 bits `RF64` containers. The declared chunk size
+/// must at least hold the form type, but is not checked against `bytes`, which is usually
+/// only the head of the file; use [`is_truncated`] with the whole file for that.
+///
+/// Returns [`None`] if `bytes` is not a RIFF file, or if the form type is unknown.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::riff::riff_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// assert_eq!(riff_kind(b"RIFF\x24\x08\0\0WAVEfmt "), Some(FileKind::Wav));
+/// assert_eq!(riff_kind(b"RIFX\0\0\x08\x24WAVEfmt "), Some(FileKind::Wav));
+/// assert_eq!(riff_kind(b"RIFF\xF0\0\0\0AVI LIST"), Some(FileKind::Avi));
+/// assert_eq!(riff_kind(b"RIFF\xF0\0\0\0MAHO"), None);
+/// ```
+#[must_use]
+pub fn riff_kind(bytes: &[u8]) -> Option<FileKind> {
+    RiffHeader::parse(bytes)?.kind()
+}
+
+/// Whether `bytes`, the whole file, is a RIFF file shorter than its header declares.
+///
+/// Returns `false` if `bytes` is not a RIFF file.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::riff::is_truncated;
+///
+/// assert!(is_truncated(b"RIFF\x24\x08\0\0WAVEfmt "));
+/// assert!(!is_truncated(b"RIFF\x08\0\0\0WAVEdata"));
+/// ```
+#[must_use]
+pub fn is_truncated(bytes: &[u8]) -> bool {
+    RiffHeader::parse(bytes).is_some_and(|header| header.is_truncated(bytes.len() as u64))
+}
+
+#[must_use]
+pub fn is_wav(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::Wav)
+}
+
+#[must_use]
+pub fn is_avi(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::Avi)
+}
+
+#[must_use]
+pub fn is_ani_cursor(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::AniCursor)
+}
+
+#[must_use]
+pub fn is_webp(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::WEBP)
+}
+
+#[must_use]
+pub fn is_rmi(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::Rmi)
+}
+
+#[must_use]
+pub fn is_qcp(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::Qcp)
+}
+
+#[must_use]
+pub fn is_corel_draw(bytes: &[u8]) -> bool {
+    riff_kind(bytes) == Some(FileKind::CorelDraw)
+}
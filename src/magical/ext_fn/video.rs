@@ -0,0 +1,175 @@
+const EBML_HEADER: u32 = 0x1A45_DFA3;
+const EBML_DOC_TYPE: u32 = 0x4282;
+const WEBM_DOC_TYPE: &[u8] = b"webm";
+/// Element ids are at most 4 bytes long, sizes at most 8.
+const EBML_MAX_ID_LEN: usize = 4;
+const EBML_MAX_SIZE_LEN: usize = 8;
+
+const TS_SYNC_BYTE: u8 = 0x47;
+/// Packet stride and offset of the sync byte in a packet: plain 188 bytes packets, M2TS
+/// packets with a 4 bytes timestamp prefix, and packets with 16 bytes of Reed-Solomon data.
+const TS_PACKET_LAYOUTS: &[(usize, usize)] = &[(188, 0), (192, 4), (204, 0)];
+/// A single `G` is not much of a signature.
+const MIN_TS_PACKETS: usize = 3;
+/// Packets checked at most, so that whatever follows them does not matter.
+const MAX_TS_PACKETS: usize = 8;
+
+const PACK_START_CODE: &[u8; 4] = b"\x00\x00\x01\xBA";
+
+const FLV: &[u8; 3] = b"FLV";
+const FLV_VERSION: u8 = 1;
+/// Only the audio (`0x04`) and video (`0x01`) flags are defined, the other bits are zero.
+const FLV_RESERVED_FLAGS: u8 = !0x05;
+/// Signature, version, flags and the offset of the body.
+const FLV_HEADER_LEN: usize = 9;
+
+/// Reads an element id, its length marker included.
+fn ebml_id(bytes: &[u8]) -> Option<(u32, usize)> {
+    let len = bytes.first()?.leading_zeros() as usize + 1;
+
+    if len > EBML_MAX_ID_LEN {
+        return None;
+    }
+
+    let id = bytes
+        .get(..len)?
+        .iter()
+        .fold(0, |id, &byte| (id << 8) | u32::from(byte));
+
+    Some((id, len))
+}
+
+/// Reads an element size, without its length marker.
+fn ebml_size(bytes: &[u8]) -> Option<(u64, usize)> {
+    let first = *bytes.first()?;
+    let len = first.leading_zeros() as usize + 1;
+
+    if len > EBML_MAX_SIZE_LEN {
+        return None;
+    }
+
+    let marker = u64::from(first) & (0xFF >> len);
+    let size = bytes
+        .get(1..len)?
+        .iter()
+        .fold(marker, |size, &byte| (size << 8) | u64::from(byte));
+
+    Some((size, len))
+}
+
+/// Returns the `DocType` of the EBML header at the start of `bytes`: `matroska`, `webm`...
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::video::ebml_doc_type;
+///
+/// let webm = b"\x1A\x45\xDF\xA3\x8F\x42\x86\x81\x01\x42\x82\x84webm\x42\x87\x81\x04";
+///
+/// assert_eq!(ebml_doc_type(webm), Some(&b"webm"[..]));
+/// assert_eq!(ebml_doc_type(&webm[..12]), None);
+/// ```
+#[must_use]
+pub fn ebml_doc_type(bytes: &[u8]) -> Option<&[u8]> {
+    let (id, id_len) = ebml_id(bytes)?;
+
+    if id != EBML_HEADER {
+        return None;
+    }
+
+    let (size, size_len) = ebml_size(bytes.get(id_len..)?)?;
+    let start = id_len + size_len;
+    let end = usize::try_from(size)
+        .map_or(bytes.len(), |size| start.saturating_add(size))
+        .min(bytes.len());
+
+    let mut offset = start;
+
+    while offset < end {
+        let (id, id_len) = ebml_id(&bytes[offset..end])?;
+        let (size, size_len) = ebml_size(bytes.get(offset + id_len..end)?)?;
+        let data = offset + id_len + size_len;
+        let next = data.checked_add(usize::try_from(size).ok()?)?;
+
+        if id == EBML_DOC_TYPE {
+            /* Strings may be padded with zeros. */
+            let doc_type = bytes.get(data..next)?;
+            let len = doc_type
+                .iter()
+                .rposition(|&byte| byte != 0)
+                .map_or(0, |i| i + 1);
+
+            return Some(&doc_type[..len]);
+        }
+
+        offset = next;
+    }
+
+    None
+}
+
+/// An EBML file whose `DocType` is `webm`.
+#[must_use]
+pub fn is_webm(bytes: &[u8]) -> bool {
+    ebml_doc_type(bytes) == Some(WEBM_DOC_TYPE)
+}
+
+/// Any other EBML file: `DocType` `matroska`, or a header too long for `bytes`.
+#[must_use]
+pub fn is_matroska(bytes: &[u8]) -> bool {
+    ebml_id(bytes).is_some_and(|(id, _)| id == EBML_HEADER) && !is_webm(bytes)
+}
+
+/// An MPEG transport stream: the `0x47` sync byte at the start of the first packets, with
+/// packets of 188, 192 (M2TS) or 204 bytes.
+///
+/// Only the first 8 complete packets are checked; a packet cut short by the end of
+/// `bytes` is not.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::video::is_mpeg_ts;
+///
+/// let mut ts = [0; 188 * 4];
+/// ts.chunks_mut(188).for_each(|packet| packet[0] = 0x47);
+///
+/// assert!(is_mpeg_ts(&ts));
+/// assert!(!is_mpeg_ts(&ts[..188 * 2 + 100]));
+///
+/// let mut long = ts.repeat(2);
+/// long.resize(188 * 20, 0);
+/// assert!(is_mpeg_ts(&long));
+/// ```
+#[must_use]
+pub fn is_mpeg_ts(bytes: &[u8]) -> bool {
+    TS_PACKET_LAYOUTS.iter().any(|&(stride, sync)| {
+        let mut packets = bytes.chunks_exact(stride).take(MAX_TS_PACKETS);
+
+        packets.len() >= MIN_TS_PACKETS && packets.all(|packet| packet[sync] == TS_SYNC_BYTE)
+    })
+}
+
+/// An MPEG program stream: a pack header, with the marker bits of MPEG-1 or MPEG-2.
+#[must_use]
+pub fn is_mpeg_ps(bytes: &[u8]) -> bool {
+    bytes.starts_with(PACK_START_CODE)
+        && bytes
+            .get(4)
+            .is_some_and(|&byte| byte & 0xC4 == 0x44 || byte & 0xF1 == 0x21)
+}
+
+/// `FLV`, version 1, no reserved flag set, and a body offset past the header.
+#[must_use]
+pub fn is_flash_video(bytes: &[u8]) -> bool {
+    let Some(header) = bytes.get(..FLV_HEADER_LEN) else {
+        return false;
+    };
+
+    let body = u32::from_be_bytes([header[5], header[6], header[7], header[8]]);
+
+    header.starts_with(FLV)
+        && header[3] == FLV_VERSION
+        && header[4] & FLV_RESERVED_FLAGS == 0
+        && body as usize >= FLV_HEADER_LEN
+}
@@ -1,24 +1,3 @@
-const RIFF: &[u8; 4] = b"RIFF";
-const WEBP: &[u8; 4] = b"WEBP";
+//! Kept for compatibility, WebP is detected along with the other RIFF formats.
 
-#[must_use]
-pub fn is_webp(bytes: &[u8]) -> bool {
-    if bytes.len() < 12 {
-        return false;
-    }
-
-    if &bytes[0..4] != RIFF {
-        return false;
-    }
-
-    if &bytes[8..12] != WEBP {
-        return false;
-    }
-
-    let file_size = match bytes.get(4..8) {
-        Some([a, b, c, d]) => u32::from_le_bytes([*a, *b, *c, *d]) as usize,
-        _ => return false,
-    };
-
-    file_size > 4
-}
+pub use crate::magical::ext_fn::riff::is_webp;
@@ -0,0 +1,287 @@
+use crate::magical::magic::FileKind;
+
+const LOCAL_FILE_HEADER: &[u8; 4] = b"PK\x03\x04";
+const CENTRAL_DIRECTORY_HEADER: &[u8; 4] = b"PK\x01\x02";
+const END_OF_CENTRAL_DIRECTORY: &[u8; 4] = b"PK\x05\x06";
+
+const LOCAL_FILE_HEADER_LEN: usize = 30;
+const CENTRAL_DIRECTORY_HEADER_LEN: usize = 46;
+const END_OF_CENTRAL_DIRECTORY_LEN: usize = 22;
+/// The end of central directory record may be followed by a comment of up to 64 KiB.
+const MAX_COMMENT_LEN: usize = u16::MAX as usize;
+
+/// General purpose flag: sizes are in a data descriptor after the data, not in the header.
+const FLAG_DATA_DESCRIPTOR: u16 = 1 << 3;
+const METHOD_STORED: u16 = 0;
+/// Sizes that do not fit 32 bits live in a ZIP64 extra field.
+const ZIP64_SIZE: u32 = u32::MAX;
+
+fn read_u16(bytes: &[u8], offset: usize) -> Option<u16> {
+    match bytes.get(offset..offset.checked_add(2)?) {
+        Some([a, b]) => Some(u16::from_le_bytes([*a, *b])),
+        _ => None,
+    }
+}
+
+fn read_u32(bytes: &[u8], offset: usize) -> Option<u32> {
+    match bytes.get(offset..offset.checked_add(4)?) {
+        Some([a, b, c, d]) => Some(u32::from_le_bytes([*a, *b, *c, *d])),
+        _ => None,
+    }
+}
+
+/// What the entry names of an archive tell about it.
+#[derive(Default)]
+#[allow(clippy::struct_excessive_bools)]
+struct Entries {
+    /// From the stored `mimetype` entry of ODF documents and EPUBs.
+    mimetype: Option<FileKind>,
+    vsix: bool,
+    nupkg: bool,
+    word: bool,
+    excel: bool,
+    powerpoint: bool,
+    android: bool,
+    mozilla: bool,
+    java: bool,
+}
+
+impl Entries {
+    fn add(&mut self, name: &[u8]) {
+        match name {
+            b"extension.vsixmanifest" => self.vsix = true,
+            b"AndroidManifest.xml" | b"classes.dex" | b"resources.arsc" => self.android = true,
+            b"install.rdf" | b"chrome.manifest" => self.mozilla = true,
+            _ if name.starts_with(b"word/") => self.word = true,
+            _ if name.starts_with(b"xl/") => self.excel = true,
+            _ if name.starts_with(b"ppt/") => self.powerpoint = true,
+            _ if name.ends_with(b".nuspec") && !name.contains(&b'/') => self.nupkg = true,
+            _ if name.len() > 9 && name[..9].eq_ignore_ascii_case(b"META-INF/") => {
+                let name = &name[9..];
+
+                if name.eq_ignore_ascii_case(b"MANIFEST.MF") {
+                    self.java = true;
+                } else if name.len() > 8 && name[..8].eq_ignore_ascii_case(b"mozilla.") {
+                    self.mozilla = true;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// The most specific kind the entries point to.
+    /// APKs and signed XPIs carry a JAR manifest, so they are checked first.
+    fn kind(&self) -> Option<FileKind> {
+        if self.mimetype.is_some() {
+            return self.mimetype;
+        }
+
+        let kinds = [
+            (self.vsix, FileKind::Vsix),
+            (self.nupkg, FileKind::Nupkg),
+            (self.word, FileKind::Docx),
+            (self.excel, FileKind::Xlsx),
+            (self.powerpoint, FileKind::Pptx),
+            (self.android, FileKind::Apk),
+            (self.mozilla, FileKind::Xpi),
+            (self.java, FileKind::Jar),
+        ];
+
+        kinds
+            .iter()
+            .find(|(found, _)| *found)
+            .map(|&(_, kind)| kind)
+    }
+}
+
+/// Maps the content of a `mimetype` entry to a kind.
+fn mimetype_kind(content: &[u8]) -> Option<FileKind> {
+    match content.trim_ascii() {
+        b"application/epub+zip" => Some(FileKind::Epub),
+        b"application/vnd.oasis.opendocument.text" => Some(FileKind::OpenDocumentText),
+        b"application/vnd.oasis.opendocument.spreadsheet" => {
+            Some(FileKind::OpenDocumentSpreadsheet)
+        }
+        b"application/vnd.oasis.opendocument.presentation" => {
+            Some(FileKind::OpenDocumentPresentation)
+        }
+        _ => None,
+    }
+}
+
+/// Walks the local file headers from the start of the archive, as far as `bytes` goes.
+/// Stops at the first entry whose size is only known after its data.
+fn scan_local_headers(bytes: &[u8], entries: &mut Entries) -> Option<()> {
+    let mut offset = 0;
+
+    while bytes
+        .get(offset..)
+        .is_some_and(|rest| rest.starts_with(LOCAL_FILE_HEADER))
+    {
+        let flags = read_u16(bytes, offset.checked_add(6)?)?;
+        let method = read_u16(bytes, offset.checked_add(8)?)?;
+        let size = read_u32(bytes, offset.checked_add(18)?)?;
+        let name_len = read_u16(bytes, offset.checked_add(26)?)?;
+        let extra_len = read_u16(bytes, offset.checked_add(28)?)?;
+
+        let name_start = offset.checked_add(LOCAL_FILE_HEADER_LEN)?;
+        let name_end = name_start.checked_add(usize::from(name_len))?;
+        let name = bytes.get(name_start..name_end)?;
+
+        /* `size` can be close to `u32::MAX`, past `usize::MAX` on 32-bit targets. */
+        let data_start = name_end.checked_add(usize::from(extra_len))?;
+        let data_end = data_start.checked_add(usize::try_from(size).ok()?)?;
+
+        if name == b"mimetype" && method == METHOD_STORED {
+            entries.mimetype = bytes.get(data_start..data_end).and_then(mimetype_kind);
+        }
+
+        entries.add(name);
+
+        if (flags & FLAG_DATA_DESCRIPTOR != 0 && size == 0) || size == ZIP64_SIZE {
+            return None;
+        }
+
+        offset = data_end;
+    }
+
+    Some(())
+}
+
+/// Finds the end of central directory record, when `bytes` holds the whole archive,
+/// and walks the central directory it points to.
+fn scan_central_directory(bytes: &[u8], entries: &mut Entries) -> Option<()> {
+    let last = bytes.len().checked_sub(END_OF_CENTRAL_DIRECTORY_LEN)?;
+    let first = last.saturating_sub(MAX_COMMENT_LEN);
+
+    let end = (first..=last)
+        .rev()
+        .find(|&offset| bytes[offset..].starts_with(END_OF_CENTRAL_DIRECTORY))?;
+
+    let count = read_u16(bytes, end.checked_add(10)?)?;
+    let mut offset = usize::try_from(read_u32(bytes, end.checked_add(16)?)?).ok()?;
+
+    for _ in 0..count {
+        if offset >= end
+            || !bytes
+                .get(offset..)
+                .is_some_and(|rest| rest.starts_with(CENTRAL_DIRECTORY_HEADER))
+        {
+            return None;
+        }
+
+        let name_len = read_u16(bytes, offset.checked_add(28)?)?;
+        let extra_len = read_u16(bytes, offset.checked_add(30)?)?;
+        let comment_len = read_u16(bytes, offset.checked_add(32)?)?;
+
+        let name_start = offset.checked_add(CENTRAL_DIRECTORY_HEADER_LEN)?;
+        let name_end = name_start.checked_add(usize::from(name_len))?;
+
+        entries.add(bytes.get(name_start..name_end)?);
+
+        offset = name_end
+            .checked_add(usize::from(extra_len))?
+            .checked_add(usize::from(comment_len))?;
+    }
+
+    Some(())
+}
+
+/// Tells ZIP based formats apart by the entries of the archive.
+///
+/// Reads the local file headers from the start of `bytes` (and the stored `mimetype`
+/// entry of ODF documents and EPUBs), then, if `bytes` holds the whole archive, its
+/// central directory. Nothing is decompressed.
+///
+/// Returns [`None`] if `bytes` is not a ZIP archive, or a generic one.
+///
+/// Every `is_*` function of this module runs its own scan, so the built-in rules scan a
+/// generic ZIP once per ZIP based kind before falling back to [`FileKind::PkgZip`]. Each scan
+/// stops at `max_bytes_read` bytes; call this function once to get the kind directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::ext_fn::zip::zip_kind;
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let mut epub = b"PK\x03\x04\x0A\0\0\0\0\0\0\0\0\0\0\0\0\0\x14\0\0\0\x14\0\0\0\x08\0\0\0".to_vec();
+/// epub.extend_from_slice(b"mimetypeapplication/epub+zip");
+///
+/// assert_eq!(zip_kind(&epub), Some(FileKind::Epub));
+/// assert_eq!(zip_kind(b"PK\x03\x04"), None);
+/// ```
+#[must_use]
+pub fn zip_kind(bytes: &[u8]) -> Option<FileKind> {
+    if !bytes.starts_with(LOCAL_FILE_HEADER) {
+        return None;
+    }
+
+    let mut entries = Entries::default();
+
+    /* Either scan stops early on a truncated or odd archive, keeping what it found. */
+    scan_local_headers(bytes, &mut entries);
+    scan_central_directory(bytes, &mut entries);
+
+    entries.kind()
+}
+
+#[must_use]
+pub fn is_docx(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Docx)
+}
+
+#[must_use]
+pub fn is_xlsx(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Xlsx)
+}
+
+#[must_use]
+pub fn is_pptx(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Pptx)
+}
+
+#[must_use]
+pub fn is_open_document_text(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::OpenDocumentText)
+}
+
+#[must_use]
+pub fn is_open_document_spreadsheet(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::OpenDocumentSpreadsheet)
+}
+
+#[must_use]
+pub fn is_open_document_presentation(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::OpenDocumentPresentation)
+}
+
+#[must_use]
+pub fn is_epub(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Epub)
+}
+
+#[must_use]
+pub fn is_jar(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Jar)
+}
+
+#[must_use]
+pub fn is_apk(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Apk)
+}
+
+#[must_use]
+pub fn is_xpi(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Xpi)
+}
+
+#[must_use]
+pub fn is_nupkg(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Nupkg)
+}
+
+#[must_use]
+pub fn is_vsix(bytes: &[u8]) -> bool {
+    zip_kind(bytes) == Some(FileKind::Vsix)
+}
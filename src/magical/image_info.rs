@@ -0,0 +1,502 @@
+use crate::magical::ext_fn::riff::RiffHeader;
+use crate::magical::magic::FileKind;
+
+/// How the pixels of an image are stored.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ColorType {
+    Gray,
+    GrayAlpha,
+    Rgb,
+    Rgba,
+    /// Indices into a palette.
+    Indexed,
+    Cmyk,
+    YCbCr,
+    Lab,
+}
+
+impl ColorType {
+    /// The number of channels (samples per pixel).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::image_info::ColorType;
+    ///
+    /// assert_eq!(ColorType::Rgba.channels(), 4);
+    /// assert_eq!(ColorType::Indexed.channels(), 1);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub const fn channels(self) -> u8 {
+        match self {
+            Self::Gray | Self::Indexed => 1,
+            Self::GrayAlpha => 2,
+            Self::Rgb | Self::YCbCr | Self::Lab => 3,
+            Self::Rgba | Self::Cmyk => 4,
+        }
+    }
+}
+
+/// What the header of an image tells, see [`image_info`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageInfo {
+    pub width: u32,
+    pub height: u32,
+    /// Bits per pixel, all channels included: 24 for 8 bits RGB, 8 for a 256 colors palette.
+    pub bits_per_pixel: Option<u16>,
+    pub color_type: Option<ColorType>,
+    /// The number of frames, or of images for icons. [`None`] if counting them needs more
+    /// bytes than given.
+    pub frames: Option<u32>,
+}
+
+impl ImageInfo {
+    const fn new(width: u32, height: u32) -> Self {
+        Self {
+            width,
+            height,
+            bits_per_pixel: None,
+            color_type: None,
+            frames: Some(1),
+        }
+    }
+}
+
+fn read_u16_be(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_be_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u16_le(bytes: &[u8], offset: usize) -> Option<u16> {
+    Some(u16::from_le_bytes(
+        bytes.get(offset..offset + 2)?.try_into().ok()?,
+    ))
+}
+
+fn read_u24_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    match bytes.get(offset..offset + 3)? {
+        [a, b, c] => Some(u32::from_le_bytes([*a, *b, *c, 0])),
+        _ => None,
+    }
+}
+
+fn read_u32_be(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_be_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+fn read_u32_le(bytes: &[u8], offset: usize) -> Option<u32> {
+    Some(u32::from_le_bytes(
+        bytes.get(offset..offset + 4)?.try_into().ok()?,
+    ))
+}
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1A\n";
+
+/// Reads `IHDR`, then walks the chunks up to `IDAT`: an `acTL` chunk before it makes an APNG.
+fn png_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(PNG_SIGNATURE) || bytes.get(12..16)? != b"IHDR" {
+        return None;
+    }
+
+    let mut info = ImageInfo::new(read_u32_be(bytes, 16)?, read_u32_be(bytes, 20)?);
+    let depth = *bytes.get(24)?;
+
+    info.color_type = match bytes.get(25)? {
+        0 => Some(ColorType::Gray),
+        2 => Some(ColorType::Rgb),
+        3 => Some(ColorType::Indexed),
+        4 => Some(ColorType::GrayAlpha),
+        6 => Some(ColorType::Rgba),
+        _ => None,
+    };
+    info.bits_per_pixel = info
+        .color_type
+        .map(|color_type| u16::from(depth) * u16::from(color_type.channels()));
+    info.frames = None;
+
+    let mut offset = PNG_SIGNATURE.len();
+
+    while let (Some(len), Some(chunk_type)) = (
+        read_u32_be(bytes, offset),
+        bytes.get(offset + 4..offset + 8),
+    ) {
+        match chunk_type {
+            b"acTL" => {
+                info.frames = read_u32_be(bytes, offset + 8);
+                break;
+            }
+            b"IDAT" => {
+                info.frames = Some(1);
+                break;
+            }
+            _ => {}
+        }
+
+        /* Length, type, data and CRC. */
+        offset = offset.checked_add(12)?.checked_add(len as usize)?;
+    }
+
+    Some(info)
+}
+
+/// Walks the JPEG markers up to the first start of frame.
+fn jpeg_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(&[0xFF, 0xD8]) {
+        return None;
+    }
+
+    let mut offset = 2;
+
+    loop {
+        if *bytes.get(offset)? != 0xFF {
+            return None;
+        }
+
+        let marker = *bytes.get(offset + 1)?;
+
+        match marker {
+            /* Fill bytes. */
+            0xFF => {
+                offset += 1;
+                continue;
+            }
+            /* Markers without a segment. */
+            0x01 | 0xD0..=0xD8 => {
+                offset += 2;
+                continue;
+            }
+            /* Start of scan or end of image before any frame. */
+            0xDA | 0xD9 => return None,
+            /* Every SOFn, but DHT (C4), JPG (C8) and DAC (CC). */
+            0xC0..=0xCF if !matches!(marker, 0xC4 | 0xC8 | 0xCC) => {
+                let precision = *bytes.get(offset + 4)?;
+                let mut info = ImageInfo::new(
+                    u32::from(read_u16_be(bytes, offset + 7)?),
+                    u32::from(read_u16_be(bytes, offset + 5)?),
+                );
+                let components = *bytes.get(offset + 9)?;
+
+                info.bits_per_pixel = Some(u16::from(precision) * u16::from(components));
+                info.color_type = match components {
+                    1 => Some(ColorType::Gray),
+                    3 => Some(ColorType::YCbCr),
+                    4 => Some(ColorType::Cmyk),
+                    _ => None,
+                };
+
+                return Some(info);
+            }
+            _ => {}
+        }
+
+        offset += 2 + read_u16_be(bytes, offset + 2)? as usize;
+    }
+}
+
+const GIF_HEADER_LEN: usize = 13;
+const GIF_COLOR_TABLE: u8 = 0x80;
+
+const fn gif_color_table_len(flags: u8) -> usize {
+    if flags & GIF_COLOR_TABLE == 0 {
+        0
+    } else {
+        3 << ((flags & 0x07) + 1)
+    }
+}
+
+/// Skips data sub-blocks, returns the offset after the block terminator.
+fn skip_gif_sub_blocks(bytes: &[u8], mut offset: usize) -> Option<usize> {
+    loop {
+        let len = *bytes.get(offset)? as usize;
+        offset += 1 + len;
+
+        if len == 0 {
+            return Some(offset);
+        }
+    }
+}
+
+/// Counts the image descriptors up to the trailer.
+fn count_gif_frames(bytes: &[u8], flags: u8) -> Option<u32> {
+    let mut offset = GIF_HEADER_LEN + gif_color_table_len(flags);
+    let mut frames = 0;
+
+    loop {
+        match bytes.get(offset)? {
+            b',' => {
+                frames += 1;
+                let local_flags = *bytes.get(offset + 9)?;
+                /* Descriptor, local color table, LZW minimum code size. */
+                offset =
+                    skip_gif_sub_blocks(bytes, offset + 11 + gif_color_table_len(local_flags))?;
+            }
+            b'!' => offset = skip_gif_sub_blocks(bytes, offset + 2)?,
+            b';' => return Some(frames),
+            _ => return None,
+        }
+    }
+}
+
+/// Reads the logical screen descriptor, and counts the frames if `bytes` reaches the trailer.
+fn gif_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(b"GIF87a") && !bytes.starts_with(b"GIF89a") {
+        return None;
+    }
+
+    let flags = *bytes.get(10)?;
+    let mut info = ImageInfo::new(
+        u32::from(read_u16_le(bytes, 6)?),
+        u32::from(read_u16_le(bytes, 8)?),
+    );
+
+    let bits = if flags & GIF_COLOR_TABLE == 0 {
+        ((flags >> 4) & 0x07) + 1
+    } else {
+        (flags & 0x07) + 1
+    };
+
+    info.bits_per_pixel = Some(u16::from(bits));
+    info.color_type = Some(ColorType::Indexed);
+    info.frames = count_gif_frames(bytes, flags);
+
+    Some(info)
+}
+
+const BMP_CORE_HEADER_LEN: u32 = 12;
+/// The alpha mask of `BITMAPV3INFOHEADER` and later.
+const BMP_ALPHA_MASK_OFFSET: usize = 66;
+const BMP_MIN_ALPHA_HEADER_LEN: u32 = 56;
+
+/// Reads the DIB header, the core (OS/2 1.x) one or any later variant.
+fn bitmap_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(b"BM") {
+        return None;
+    }
+
+    let header_len = read_u32_le(bytes, 14)?;
+
+    let (mut info, bits) = if header_len == BMP_CORE_HEADER_LEN {
+        (
+            ImageInfo::new(
+                u32::from(read_u16_le(bytes, 18)?),
+                u32::from(read_u16_le(bytes, 20)?),
+            ),
+            read_u16_le(bytes, 24)?,
+        )
+    } else {
+        /* A negative height means top-down rows. */
+        #[allow(clippy::cast_possible_wrap)]
+        let height = (read_u32_le(bytes, 22)? as i32).unsigned_abs();
+
+        (
+            ImageInfo::new(read_u32_le(bytes, 18)?, height),
+            read_u16_le(bytes, 28)?,
+        )
+    };
+
+    let has_alpha = header_len >= BMP_MIN_ALPHA_HEADER_LEN
+        && read_u32_le(bytes, BMP_ALPHA_MASK_OFFSET).is_some_and(|mask| mask != 0);
+
+    info.bits_per_pixel = Some(bits);
+    info.color_type = match bits {
+        1 | 2 | 4 | 8 => Some(ColorType::Indexed),
+        32 if has_alpha => Some(ColorType::Rgba),
+        16 | 24 | 32 => Some(ColorType::Rgb),
+        _ => None,
+    };
+
+    Some(info)
+}
+
+/// Walks the chunks of an animated WebP and counts the `ANMF` frames, if `bytes` is the
+/// whole file.
+fn count_webp_frames(bytes: &[u8], riff_len: u64) -> Option<u32> {
+    let mut offset = 12;
+    let mut frames = 0;
+
+    while (offset as u64) < riff_len {
+        let size = read_u32_le(bytes, offset + 4)? as usize;
+
+        if bytes.get(offset..offset + 4)? == b"ANMF" {
+            frames += 1;
+        }
+
+        /* Chunks are padded to an even size. */
+        offset = offset.checked_add(8)?.checked_add(size + (size & 1))?;
+    }
+
+    Some(frames)
+}
+
+/// Reads the first chunk: `VP8 ` (lossy), `VP8L` (lossless) or `VP8X` (extended).
+fn webp_info(bytes: &[u8]) -> Option<ImageInfo> {
+    let riff = RiffHeader::parse(bytes)?;
+
+    if riff.kind() != Some(FileKind::WEBP) {
+        return None;
+    }
+
+    match bytes.get(12..16)? {
+        b"VP8 " => {
+            /* Frame tag, then the key frame start code. */
+            if bytes.get(23..26)? != [0x9D, 0x01, 0x2A] {
+                return None;
+            }
+
+            let mut info = ImageInfo::new(
+                u32::from(read_u16_le(bytes, 26)? & 0x3FFF),
+                u32::from(read_u16_le(bytes, 28)? & 0x3FFF),
+            );
+            info.bits_per_pixel = Some(24);
+            info.color_type = Some(ColorType::Rgb);
+
+            Some(info)
+        }
+        b"VP8L" => {
+            if *bytes.get(20)? != 0x2F {
+                return None;
+            }
+
+            /* 14 bits width - 1, 14 bits height - 1, alpha hint, 3 bits version. */
+            let bits = read_u32_le(bytes, 21)?;
+            let mut info = ImageInfo::new((bits & 0x3FFF) + 1, ((bits >> 14) & 0x3FFF) + 1);
+
+            (info.bits_per_pixel, info.color_type) = if bits & (1 << 28) == 0 {
+                (Some(24), Some(ColorType::Rgb))
+            } else {
+                (Some(32), Some(ColorType::Rgba))
+            };
+
+            Some(info)
+        }
+        b"VP8X" => {
+            const ANIMATION: u8 = 0x02;
+            const ALPHA: u8 = 0x10;
+
+            let flags = *bytes.get(20)?;
+            let mut info = ImageInfo::new(read_u24_le(bytes, 24)? + 1, read_u24_le(bytes, 27)? + 1);
+
+            (info.bits_per_pixel, info.color_type) = if flags & ALPHA == 0 {
+                (Some(24), Some(ColorType::Rgb))
+            } else {
+                (Some(32), Some(ColorType::Rgba))
+            };
+
+            if flags & ANIMATION != 0 {
+                info.frames = count_webp_frames(bytes, riff.declared_len);
+            }
+
+            Some(info)
+        }
+        _ => None,
+    }
+}
+
+const ICO_DIRECTORY_ENTRY_LEN: usize = 16;
+
+/// Reads the icon directory and reports its largest image.
+fn ico_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(&[0x00, 0x00, 0x01, 0x00]) {
+        return None;
+    }
+
+    let count = read_u16_le(bytes, 4)?;
+    let mut largest: Option<ImageInfo> = None;
+
+    for index in 0..count as usize {
+        let entry = bytes.get(6 + index * ICO_DIRECTORY_ENTRY_LEN..)?;
+
+        /* 0 stands for 256 pixels. */
+        let size = |byte: u8| if byte == 0 { 256 } else { u32::from(byte) };
+        let mut info = ImageInfo::new(size(*entry.first()?), size(*entry.get(1)?));
+        let bits = read_u16_le(entry, 6)?;
+
+        info.bits_per_pixel = (bits != 0).then_some(bits);
+        info.color_type = match bits {
+            1 | 2 | 4 | 8 => Some(ColorType::Indexed),
+            24 => Some(ColorType::Rgb),
+            32 => Some(ColorType::Rgba),
+            _ => None,
+        };
+        info.frames = Some(u32::from(count));
+
+        if largest.is_none_or(|found| {
+            u64::from(info.width) * u64::from(info.height)
+                > u64::from(found.width) * u64::from(found.height)
+        }) {
+            largest = Some(info);
+        }
+    }
+
+    largest
+}
+
+/// Reads the Photoshop file header, PSD (version 1) or PSB (version 2).
+fn psd_info(bytes: &[u8]) -> Option<ImageInfo> {
+    if !bytes.starts_with(b"8BPS") || !matches!(read_u16_be(bytes, 4)?, 1 | 2) {
+        return None;
+    }
+
+    let channels = read_u16_be(bytes, 12)?;
+    let depth = read_u16_be(bytes, 22)?;
+    let mut info = ImageInfo::new(read_u32_be(bytes, 18)?, read_u32_be(bytes, 14)?);
+
+    info.bits_per_pixel = depth.checked_mul(channels);
+    /* Bitmap, grayscale, indexed, RGB, CMYK, multichannel, duotone and Lab modes. */
+    info.color_type = match (read_u16_be(bytes, 24)?, channels) {
+        (0 | 1 | 8, 2..) => Some(ColorType::GrayAlpha),
+        (0 | 1 | 8, _) => Some(ColorType::Gray),
+        (2, _) => Some(ColorType::Indexed),
+        (3, 4..) => Some(ColorType::Rgba),
+        (3, _) => Some(ColorType::Rgb),
+        (4, _) => Some(ColorType::Cmyk),
+        (9, _) => Some(ColorType::Lab),
+        _ => None,
+    };
+
+    Some(info)
+}
+
+/// Reads the dimensions, bit depth, color type and frame count of an image from its headers.
+///
+/// Supports [`FileKind::Png`] (and APNG), [`FileKind::Jpg`], [`FileKind::GIF`],
+/// [`FileKind::Bitmap`], [`FileKind::WEBP`], [`FileKind::ICO`] and [`FileKind::_8BPS`].
+/// Only headers are parsed, nothing is decoded: a few hundred bytes are usually enough,
+/// though JPEG metadata (EXIF thumbnails...) may push the frame header further.
+///
+/// Frames are counted when the header holds the count (APNG, ICO), or when `bytes` reaches
+/// the end of the file (GIF, animated WebP).
+///
+/// Returns [`None`] for other kinds, or if `bytes` ends before the needed fields.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::image_info::{ColorType, image_info};
+/// use magical_rs::magical::magic::FileKind;
+///
+/// let gif = b"GIF89a\x40\x01\xF0\x00\x00\x00\x00;";
+/// let info = image_info(FileKind::GIF, gif).unwrap();
+///
+/// assert_eq!((info.width, info.height), (320, 240));
+/// assert_eq!(info.color_type, Some(ColorType::Indexed));
+/// assert_eq!(info.frames, Some(0));
+/// assert_eq!(image_info(FileKind::PDF, b"%PDF-1.7"), None);
+/// ```
+#[must_use]
+pub fn image_info(kind: FileKind, bytes: &[u8]) -> Option<ImageInfo> {
+    match kind {
+        FileKind::Png => png_info(bytes),
+        FileKind::Jpg => jpeg_info(bytes),
+        FileKind::GIF => gif_info(bytes),
+        FileKind::Bitmap => bitmap_info(bytes),
+        FileKind::WEBP => webp_info(bytes),
+        FileKind::ICO => ico_info(bytes),
+        FileKind::_8BPS => psd_info(bytes),
+        _ => None,
+    }
+}
@@ -1,4 +1,9 @@
-use crate::magical::signatures::SIGNATURE_KIND;
+use crate::magical::dispatch::SIGNATURE_INDEX;
+use crate::magical::signatures::{KIND_INFO, KindInfo};
+use crate::magical::validate::{Validation, validate};
+
+#[cfg(feature = "std")]
+use crate::magical::match_rules::{RankedMatch, rank};
 
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum FileKind {
@@ -50,9 +55,256 @@ pub enum FileKind {
     FlashVideo,
     Vmdk,
     GoogleChromeExtension,
+    Docx,
+    Xlsx,
+    Pptx,
+    OpenDocumentText,
+    OpenDocumentSpreadsheet,
+    OpenDocumentPresentation,
+    Epub,
+    Jar,
+    Apk,
+    Xpi,
+    Nupkg,
+    Vsix,
+    Mp4,
+    M4v,
+    M4a,
+    QuickTime,
+    Heic,
+    Heif,
+    Avif,
+    ThreeGpp,
+    ThreeGpp2,
+    Wav,
+    Avi,
+    AniCursor,
+    Rmi,
+    Qcp,
+    CorelDraw,
+    MachO,
+    MachOFat,
+    Pe32,
+    Pe32Plus,
+    Dll,
+    DotNetAssembly,
+    WindowsDriver,
+    Xz,
+    Zstd,
+    Lz4,
+    SevenZip,
+    Lzip,
+    Lzma,
+    UnixCompress,
+    Lzop,
+    Cpio,
+    Flac,
+    Aiff,
+    Midi,
+    Aac,
+    Opus,
+    Vorbis,
+    Speex,
+    Ape,
+    WavPack,
+    Amr,
+    Webm,
+    MpegTs,
+    MpegPs,
+    Asf,
+    Qcow2,
+    Vhd,
+    Vhdx,
+    Vdi,
+    Gpt,
+    Mbr,
+    Ext2,
+    Ext3,
+    Ext4,
+    Fat12,
+    Fat16,
+    Fat32,
+    ExFat,
+    Ntfs,
+    Squashfs,
+    Btrfs,
+    Xfs,
+    Udf,
+    Parquet,
+    ArrowIpc,
+    Feather,
+    Orc,
+    AvroObjectContainer,
+    Hdf5,
+    NetCdf,
+    Fits,
+    NumpyNpy,
+    MatlabMat,
+}
+
+/// The outcome of a detection, telling an empty input apart from an unknown one.
+///
+/// Returned by [`FileKind::detect`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Detection {
+    /// A built-in signature matched.
+    Known(FileKind),
+    /// There was nothing to look at: the file (or buffer) is empty.
+    Empty,
+    /// Some bytes were read, but no built-in signature matched.
+    Unknown,
+}
+
+impl Detection {
+    /// Returns the detected kind, if any.
+    #[must_use]
+    #[inline]
+    pub const fn kind(self) -> Option<FileKind> {
+        match self {
+            Self::Known(kind) => Some(kind),
+            Self::Empty | Self::Unknown => None,
+        }
+    }
 }
 
 impl FileKind {
+    /// Every built-in [`FileKind`], in declaration order.
+    pub const ALL: &'static [Self] = &[
+        Self::Png,
+        Self::Bitmap,
+        Self::Gzip,
+        Self::Bzip,
+        Self::PkgZip,
+        Self::Tar,
+        Self::MSDOS,
+        Self::Jpg,
+        Self::Class,
+        Self::MP3,
+        Self::ISO,
+        Self::RPM,
+        Self::SQLite,
+        Self::XML,
+        Self::ICO,
+        Self::WASM,
+        Self::Deb,
+        Self::RAR,
+        Self::ScriptExecute,
+        Self::ELF,
+        Self::OGG,
+        Self::_8BPS,
+        Self::BLENDER,
+        Self::TrueTypeFont,
+        Self::OpenTypeFont,
+        Self::ModuleForEvenvironmentModules,
+        Self::WindowImagingFormat,
+        Self::Slob,
+        Self::SerializedJavaData,
+        Self::CreativeVoiceFile,
+        Self::AuAudioFileFormat,
+        Self::OpenGLIrisPerformer,
+        Self::NoodlesoftHazel,
+        Self::VBScriptEncoded,
+        Self::WEBP,
+        Self::AppleIconImage,
+        Self::GIF,
+        Self::JPEG2000,
+        Self::PDF,
+        Self::AppleDiskImage,
+        Self::Cabinet,
+        Self::MatroskaMediaContainer,
+        Self::RichTextFormat,
+        Self::PhotoCapTemplate,
+        Self::AceCompressed,
+        Self::FlashVideo,
+        Self::Vmdk,
+        Self::GoogleChromeExtension,
+        Self::Docx,
+        Self::Xlsx,
+        Self::Pptx,
+        Self::OpenDocumentText,
+        Self::OpenDocumentSpreadsheet,
+        Self::OpenDocumentPresentation,
+        Self::Epub,
+        Self::Jar,
+        Self::Apk,
+        Self::Xpi,
+        Self::Nupkg,
+        Self::Vsix,
+        Self::Mp4,
+        Self::M4v,
+        Self::M4a,
+        Self::QuickTime,
+        Self::Heic,
+        Self::Heif,
+        Self::Avif,
+        Self::ThreeGpp,
+        Self::ThreeGpp2,
+        Self::Wav,
+        Self::Avi,
+        Self::AniCursor,
+        Self::Rmi,
+        Self::Qcp,
+        Self::CorelDraw,
+        Self::MachO,
+        Self::MachOFat,
+        Self::Pe32,
+        Self::Pe32Plus,
+        Self::Dll,
+        Self::DotNetAssembly,
+        Self::WindowsDriver,
+        Self::Xz,
+        Self::Zstd,
+        Self::Lz4,
+        Self::SevenZip,
+        Self::Lzip,
+        Self::Lzma,
+        Self::UnixCompress,
+        Self::Lzop,
+        Self::Cpio,
+        Self::Flac,
+        Self::Aiff,
+        Self::Midi,
+        Self::Aac,
+        Self::Opus,
+        Self::Vorbis,
+        Self::Speex,
+        Self::Ape,
+        Self::WavPack,
+        Self::Amr,
+        Self::Webm,
+        Self::MpegTs,
+        Self::MpegPs,
+        Self::Asf,
+        Self::Qcow2,
+        Self::Vhd,
+        Self::Vhdx,
+        Self::Vdi,
+        Self::Gpt,
+        Self::Mbr,
+        Self::Ext2,
+        Self::Ext3,
+        Self::Ext4,
+        Self::Fat12,
+        Self::Fat16,
+        Self::Fat32,
+        Self::ExFat,
+        Self::Ntfs,
+        Self::Squashfs,
+        Self::Btrfs,
+        Self::Xfs,
+        Self::Udf,
+        Self::Parquet,
+        Self::ArrowIpc,
+        Self::Feather,
+        Self::Orc,
+        Self::AvroObjectContainer,
+        Self::Hdf5,
+        Self::NetCdf,
+        Self::Fits,
+        Self::NumpyNpy,
+        Self::MatlabMat,
+    ];
+
     /// Attemps to determine the file type by matching the given bytes slice against known magic
     /// signatures.
     ///
@@ -112,8 +364,9 @@ impl FileKind {
     ///
     /// - Order matters: Since the function returns on the first match, the order of entries
     ///   in `SIGNATURE_KIND` can affect the result (e.g., in case of ambiguous or overlapping signatures).
-    /// - Performance: The function short-circuits on the first match. Worst-case time complexity
-    ///   is linear in the number of signatures and offsets.
+    /// - Performance: Only rules that can possibly match the first byte of `bytes` are evaluated,
+    ///   using the compile-time `SIGNATURE_INDEX` (see `dispatch.rs`), and the function
+    ///   short-circuits on the first match. Results are identical to a linear scan of `SIGNATURE_KIND`.
     /// - Buffer size: Ensure that `bytes` is at least as large as required by [`with_bytes_read`],
     ///   otherwise some signatures may not be detectable.
     ///
@@ -126,12 +379,222 @@ impl FileKind {
     #[must_use]
     #[inline]
     pub fn match_types(bytes: &[u8]) -> Option<Self> {
-        SIGNATURE_KIND
-            .iter()
+        SIGNATURE_INDEX
+            .candidates(bytes)
             .find(|magic| magic.matches(bytes))
             .map(|magic| magic.kind)
     }
 
+    /// Like [`FileKind::match_types`], but reports empty input as [`Detection::Empty`]
+    /// instead of folding it into "unknown".
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::{Detection, FileKind};
+    ///
+    /// assert_eq!(FileKind::detect(b"GIF89a"), Detection::Known(FileKind::GIF));
+    /// assert_eq!(FileKind::detect(b"moe"), Detection::Unknown);
+    /// assert_eq!(FileKind::detect(&[]), Detection::Empty);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn detect(bytes: &[u8]) -> Detection {
+        if bytes.is_empty() {
+            return Detection::Empty;
+        }
+
+        Self::match_types(bytes).map_or(Detection::Unknown, Detection::Known)
+    }
+
+    /// Checks the structure behind the signature of this kind: a forged header (a text file
+    /// starting with `\x89PNG\r\n\x1A\n`...) passes [`FileKind::match_types`], not this.
+    ///
+    /// `bytes` is the head of the file, a few hundred bytes are enough for every check.
+    ///
+    /// Supported kinds and what is checked:
+    /// - [`FileKind::Png`]: the `IHDR` chunk length, CRC, dimensions, bit depth and color type.
+    /// - [`FileKind::GIF`]: the logical screen descriptor, and the block after the color table.
+    /// - [`FileKind::Bitmap`]: the DIB header size against the known variants, the pixel data
+    ///   offset and the color planes.
+    /// - [`FileKind::ELF`]: `e_ident` class, data encoding and version, and `e_version`.
+    /// - [`FileKind::PkgZip`] and the ZIP based kinds: the first local file header.
+    /// - [`FileKind::SQLite`]: the page size, file format versions and payload fractions.
+    ///
+    /// Returns [`None`] for other kinds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    /// use magical_rs::magical::validate::Validation;
+    ///
+    /// let gif = b"GIF89a\x01\x00\x01\x00\x00\x00\x00,";
+    ///
+    /// assert_eq!(FileKind::GIF.validate(gif), Some(Validation::Valid));
+    /// assert_eq!(FileKind::GIF.validate(b"GIF89a\x01\x00"), Some(Validation::Truncated));
+    /// assert_eq!(FileKind::GIF.validate(b"GIF89a is a format"), Some(Validation::Malformed));
+    /// assert_eq!(FileKind::PDF.validate(b"%PDF-1.7"), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn validate(self, bytes: &[u8]) -> Option<Validation> {
+        validate(self, bytes)
+    }
+
+    /// Like [`FileKind::match_types`], but skips kinds whose structure is not
+    /// [`Validation::Valid`], see [`FileKind::validate`].
+    ///
+    /// Kinds without a structural check are kept as they are. Truncated input is rejected,
+    /// so `bytes` should hold a few hundred bytes at least.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// let forged = b"\x89PNG\r\n\x1A\nnot really a picture";
+    ///
+    /// assert_eq!(FileKind::match_types(forged), Some(FileKind::Png));
+    /// assert_eq!(FileKind::match_types_strict(forged), None);
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn match_types_strict(bytes: &[u8]) -> Option<Self> {
+        SIGNATURE_INDEX
+            .candidates(bytes)
+            .filter(|magic| magic.matches(bytes))
+            .map(|magic| magic.kind)
+            .find(|kind| {
+                kind.validate(bytes)
+                    .is_none_or(|found| found == Validation::Valid)
+            })
+    }
+
+    /// Detects the file type from the first **and** the last bytes of a file.
+    ///
+    /// Works like [`FileKind::match_types`], but also checks end-anchored rules
+    /// (trailers and footers such as the ZIP end of central directory, the DMG `koly` block
+    /// or `ID3v1` tags) against `tail`. Rules keep the order of `SIGNATURE_KIND`: most
+    /// end-anchored rules come last, so header rules win, but some trailers deliberately
+    /// outrank the header rules after them. The DMG `koly` block and the fixed VHD footer
+    /// describe the whole file, while its first bytes are those of the disk inside.
+    ///
+    /// `tail` must hold the last bytes of the file, see [`with_tail_bytes_read`].
+    /// For small files, `head` and `tail` may both be the whole file.
+    ///
+    /// [`with_tail_bytes_read`]: crate::magical::bytes_read::with_tail_bytes_read
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// // Raw deflate data, followed by an ID3v1 tag.
+    /// let mut file = vec![0x00; 64];
+    /// file.extend_from_slice(b"TAG");
+    /// file.extend_from_slice(&[0x20; 125]);
+    ///
+    /// assert_eq!(FileKind::match_types(&file), None);
+    /// assert_eq!(FileKind::match_types_with_tail(&file, &file), Some(FileKind::MP3));
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn match_types_with_tail(head: &[u8], tail: &[u8]) -> Option<Self> {
+        SIGNATURE_INDEX
+            .candidates_with_tail(head)
+            .find(|magic| magic.matches_with_tail(head, tail))
+            .map(|magic| magic.kind)
+    }
+
+    /// Returns every built-in kind whose signature matches the given bytes.
+    ///
+    /// Unlike [`FileKind::match_types`], this function does not stop at the first match,
+    /// so callers can see when several signatures claim the same input
+    /// (e.g. short signatures like `BM` or `MZ` shadowing later entries).
+    ///
+    /// The kinds are yielded in `SIGNATURE_KIND` order. A kind with several rules
+    /// may appear more than once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// let png_header = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+    /// let kinds = FileKind::match_all_types(png_header);
+    ///
+    /// assert_eq!(kinds, vec![FileKind::Png]);
+    /// ```
+    ///
+    /// # `no_std` Compatibility
+    ///
+    /// With the `std` feature this returns a `Vec<FileKind>`.
+    /// Without it, the same function returns a lazy iterator, no allocation involved.
+    #[must_use]
+    #[cfg(feature = "std")]
+    pub fn match_all_types(bytes: &[u8]) -> Vec<Self> {
+        SIGNATURE_INDEX
+            .candidates(bytes)
+            .filter(|magic| magic.matches(bytes))
+            .map(|magic| magic.kind)
+            .collect()
+    }
+
+    /// Returns every matching built-in kind together with a confidence value,
+    /// best candidate first.
+    ///
+    /// The confidence of a candidate is the strength of the rule that matched it
+    /// (see [`Magic::strength`]), from `0` to [`MAX_STRENGTH`]. Short signatures like
+    /// `BM`, `MZ` or `BZ` get a low confidence, so weak guesses can be thresholded out.
+    ///
+    /// Candidates with equal confidence keep the order of `SIGNATURE_KIND`,
+    /// and each kind is reported once, with its best confidence.
+    ///
+    /// [`Magic::strength`]: crate::magical::signatures::Magic::strength
+    /// [`MAX_STRENGTH`]: crate::magical::match_rules::MAX_STRENGTH
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// let ranked = FileKind::match_types_ranked(b"BM some plain text");
+    ///
+    /// assert_eq!(ranked[0].kind, FileKind::Bitmap);
+    /// assert!(ranked[0].confidence < 50);
+    ///
+    /// let strong: Vec<_> = ranked.iter().filter(|m| m.confidence >= 50).collect();
+    /// assert!(strong.is_empty());
+    /// ```
+    #[must_use]
+    #[cfg(feature = "std")]
+    pub fn match_types_ranked(bytes: &[u8]) -> Vec<RankedMatch<Self>> {
+        rank(
+            SIGNATURE_INDEX
+                .candidates(bytes)
+                .filter(|magic| magic.matches(bytes))
+                .map(|magic| RankedMatch {
+                    kind: magic.kind,
+                    confidence: magic.strength(),
+                })
+                .collect(),
+        )
+    }
+
+    /// Returns an iterator over every built-in kind whose signature matches the given bytes.
+    ///
+    /// This is the `no_std` flavour of `match_all_types`: it yields the kinds
+    /// lazily in `SIGNATURE_KIND` order, without any allocation.
+    #[inline]
+    #[cfg(not(feature = "std"))]
+    pub fn match_all_types(bytes: &[u8]) -> impl Iterator<Item = Self> + '_ {
+        SIGNATURE_INDEX
+            .candidates(bytes)
+            .filter(move |magic| magic.matches(bytes))
+            .map(|magic| magic.kind)
+    }
+
     /// Detects the file type by matching against built-in signatures, but only considers rules
     /// where `max_bytes_read` is less than or equal to `allowed_max_read`.
     ///
@@ -207,8 +670,8 @@ impl FileKind {
     #[inline]
     #[cfg(not(feature = "std"))]
     pub fn match_with_max_read_rule(bytes: &[u8], allowed_max_read: usize) -> Option<Self> {
-        SIGNATURE_KIND
-            .iter()
+        SIGNATURE_INDEX
+            .candidates(bytes)
             .filter(|magic| magic.max_bytes_read <= allowed_max_read)
             .find(|magic| magic.matches(bytes))
             .map(|magic| magic.kind)
@@ -276,9 +739,107 @@ impl FileKind {
             return None;
         }
 
-        SIGNATURE_KIND
-            .iter()
+        SIGNATURE_INDEX
+            .candidates(bytes)
             .find(|magic| magic.matches(bytes))
             .map(|magic| magic.kind)
     }
+
+    /// Returns the metadata entry of this kind in `KIND_INFO`.
+    fn info(self) -> Option<&'static KindInfo> {
+        KIND_INFO.iter().find(|info| info.kind == self)
+    }
+
+    /// Returns the MIME type of this kind, e.g. `image/png`.
+    ///
+    /// Falls back to `application/octet-stream` for a kind without a metadata entry,
+    /// which never happens for built-in kinds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// assert_eq!(FileKind::Png.mime_type(), "image/png");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn mime_type(self) -> &'static str {
+        self.info()
+            .map_or("application/octet-stream", |info| info.mime_type)
+    }
+
+    /// Returns the canonical extensions of this kind, without the leading dot.
+    ///
+    /// The first extension is the preferred one.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// assert_eq!(FileKind::Jpg.extensions()[0], "jpg");
+    /// ```
+    #[must_use]
+    #[inline]
+    pub fn extensions(self) -> &'static [&'static str] {
+        self.info().map_or(&[], |info| info.extensions)
+    }
+
+    /// Returns a short human readable description, e.g. `Portable Network Graphics`.
+    #[must_use]
+    #[inline]
+    pub fn description(self) -> &'static str {
+        self.info().map_or("Unknown data", |info| info.description)
+    }
+
+    /// Looks up the first kind whose MIME type equals `mime`.
+    ///
+    /// The comparison is ASCII case-insensitive and ignores parameters,
+    /// so `Image/PNG; charset=binary` resolves to [`FileKind::Png`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// assert_eq!(FileKind::from_mime("image/png"), Some(FileKind::Png));
+    /// assert_eq!(FileKind::from_mime("application/x-unknown"), None);
+    /// ```
+    #[must_use]
+    pub fn from_mime(mime: &str) -> Option<Self> {
+        let essence = mime.split(';').next().unwrap_or_default().trim();
+
+        KIND_INFO
+            .iter()
+            .find(|info| info.mime_type.eq_ignore_ascii_case(essence))
+            .map(|info| info.kind)
+    }
+
+    /// Looks up the first kind that lists `extension` among its extensions.
+    ///
+    /// The comparison is ASCII case-insensitive and a leading dot is ignored,
+    /// so both `"PNG"` and `".png"` resolve to [`FileKind::Png`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic::FileKind;
+    ///
+    /// assert_eq!(FileKind::from_extension(".JPEG"), Some(FileKind::Jpg));
+    /// assert_eq!(FileKind::from_extension("nope"), None);
+    /// ```
+    #[must_use]
+    pub fn from_extension(extension: &str) -> Option<Self> {
+        let extension = extension.strip_prefix('.').unwrap_or(extension);
+
+        KIND_INFO
+            .iter()
+            .find(|info| {
+                info.extensions
+                    .iter()
+                    .any(|ext| ext.eq_ignore_ascii_case(extension))
+            })
+            .map(|info| info.kind)
+    }
 }
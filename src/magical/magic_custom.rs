@@ -1,3 +1,12 @@
+use crate::magical::match_rules::{
+    ANY_FN_STRENGTH, FN_STRENGTH, Strength, all_fn_strength, masked_strength, signature_at_end,
+    signature_strength,
+};
+use crate::magical::pattern::MaskedSignature;
+
+#[cfg(feature = "std")]
+use crate::magical::match_rules::{RankedMatch, rank};
+
 /// Rules that determine how a `MagicCustom` instance matches against input bytes.
 ///
 /// This enum allows defining flexible matching strategies for custom file type detection.
@@ -10,6 +19,7 @@
 ///
 /// ```rust
 /// use magical_rs::magical::magic_custom::{MagicCustom, CustomMatchRules};
+/// use magical_rs::magical::match_rules::Strength;
 ///
 /// #[derive(Debug, Clone, Copy, PartialEq)]
 /// enum ShoujuFile {
@@ -22,6 +32,7 @@
 ///     offsets: &[0],
 ///     max_bytes_read: 32,
 ///     kind: ShoujuFile::MahouShouju,
+///     strength: Strength::Auto,
 ///     rules: CustomMatchRules::Default,
 /// };
 ///
@@ -33,6 +44,7 @@ pub enum CustomMatchRules<'a> {
     /// # Examples
     /// ```
     /// use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom, match_types_custom};
+    /// use magical_rs::magical::match_rules::Strength;
     /// #[derive(Debug, Clone, Copy, PartialEq)]
     /// enum FileKind {
     ///     Png,
@@ -45,6 +57,7 @@ pub enum CustomMatchRules<'a> {
     ///     offsets: &[0],
     ///     max_bytes_read: 2048,
     ///     kind: FileKind::Png,
+    ///     strength: Strength::Auto,
     ///     rules: CustomMatchRules::Default,
     /// };
     ///
@@ -82,6 +95,7 @@ pub enum CustomMatchRules<'a> {
     /// # Examples:
     /// ```rust
     /// use magical_rs::magical::magic_custom::{MagicCustom, match_types_custom};
+    /// use magical_rs::magical::match_rules::Strength;
     /// use magical_rs::with_fn_matches;
     ///
     /// #[derive(Debug, Clone, Copy, PartialEq)]
@@ -99,6 +113,7 @@ pub enum CustomMatchRules<'a> {
     ///     offsets: &[],
     ///     max_bytes_read: 2048,
     ///     kind: ShoujuFile::MahouShouju,
+    ///     strength: Strength::Auto,
     ///     rules: with_fn_matches!(is_shoujo_girl),
     /// };
     ///
@@ -262,6 +277,111 @@ pub enum CustomMatchRules<'a> {
     /// It only requires at least Rust's [`core`].
     AllMatches(&'a [fn(bytes: &[u8]) -> bool]),
 
+    /// # Safety:
+    /// Signatures with wildcards or bit masks, for formats whose
+    /// header contains variable bytes: JPEG's `FF D8 FF ??`, a RIFF
+    /// size field between `RIFF` and the form type, MP3 frame sync bits...
+    ///
+    /// Each [`MaskedSignature`] is checked at every offset in [`MagicCustom::offsets`],
+    /// and one match is enough (`OR` logic, like [`CustomMatchRules::Default`]).
+    ///
+    /// Leave [`MagicCustom::signatures`] as `&[]` with this rule.
+    ///
+    /// ---
+    ///
+    /// # Examples:
+    /// ```rust
+    /// use magical_rs::{magic_custom, masked_matches, match_custom};
+    ///
+    /// #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    /// enum CuteGirlKind {
+    ///     Wave,
+    ///     UnknownFallback,
+    /// }
+    ///
+    /// let rule = magic_custom! (
+    ///     signatures: [],
+    ///     offsets: [0],
+    ///     max_bytes_read: 12,
+    ///     kind: CuteGirlKind::Wave,
+    ///     rules: masked_matches!("52 49 46 46 ?? ?? ?? ?? 57 41 56 45")
+    /// );
+    ///
+    /// let result = match_custom! {
+    ///     bytes: b"RIFF\x24\x08\0\0WAVEfmt ",
+    ///     rules: [rule],
+    ///     fallback: CuteGirlKind::UnknownFallback
+    /// };
+    ///
+    /// assert_eq!(result, CuteGirlKind::Wave);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// # Macros:
+    /// Macros with sugar syntax: [`masked_matches`], and the `masked:` form of [`magic_custom`].
+    ///
+    /// [`masked_matches`]: crate::masked_matches
+    /// [`magic_custom`]: crate::magic_custom
+    ///
+    /// ---
+    ///
+    /// # No Standard Library Context:
+    /// Basically, [`CustomMatchRules::Masked`] supports `no_std` context.
+    ///
+    /// It only requires at least Rust's [`core`].
+    Masked(&'a [MaskedSignature]),
+
+    /// # Safety:
+    /// Like [`CustomMatchRules::Default`], but [`MagicCustom::offsets`] count back
+    /// from the **end** of the file: offset `22` means the signature starts 22 bytes
+    /// before the end. Useful for trailers and footers (ZIP end of central directory,
+    /// DMG `koly` block, `ID3v1` tags...).
+    ///
+    /// These rules never match in [`match_types_custom`], which only sees the beginning
+    /// of a file. Use [`match_types_custom_with_tail`] and give it the last bytes of the file.
+    ///
+    /// ---
+    ///
+    /// # Examples:
+    /// ```rust
+    /// use magical_rs::magical::magic_custom::{
+    ///     CustomMatchRules, MagicCustom, match_types_custom, match_types_custom_with_tail,
+    /// };
+    /// use magical_rs::magical::match_rules::Strength;
+    ///
+    /// #[derive(Debug, Clone, Copy, PartialEq)]
+    /// enum CuteGirlKind {
+    ///     Diary,
+    ///     UnknownFallback,
+    /// }
+    ///
+    /// static DIARY_RULE: MagicCustom<CuteGirlKind> = MagicCustom {
+    ///     signatures: &[b"THE END"],
+    ///     offsets: &[7],
+    ///     max_bytes_read: 7,
+    ///     kind: CuteGirlKind::Diary,
+    ///     strength: Strength::Auto,
+    ///     rules: CustomMatchRules::FromEnd,
+    /// };
+    ///
+    /// let diary = b"Today I became a magical girl. THE END";
+    ///
+    /// let head_only = match_types_custom(diary, &[DIARY_RULE], CuteGirlKind::UnknownFallback);
+    /// let result = match_types_custom_with_tail(diary, diary, &[DIARY_RULE], CuteGirlKind::UnknownFallback);
+    ///
+    /// assert_eq!(head_only, CuteGirlKind::UnknownFallback);
+    /// assert_eq!(result, CuteGirlKind::Diary);
+    /// ```
+    ///
+    /// ---
+    ///
+    /// # No Standard Library Context:
+    /// Basically, [`CustomMatchRules::FromEnd`] supports `no_std` context.
+    ///
+    /// It only requires at least Rust's [`core`].
+    FromEnd,
+
     /// # Safety:
     /// Here, you can use raw pointers. There are no restrictions.
     ///
@@ -304,6 +424,7 @@ pub enum CustomMatchRules<'a> {
     ///```rust
     /// use core::slice;
     /// use magical_rs::magical::magic_custom::match_types_custom;
+    /// use magical_rs::magical::match_rules::Strength;
     /// use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
     ///
     /// fn is_magic_file() {
@@ -327,6 +448,7 @@ pub enum CustomMatchRules<'a> {
     ///         offsets: &[],
     ///         max_bytes_read: 200,
     ///         kind: MagicKind::MoeMoe,
+    ///         strength: Strength::Auto,
     ///         rules: CustomMatchRules::WithFnUnsafe {
     ///             func: is_shoujo_girl,
     ///         },
@@ -404,6 +526,7 @@ pub enum CustomMatchRules<'a> {
     /// fn any_unsafe_fn() {
     ///     use core::slice;
     ///     use magical_rs::magical::magic_custom::match_types_custom;
+    ///     use magical_rs::magical::match_rules::Strength;
     ///     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
     ///
     ///     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -435,6 +558,7 @@ pub enum CustomMatchRules<'a> {
     ///         offsets: &[],
     ///         max_bytes_read: 200,
     ///         kind: MagicKind::MoeMoe,
+    ///         strength: Strength::Auto,
     ///         rules: CustomMatchRules::AnyMatchesUnsafe(
     ///                 &[is_shoujo_girl, is_not_shoujo_girl]),
     ///     }];
@@ -496,6 +620,7 @@ pub enum CustomMatchRules<'a> {
     /// fn any_unsafe_fn() {
     ///     use core::slice;
     ///     use magical_rs::magical::magic_custom::match_types_custom;
+    ///     use magical_rs::magical::match_rules::Strength;
     ///     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
     ///
     ///     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -527,6 +652,7 @@ pub enum CustomMatchRules<'a> {
     ///         offsets: &[],
     ///         max_bytes_read: 200,
     ///         kind: MagicKind::MoeMoe,
+    ///         strength: Strength::Auto,
     ///         rules: CustomMatchRules::AllMatchesUnsafe(
     ///                 &[is_shoujo_girl, is_not_shoujo_girl]),
     ///     }];
@@ -566,6 +692,11 @@ pub struct MagicCustom<'a, K> {
     /// The file's identifier is determined by the enum
     /// if it matches.
     pub kind: K,
+    /// How strongly a match of this rule identifies `kind`.
+    ///
+    /// Leave it as [`Strength::Auto`] to derive it from the signatures
+    /// and the number of checks, or use [`Strength::Fixed`] to override it.
+    pub strength: Strength,
     /// File identification rules.
     ///
     /// If not used, leave this field as: [`CustomMatchRules::Default`].
@@ -584,9 +715,56 @@ const fn __from_ref<T: ?Sized>(r: &T) -> *const T {
 }
 
 impl<K: Clone> MagicCustom<'_, K> {
+    /// Returns the strength of this rule, from `0` to `MAX_STRENGTH`.
+    ///
+    /// With [`Strength::Auto`], signature rules are ranked by their shortest signature,
+    /// less `alternatives_penalty` for their number of signatures and offsets,
+    /// single function rules get `FN_STRENGTH`, `OR`'d functions get `ANY_FN_STRENGTH`
+    /// and `AND`'d functions get stronger with every extra function.
+    #[must_use]
+    #[inline]
+    pub const fn strength(&self) -> u8 {
+        let derived = match &self.rules {
+            CustomMatchRules::Default | CustomMatchRules::FromEnd => {
+                signature_strength(self.signatures, self.offsets.len())
+            }
+            CustomMatchRules::WithFn(_) => FN_STRENGTH,
+            CustomMatchRules::AnyMatches(_) => ANY_FN_STRENGTH,
+            CustomMatchRules::AllMatches(funcs) => all_fn_strength(funcs.len()),
+            CustomMatchRules::Masked(patterns) => masked_strength(patterns, self.offsets.len()),
+
+            #[cfg(feature = "unsafe_context")]
+            CustomMatchRules::WithFnUnsafe { .. } => FN_STRENGTH,
+            #[cfg(feature = "unsafe_context")]
+            CustomMatchRules::AnyMatchesUnsafe(_) => ANY_FN_STRENGTH,
+            #[cfg(feature = "unsafe_context")]
+            CustomMatchRules::AllMatchesUnsafe(funcs) => all_fn_strength(funcs.len()),
+        };
+
+        self.strength.value_or(derived)
+    }
+
+    /// Checks this rule against the first (`head`) and last (`tail`) bytes of a file.
+    ///
+    /// End-anchored rules ([`CustomMatchRules::FromEnd`]) are checked against `tail`,
+    /// every other rule against `head`.
+    #[must_use]
+    #[inline]
+    pub(crate) fn matches_custom_with_tail(&self, head: &[u8], tail: &[u8]) -> bool {
+        if matches!(self.rules, CustomMatchRules::FromEnd) {
+            self.signatures.iter().any(|&signature| {
+                self.offsets
+                    .iter()
+                    .any(|&offset| signature_at_end(tail, signature, offset))
+            })
+        } else {
+            self.matches_custom(head)
+        }
+    }
+
     #[must_use]
     #[inline]
-    fn matches_custom(&self, bytes: &[u8]) -> bool {
+    pub(crate) fn matches_custom(&self, bytes: &[u8]) -> bool {
         match &self.rules {
             CustomMatchRules::Default => self.signatures.iter().any(|&signature| {
                 self.offsets.iter().any(|&offset| {
@@ -599,6 +777,12 @@ impl<K: Clone> MagicCustom<'_, K> {
             CustomMatchRules::WithFn(func) => func(bytes),
             CustomMatchRules::AnyMatches(funcs) => funcs.iter().any(|&func| func(bytes)),
             CustomMatchRules::AllMatches(funcs) => funcs.iter().all(|&func| func(bytes)),
+            CustomMatchRules::Masked(patterns) => patterns.iter().any(|pattern| {
+                self.offsets
+                    .iter()
+                    .any(|&offset| pattern.matches_at(bytes, offset))
+            }),
+            CustomMatchRules::FromEnd => false,
 
             #[cfg(feature = "unsafe_context")]
             CustomMatchRules::WithFnUnsafe { func, .. } => {
@@ -680,6 +864,7 @@ impl<K: Clone> MagicCustom<'_, K> {
 ///
 /// ```rust
 /// use magical_rs::magical::magic_custom::{MagicCustom, CustomMatchRules, match_types_custom};
+/// use magical_rs::magical::match_rules::Strength;
 ///
 /// #[derive(Debug, Clone, Copy, PartialEq)]
 /// enum ShoujoKind {
@@ -693,6 +878,7 @@ impl<K: Clone> MagicCustom<'_, K> {
 ///     offsets: &[0],
 ///     max_bytes_read: 32,
 ///     kind: ShoujoKind::Shoujo,
+///     strength: Strength::Auto,
 ///     rules: CustomMatchRules::Default,
 /// };
 ///
@@ -723,6 +909,199 @@ pub fn match_types_custom<K: Clone>(bytes: &[u8], rules: &[MagicCustom<K>], fall
         .map_or(fallback, |rule| rule.kind.clone())
 }
 
+/// Detects the file type from the first **and** the last bytes of a file,
+/// using a custom list of `MagicCustom` rules.
+///
+/// Works like [`match_types_custom`], but rules using [`CustomMatchRules::FromEnd`]
+/// are checked against `tail`, so trailer and footer signatures can be detected.
+/// Rules are still checked in order and the first match wins.
+///
+/// For small files, `head` and `tail` may both be the whole file.
+///
+/// ---
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::magic_custom::{MagicCustom, CustomMatchRules, match_types_custom_with_tail};
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum ShoujoKind {
+///     Transformed,
+///     Unknown,
+/// }
+///
+/// static SPARKLE_RULE: MagicCustom<ShoujoKind> = MagicCustom {
+///     signatures: &[b"*sparkle*"],
+///     offsets: &[9],
+///     max_bytes_read: 9,
+///     kind: ShoujoKind::Transformed,
+///     strength: Strength::Auto,
+///     rules: CustomMatchRules::FromEnd,
+/// };
+///
+/// let head = b"Moon Prism Power...";
+/// let tail = b"...Make Up! *sparkle*";
+///
+/// let kind = match_types_custom_with_tail(head, tail, &[SPARKLE_RULE], ShoujoKind::Unknown);
+/// assert_eq!(kind, ShoujoKind::Transformed);
+/// ```
+/// ---
+///
+/// # No Standard Library Context:
+/// Basically, [`match_types_custom_with_tail`] supports `no_std` context.
+///
+/// It only requires at least Rust's [`core`].
+#[inline]
+#[must_use]
+pub fn match_types_custom_with_tail<K: Clone>(
+    head: &[u8],
+    tail: &[u8],
+    rules: &[MagicCustom<K>],
+    fallback: K,
+) -> K {
+    rules
+        .iter()
+        .find(|rule| rule.matches_custom_with_tail(head, tail))
+        .map_or(fallback, |rule| rule.kind.clone())
+}
+
+/// Returns the `kind` of every rule in `rules` that matches the input `bytes`.
+///
+/// This is the counterpart of [`match_types_custom`] that does not stop at the first match,
+/// so ambiguous rule sets can be detected by the caller.
+///
+/// ---
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::magic_custom::{MagicCustom, CustomMatchRules, match_all_types_custom};
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum ShoujoKind {
+///     Shoujo,
+///     Magical,
+/// }
+///
+/// static RULES: &[MagicCustom<ShoujoKind>] = &[
+///     MagicCustom {
+///         signatures: &[b"Shoujo"],
+///         offsets: &[0],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::Shoujo,
+///         strength: Strength::Auto,
+///         rules: CustomMatchRules::Default,
+///     },
+///     MagicCustom {
+///         signatures: &[b"Magical"],
+///         offsets: &[7],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::Magical,
+///         strength: Strength::Auto,
+///         rules: CustomMatchRules::Default,
+///     },
+/// ];
+///
+/// let kinds = match_all_types_custom(b"Shoujo Magical", RULES);
+/// assert_eq!(kinds, vec![ShoujoKind::Shoujo, ShoujoKind::Magical]);
+/// ```
+///
+/// ---
+///
+/// # No Standard Library Context:
+/// With the `std` feature this returns a `Vec<K>`.
+/// Without it, the same function returns a lazy iterator, no allocation involved.
+#[must_use]
+#[cfg(feature = "std")]
+pub fn match_all_types_custom<K: Clone>(bytes: &[u8], rules: &[MagicCustom<K>]) -> Vec<K> {
+    rules
+        .iter()
+        .filter(|rule| rule.matches_custom(bytes))
+        .map(|rule| rule.kind.clone())
+        .collect()
+}
+
+/// Returns an iterator over the `kind` of every rule in `rules` that matches the input `bytes`.
+///
+/// This is the `no_std` flavour of `match_all_types_custom`, yielding kinds in rule order.
+#[inline]
+#[cfg(not(feature = "std"))]
+pub fn match_all_types_custom<'r, K: Clone>(
+    bytes: &'r [u8],
+    rules: &'r [MagicCustom<'r, K>],
+) -> impl Iterator<Item = K> + 'r {
+    rules
+        .iter()
+        .filter(move |rule| rule.matches_custom(bytes))
+        .map(|rule| rule.kind.clone())
+}
+
+/// Returns every matching rule `kind` together with a confidence value, best candidate first.
+///
+/// The confidence of a candidate is [`MagicCustom::strength`] of the rule that matched,
+/// from `0` to `MAX_STRENGTH`. Ties keep the order of `rules`, and each kind is
+/// reported once, with its best confidence.
+///
+/// ---
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::magic_custom::{MagicCustom, CustomMatchRules, match_types_custom_ranked};
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// #[derive(Debug, Clone, Copy, PartialEq)]
+/// enum ShoujoKind {
+///     Shoujo,
+///     MagicalGirl,
+/// }
+///
+/// static RULES: &[MagicCustom<ShoujoKind>] = &[
+///     MagicCustom {
+///         signatures: &[b"Sh"],
+///         offsets: &[0],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::Shoujo,
+///         strength: Strength::Auto,
+///         rules: CustomMatchRules::Default,
+///     },
+///     MagicCustom {
+///         signatures: &[b"Shoujo"],
+///         offsets: &[0],
+///         max_bytes_read: 32,
+///         kind: ShoujoKind::MagicalGirl,
+///         strength: Strength::Fixed(90),
+///         rules: CustomMatchRules::Default,
+///     },
+/// ];
+///
+/// let ranked = match_types_custom_ranked(b"Shoujo", RULES);
+///
+/// assert_eq!(ranked[0].kind, ShoujoKind::MagicalGirl);
+/// assert_eq!(ranked[0].confidence, 90);
+/// assert_eq!(ranked[1].confidence, 20);
+/// ```
+#[must_use]
+#[cfg(feature = "std")]
+pub fn match_types_custom_ranked<K: Clone + PartialEq>(
+    bytes: &[u8],
+    rules: &[MagicCustom<K>],
+) -> Vec<RankedMatch<K>> {
+    rank(
+        rules
+            .iter()
+            .filter(|rule| rule.matches_custom(bytes))
+            .map(|rule| RankedMatch {
+                kind: rule.kind.clone(),
+                confidence: rule.strength(),
+            })
+            .collect(),
+    )
+}
+
 /// Macros with sugar-coated syntax for [`CustomMatchRules::AnyMatches`]
 #[macro_export]
 macro_rules! any_matches {
@@ -738,6 +1117,20 @@ macro_rules! all_matches {
     };
 }
 
+/// Macros with sugar-coated syntax for [`CustomMatchRules::Masked`]
+///
+/// Each argument is a hex pattern parsed at compile time by [`masked`](crate::masked),
+/// `??` being a wildcard byte.
+#[macro_export]
+macro_rules! masked_matches {
+    ($($pattern:expr),+ $(,)?) => {{
+        const __PATTERNS: &[$crate::magical::pattern::MaskedSignature] =
+            &[$($crate::masked!($pattern)),+];
+
+        $crate::magical::magic_custom::CustomMatchRules::Masked(__PATTERNS)
+    }};
+}
+
 #[macro_export]
 macro_rules! with_fn_matches {
     ($func:expr) => {
@@ -774,21 +1167,98 @@ macro_rules! with_fn_matches {
 ///     rules: any_matches!(find_shoujo_girl)
 /// );
 /// ```
+///
+/// `strength` is optional and defaults to [`Strength::Auto`]:
+///
+/// ```rust
+/// use magical_rs::magic_custom;
+/// use magical_rs::magical::magic_custom::CustomMatchRules;
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// let rule = magic_custom! (
+///     signatures: [b"MZ"],
+///     offsets: [0],
+///     max_bytes_read: 64,
+///     kind: "dos",
+///     strength: Strength::Fixed(5),
+///     rules: CustomMatchRules::Default
+/// );
+///
+/// assert_eq!(rule.strength(), 5);
+/// ```
+///
+/// Signatures with wildcards use `masked:` instead of `signatures:` and `rules:`:
+///
+/// ```rust
+/// use magical_rs::{magic_custom, match_custom};
+///
+/// let rule = magic_custom! (
+///     masked: ["FF D8 FF ??"],
+///     offsets: [0],
+///     max_bytes_read: 4,
+///     kind: "jpeg"
+/// );
+///
+/// let result = match_custom! {
+///     bytes: &[0xFF, 0xD8, 0xFF, 0xDB],
+///     rules: [rule],
+///     fallback: "unknown"
+/// };
+///
+/// assert_eq!(result, "jpeg");
+/// assert_eq!(rule.strength(), 30);
+/// ```
 #[macro_export]
 macro_rules! magic_custom {
     (signatures: [$($sig:expr),* $(,)?],
     offsets: [$($offsets:expr),* $(,)?],
     max_bytes_read: $max_bytes_read:expr,
     kind: $kind:expr,
+    rules: $rules:expr) => {
+        $crate::magic_custom!(
+            signatures: [$($sig),*],
+            offsets: [$($offsets),*],
+            max_bytes_read: $max_bytes_read,
+            kind: $kind,
+            strength: $crate::magical::match_rules::Strength::Auto,
+            rules: $rules
+        )
+    };
+    (signatures: [$($sig:expr),* $(,)?],
+    offsets: [$($offsets:expr),* $(,)?],
+    max_bytes_read: $max_bytes_read:expr,
+    kind: $kind:expr,
+    strength: $strength:expr,
     rules: $rules:expr) => {
         $crate::magical::magic_custom::MagicCustom {
             signatures: &[$($sig),*],
             offsets: &[$($offsets),*],
             max_bytes_read: $max_bytes_read,
             kind: $kind,
+            strength: $strength,
             rules: $rules
         }
     };
+    (masked: [$($pattern:expr),+ $(,)?],
+    offsets: [$($offsets:expr),* $(,)?],
+    max_bytes_read: $max_bytes_read:expr,
+    kind: $kind:expr
+    $(, strength: $strength:expr)? $(,)?) => {
+        $crate::magic_custom!(
+            signatures: [],
+            offsets: [$($offsets),*],
+            max_bytes_read: $max_bytes_read,
+            kind: $kind,
+            strength: $crate::magic_custom!(@strength $($strength)?),
+            rules: $crate::masked_matches!($($pattern),+)
+        )
+    };
+    (@strength) => {
+        $crate::magical::match_rules::Strength::Auto
+    };
+    (@strength $strength:expr) => {
+        $strength
+    };
 }
 
 /// Macros with sugar-coated syntax for [`match_types_custom`]
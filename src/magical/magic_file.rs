@@ -0,0 +1,1500 @@
+use crate::magical::bytes_read::DEFAULT_MAX_BYTES_READ;
+use crate::magical::match_rules::{MAX_STRENGTH, bytes_strength};
+use crate::magical::regex::Regex;
+use std::fmt;
+use std::string::{String, ToString};
+use std::vec::Vec;
+
+#[cfg(feature = "magical_dyn")]
+use {crate::magical::dyn_magic::DynMagicCustom, std::sync::Arc};
+
+/// Bytes searched by `search` and `regex` tests that do not give a range.
+pub const DEFAULT_SEARCH_RANGE: usize = 8192;
+
+/// Longest string printed for a `%s` in a message, and widest padding of a conversion.
+const MAX_PRINTED_STRING: usize = 64;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Endian {
+    Little,
+    Big,
+}
+
+/// How a number is read from the input.
+#[derive(Clone, Copy, Debug)]
+struct Width {
+    size: usize,
+    endian: Endian,
+    signed: bool,
+}
+
+impl Width {
+    fn read(self, bytes: &[u8], offset: usize) -> Option<u64> {
+        let raw = bytes.get(offset..offset.checked_add(self.size)?)?;
+        let mut buffer = [0u8; 8];
+
+        let value = match self.endian {
+            Endian::Little => {
+                buffer[..self.size].copy_from_slice(raw);
+                u64::from_le_bytes(buffer)
+            }
+            Endian::Big => {
+                buffer[8 - self.size..].copy_from_slice(raw);
+                u64::from_be_bytes(buffer)
+            }
+        };
+
+        Some(value)
+    }
+
+    const fn mask(self) -> u64 {
+        if self.size >= 8 {
+            u64::MAX
+        } else {
+            (1 << (self.size * 8)) - 1
+        }
+    }
+
+    /// Sign-extends `value` if this width is signed.
+    #[allow(clippy::cast_possible_wrap)]
+    const fn extend(self, value: u64) -> i64 {
+        if self.signed && self.size < 8 {
+            let shift = 64 - self.size * 8;
+            ((value << shift) as i64) >> shift
+        } else {
+            value as i64
+        }
+    }
+}
+
+/// Arithmetic applied to values (`lelong&0xff`) and to indirect offsets (`(4.l+2)`).
+#[derive(Clone, Copy, Debug)]
+enum Arith {
+    And,
+    Or,
+    Xor,
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+}
+
+impl Arith {
+    const fn parse(symbol: u8) -> Option<Self> {
+        match symbol {
+            b'&' => Some(Self::And),
+            b'|' => Some(Self::Or),
+            b'^' => Some(Self::Xor),
+            b'+' => Some(Self::Add),
+            b'-' => Some(Self::Sub),
+            b'*' => Some(Self::Mul),
+            b'/' => Some(Self::Div),
+            b'%' => Some(Self::Mod),
+            _ => None,
+        }
+    }
+
+    const fn apply(self, value: u64, operand: u64) -> Option<u64> {
+        Some(match self {
+            Self::And => value & operand,
+            Self::Or => value | operand,
+            Self::Xor => value ^ operand,
+            Self::Add => value.wrapping_add(operand),
+            Self::Sub => value.wrapping_sub(operand),
+            Self::Mul => value.wrapping_mul(operand),
+            Self::Div => match value.checked_div(operand) {
+                Some(value) => value,
+                None => return None,
+            },
+            Self::Mod => match value.checked_rem(operand) {
+                Some(value) => value,
+                None => return None,
+            },
+        })
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Offset {
+    /// `N`, from the start of the input.
+    Absolute(u64),
+    /// `-N`, from the end of the input.
+    FromEnd(u64),
+    /// `&N`, from the end of the last match one level up.
+    Relative(u64),
+    /// `(N.l+M)`: read a number at `N` and use it (adjusted) as the offset.
+    Indirect {
+        base: u64,
+        base_relative: bool,
+        width: Width,
+        adjust: Option<(Arith, u64)>,
+        relative: bool,
+    },
+}
+
+impl Offset {
+    fn resolve(&self, bytes: &[u8], parent_end: usize) -> Option<usize> {
+        let offset = match *self {
+            Self::Absolute(offset) => offset,
+            Self::FromEnd(offset) => (bytes.len() as u64).checked_sub(offset)?,
+            Self::Relative(offset) => (parent_end as u64).wrapping_add(offset),
+            Self::Indirect {
+                base,
+                base_relative,
+                width,
+                adjust,
+                relative,
+            } => {
+                let base = if base_relative {
+                    (parent_end as u64).wrapping_add(base)
+                } else {
+                    base
+                };
+
+                let value = width.read(bytes, usize::try_from(base).ok()?)?;
+                #[allow(clippy::cast_sign_loss)]
+                let mut value = width.extend(value) as u64;
+
+                if let Some((arith, operand)) = adjust {
+                    value = arith.apply(value, operand)?;
+                }
+
+                if relative {
+                    value = value.wrapping_add(parent_end as u64);
+                }
+
+                value
+            }
+        };
+
+        /* Wrapped around: a negative offset. */
+        if offset > i64::MAX as u64 {
+            return None;
+        }
+
+        usize::try_from(offset).ok()
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Compare {
+    /// `x`, always true.
+    Any,
+    Equal,
+    NotEqual,
+    Less,
+    Greater,
+    /// `&`, every bit of the test value is set.
+    AllSet,
+    /// `^`, every bit of the test value is clear.
+    AllClear,
+    /// `~`, equal to the negated test value.
+    Negated,
+}
+
+impl Compare {
+    fn parse(text: &str, numeric: bool) -> (Self, &str) {
+        if text == "x" {
+            return (Self::Any, "");
+        }
+
+        let compare = match text.as_bytes().first() {
+            Some(b'=') => Self::Equal,
+            Some(b'!') => Self::NotEqual,
+            Some(b'<') => Self::Less,
+            Some(b'>') => Self::Greater,
+            Some(b'&') if numeric => Self::AllSet,
+            Some(b'^') if numeric => Self::AllClear,
+            Some(b'~') if numeric => Self::Negated,
+            _ => return (Self::Equal, text),
+        };
+
+        (compare, text[1..].trim_start())
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default)]
+struct StringFlags {
+    /// `/c`: lower case letters of the test also match upper case input.
+    lower: bool,
+    /// `/C`: upper case letters of the test also match lower case input.
+    upper: bool,
+}
+
+impl StringFlags {
+    const fn byte_matches(self, expected: u8, actual: u8) -> bool {
+        expected == actual
+            || (self.lower
+                && expected.is_ascii_lowercase()
+                && expected == actual.to_ascii_lowercase())
+            || (self.upper
+                && expected.is_ascii_uppercase()
+                && expected == actual.to_ascii_uppercase())
+    }
+
+    fn starts_with(self, bytes: &[u8], expected: &[u8]) -> bool {
+        bytes.len() >= expected.len()
+            && expected
+                .iter()
+                .zip(bytes)
+                .all(|(&expected, &actual)| self.byte_matches(expected, actual))
+    }
+}
+
+#[derive(Clone, Debug)]
+enum Test {
+    Number {
+        width: Width,
+        mask: Option<(Arith, u64)>,
+        compare: Compare,
+        value: u64,
+    },
+    String {
+        value: Vec<u8>,
+        compare: Compare,
+        flags: StringFlags,
+    },
+    Search {
+        value: Vec<u8>,
+        range: usize,
+        flags: StringFlags,
+    },
+    Regex {
+        regex: Regex,
+        range: usize,
+        lines: bool,
+        start: bool,
+    },
+    Default,
+    Clear,
+}
+
+/// The value a test read, printed by the `%` conversions of its message.
+enum Value<'b> {
+    None,
+    Number(u64, Width),
+    Bytes(&'b [u8]),
+}
+
+impl Test {
+    /// Runs the test at `offset`. Returns the end of the match and the value read.
+    fn run<'b>(&self, bytes: &'b [u8], offset: usize) -> Option<(usize, Value<'b>)> {
+        match self {
+            Self::Number {
+                width,
+                mask,
+                compare,
+                value,
+            } => {
+                let mut read = width.read(bytes, offset)?;
+
+                if let Some((arith, operand)) = mask {
+                    read = arith.apply(read, *operand)? & width.mask();
+                }
+
+                let matched = match compare {
+                    Compare::Any => true,
+                    Compare::Equal => read == *value,
+                    Compare::NotEqual => read != *value,
+                    Compare::Less if width.signed => width.extend(read) < width.extend(*value),
+                    Compare::Greater if width.signed => width.extend(read) > width.extend(*value),
+                    Compare::Less => read < *value,
+                    Compare::Greater => read > *value,
+                    Compare::AllSet => read & value == *value,
+                    Compare::AllClear => read & value == 0,
+                    Compare::Negated => read == !value & width.mask(),
+                };
+
+                matched.then_some((offset + width.size, Value::Number(read, *width)))
+            }
+            Self::String {
+                value,
+                compare,
+                flags,
+            } => {
+                let rest = bytes.get(offset..)?;
+
+                if *compare == Compare::Any {
+                    let len = printed_len(rest);
+                    return Some((offset + len, Value::Bytes(&rest[..len])));
+                }
+
+                let window = rest.get(..value.len())?;
+                let matched = match compare {
+                    Compare::Less => window < value.as_slice(),
+                    Compare::Greater => window > value.as_slice(),
+                    Compare::NotEqual => !flags.starts_with(window, value),
+                    _ => flags.starts_with(window, value),
+                };
+
+                matched.then(|| {
+                    let len = printed_len(rest).max(value.len());
+                    (offset + value.len(), Value::Bytes(&rest[..len]))
+                })
+            }
+            Self::Search {
+                value,
+                range,
+                flags,
+            } => {
+                let window = bytes.get(offset..)?;
+                let window = &window[..window.len().min(range.saturating_add(value.len()))];
+
+                (0..=window.len().checked_sub(value.len())?)
+                    .find(|&start| flags.starts_with(&window[start..], value))
+                    .map(|start| {
+                        let end = start + value.len();
+                        (offset + end, Value::Bytes(&window[start..end]))
+                    })
+            }
+            Self::Regex {
+                regex,
+                range,
+                lines,
+                start,
+            } => {
+                let window = bytes.get(offset..)?;
+                let len = if *lines {
+                    window
+                        .iter()
+                        .enumerate()
+                        .filter(|&(_, &byte)| byte == b'\n')
+                        .nth(range.saturating_sub(1))
+                        .map_or(window.len(), |(index, _)| index + 1)
+                } else {
+                    window.len().min(*range)
+                };
+                let window = &window[..len];
+
+                regex.find(window).map(|(match_start, match_end)| {
+                    let end = if *start { match_start } else { match_end };
+                    (offset + end, Value::Bytes(&window[match_start..match_end]))
+                })
+            }
+            Self::Default | Self::Clear => Some((offset, Value::None)),
+        }
+    }
+
+    /// Base strength of the test, before `!:strength`.
+    const fn strength(&self) -> u8 {
+        let (strength, compare) = match self {
+            Self::Number { width, compare, .. } => (bytes_strength(width.size), *compare),
+            Self::String { value, compare, .. } => (bytes_strength(value.len()), *compare),
+            Self::Search { value, .. } => (bytes_strength(value.len()) / 2, Compare::Equal),
+            Self::Regex { .. } => (bytes_strength(1), Compare::Equal),
+            Self::Default | Self::Clear => (0, Compare::Any),
+        };
+
+        match compare {
+            Compare::Any => 0,
+            Compare::Equal | Compare::Negated => strength,
+            _ => strength / 2,
+        }
+    }
+
+    /// Bytes needed after the offset to run the test.
+    fn len(&self) -> usize {
+        match self {
+            Self::Number { width, .. } => width.size,
+            Self::String { value, .. } => value.len().max(MAX_PRINTED_STRING),
+            Self::Search { value, range, .. } => range.saturating_add(value.len()),
+            Self::Regex { range, lines, .. } => {
+                if *lines {
+                    DEFAULT_SEARCH_RANGE
+                } else {
+                    *range
+                }
+            }
+            Self::Default | Self::Clear => 0,
+        }
+    }
+}
+
+/// Length of the string printed by `%s`: up to a NUL byte or the end of the line.
+fn printed_len(bytes: &[u8]) -> usize {
+    bytes
+        .iter()
+        .take(MAX_PRINTED_STRING)
+        .position(|&byte| byte == 0 || byte == b'\n' || byte == b'\r')
+        .unwrap_or_else(|| bytes.len().min(MAX_PRINTED_STRING))
+}
+
+#[derive(Clone, Debug)]
+struct MagicLine {
+    line: usize,
+    level: usize,
+    offset: Offset,
+    test: Test,
+    message: String,
+    mime: Option<String>,
+    extensions: Vec<String>,
+    strength: Option<(Arith, u64)>,
+}
+
+/// A top-level test and its continuation lines.
+#[derive(Clone, Debug)]
+struct MagicEntry {
+    lines: Vec<MagicLine>,
+}
+
+/// The result of a magic(5) entry matching some input.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MagicMatch {
+    /// The messages of every matching line, joined like `file(1)` does.
+    pub description: String,
+    /// The first `!:mime` annotation of the matching lines.
+    pub mime: Option<String>,
+    /// The first `!:ext` annotation of the matching lines, split on `/`.
+    pub extensions: Vec<String>,
+    /// Strength of the top-level test (`!:strength` applied), from `0` to `MAX_STRENGTH`.
+    pub strength: u8,
+    /// Line number of the top-level test in the magic source.
+    pub line: usize,
+}
+
+impl MagicEntry {
+    fn strength(&self) -> u8 {
+        let first = &self.lines[0];
+        let strength = u64::from(first.test.strength());
+
+        let strength = first
+            .strength
+            .and_then(|(arith, operand)| arith.apply(strength, operand))
+            .unwrap_or(strength);
+
+        #[allow(clippy::cast_possible_truncation)]
+        let strength = strength.min(u64::from(MAX_STRENGTH)) as u8;
+
+        strength
+    }
+
+    fn max_bytes_read(&self) -> usize {
+        self.lines
+            .iter()
+            .map(|line| match line.offset {
+                Offset::Absolute(offset) => usize::try_from(offset)
+                    .unwrap_or(usize::MAX)
+                    .saturating_add(line.test.len()),
+                _ => DEFAULT_MAX_BYTES_READ.max(line.test.len()),
+            })
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Evaluates the entry like `file(1)`: the top-level line must match, then every
+    /// continuation line runs if the last line one level up matched.
+    fn evaluate(&self, bytes: &[u8]) -> Option<MagicMatch> {
+        let depth = self.lines.iter().map(|line| line.level).max().unwrap_or(0) + 2;
+
+        /* End of the last match at each level, for `&N` offsets. */
+        let mut ends = vec![0usize; depth];
+        /* Whether a line matched at each level, for `default` tests. */
+        let mut matched = vec![false; depth];
+        let mut enabled_level = 0;
+
+        let mut result = MagicMatch {
+            description: String::new(),
+            mime: None,
+            extensions: Vec::new(),
+            strength: self.strength(),
+            line: self.lines[0].line,
+        };
+
+        for line in &self.lines {
+            if line.level > enabled_level {
+                continue;
+            }
+
+            enabled_level = line.level;
+
+            let parent_end = if line.level == 0 {
+                0
+            } else {
+                ends[line.level - 1]
+            };
+
+            let outcome = match line.test {
+                Test::Default if matched[line.level] => None,
+                Test::Clear => {
+                    matched[line.level] = false;
+                    line.offset
+                        .resolve(bytes, parent_end)
+                        .map(|offset| (offset, Value::None))
+                }
+                _ => line
+                    .offset
+                    .resolve(bytes, parent_end)
+                    .and_then(|offset| line.test.run(bytes, offset)),
+            };
+
+            let Some((end, value)) = outcome else {
+                if line.level == 0 {
+                    return None;
+                }
+
+                continue;
+            };
+
+            if !matches!(line.test, Test::Clear) {
+                matched[line.level] = true;
+            }
+
+            ends[line.level] = end;
+            matched[line.level + 1] = false;
+            enabled_level = line.level + 1;
+
+            append_message(&mut result.description, &line.message, &value);
+
+            if result.mime.is_none() {
+                result.mime.clone_from(&line.mime);
+            }
+
+            if result.extensions.is_empty() {
+                result.extensions.clone_from(&line.extensions);
+            }
+        }
+
+        Some(result)
+    }
+}
+
+/// Appends a message to the description, with a space unless it starts with `\b`.
+fn append_message(description: &mut String, message: &str, value: &Value<'_>) {
+    let (message, separate) = message
+        .strip_prefix("\\b")
+        .map_or((message, true), |message| (message, false));
+
+    let formatted = format_message(message, value);
+
+    if formatted.is_empty() {
+        return;
+    }
+
+    if separate && !description.is_empty() {
+        description.push(' ');
+    }
+
+    description.push_str(&formatted);
+}
+
+/// Expands the `printf`-like conversions of a message with the value a test read.
+fn format_message(message: &str, value: &Value<'_>) -> String {
+    let mut output = String::with_capacity(message.len());
+    let mut chars = message.chars().peekable();
+
+    while let Some(char) = chars.next() {
+        if char != '%' {
+            output.push(char);
+            continue;
+        }
+
+        let mut flags = String::new();
+
+        while let Some(&flag @ ('-' | '+' | ' ' | '#' | '0')) = chars.peek() {
+            flags.push(flag);
+            chars.next();
+        }
+
+        let mut width = 0usize;
+
+        while let Some(digit) = chars.peek().and_then(|char| char.to_digit(10)) {
+            width = width.saturating_mul(10).saturating_add(digit as usize);
+            chars.next();
+        }
+
+        /* Messages come from untrusted files: no padding to gigabytes. */
+        let width = width.min(MAX_PRINTED_STRING);
+
+        let precision = if chars.peek() == Some(&'.') {
+            chars.next();
+            let mut digits = 0usize;
+
+            while let Some(digit) = chars.peek().and_then(|char| char.to_digit(10)) {
+                digits = digits.saturating_mul(10).saturating_add(digit as usize);
+                chars.next();
+            }
+
+            Some(digits.min(MAX_PRINTED_STRING))
+        } else {
+            None
+        };
+
+        while matches!(chars.peek(), Some('h' | 'l' | 'q' | 'j' | 'z' | 't')) {
+            chars.next();
+        }
+
+        let Some(conversion) = chars.next() else {
+            output.push('%');
+            break;
+        };
+
+        let text = match (conversion, value) {
+            ('%', _) => "%".to_owned(),
+            ('d' | 'i' | 's', Value::Number(number, width)) => width.extend(*number).to_string(),
+            ('u', Value::Number(number, _)) => number.to_string(),
+            ('x', Value::Number(number, _)) if flags.contains('#') => format!("{number:#x}"),
+            ('x', Value::Number(number, _)) => format!("{number:x}"),
+            ('X', Value::Number(number, _)) if flags.contains('#') => format!("0X{number:X}"),
+            ('X', Value::Number(number, _)) => format!("{number:X}"),
+            ('o', Value::Number(number, _)) => format!("{number:o}"),
+            #[allow(clippy::cast_possible_truncation)]
+            ('c', Value::Number(number, _)) => char::from(*number as u8).to_string(),
+            (_, Value::Bytes(bytes)) => {
+                let text = String::from_utf8_lossy(bytes);
+                precision.map_or_else(
+                    || text.to_string(),
+                    |precision| text.chars().take(precision).collect(),
+                )
+            }
+            _ => String::new(),
+        };
+
+        let padding = width.saturating_sub(text.chars().count());
+
+        if flags.contains('-') {
+            output.push_str(&text);
+            output.extend(core::iter::repeat_n(' ', padding));
+        } else {
+            let pad = if flags.contains('0') && matches!(value, Value::Number(..)) {
+                '0'
+            } else {
+                ' '
+            };
+
+            output.extend(core::iter::repeat_n(pad, padding));
+            output.push_str(&text);
+        }
+    }
+
+    output
+}
+
+/// Why a line of a magic(5) source was not loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum MagicFileErrorKind {
+    /// The line is valid magic(5), but uses something this parser does not implement
+    /// (`name`/`use`, `pstring`, dates, floats, `!:apple`...).
+    Unsupported(String),
+    /// The line is not valid magic(5).
+    Invalid(String),
+}
+
+/// A line of a magic(5) source that could not be loaded.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MagicFileError {
+    /// Line number, starting at `1`.
+    pub line: usize,
+    pub kind: MagicFileErrorKind,
+}
+
+impl fmt::Display for MagicFileError {
+    fn fmt(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.kind {
+            MagicFileErrorKind::Unsupported(what) => {
+                write!(formatter, "line {}: unsupported {what}", self.line)
+            }
+            MagicFileErrorKind::Invalid(why) => write!(formatter, "line {}: {why}", self.line),
+        }
+    }
+}
+
+impl std::error::Error for MagicFileError {}
+
+fn unsupported(what: impl Into<String>) -> MagicFileErrorKind {
+    MagicFileErrorKind::Unsupported(what.into())
+}
+
+fn invalid(why: impl Into<String>) -> MagicFileErrorKind {
+    MagicFileErrorKind::Invalid(why.into())
+}
+
+/// Parses a C-like number: decimal, `0x` hexadecimal or `0` octal, possibly negative.
+/// Negative numbers are returned in two's complement.
+pub(crate) fn parse_number(text: &str) -> Option<u64> {
+    let text = text.trim_end_matches(['l', 'L', 'u', 'U']);
+
+    let (negative, digits) = text.strip_prefix('-').map_or_else(
+        || (false, text.strip_prefix('+').unwrap_or(text)),
+        |digits| (true, digits),
+    );
+
+    if digits.is_empty() || digits.starts_with(['+', '-']) {
+        return None;
+    }
+
+    let magnitude = if let Some(hex) = digits
+        .strip_prefix("0x")
+        .or_else(|| digits.strip_prefix("0X"))
+    {
+        u64::from_str_radix(hex, 16).ok()?
+    } else if digits.len() > 1 && digits.starts_with('0') {
+        u64::from_str_radix(&digits[1..], 8).ok()?
+    } else {
+        digits.parse().ok()?
+    };
+
+    Some(if negative {
+        magnitude.wrapping_neg()
+    } else {
+        magnitude
+    })
+}
+
+/// Splits the longest leading number (`12`, `0x1F`) off `text`.
+fn split_number(text: &str) -> (&str, &str) {
+    let bytes = text.as_bytes();
+    let mut end = usize::from(
+        bytes
+            .first()
+            .is_some_and(|&byte| byte == b'-' || byte == b'+'),
+    );
+
+    if bytes[end..].starts_with(b"0x") || bytes[end..].starts_with(b"0X") {
+        end += 2;
+
+        while bytes.get(end).is_some_and(u8::is_ascii_hexdigit) {
+            end += 1;
+        }
+    } else {
+        while bytes.get(end).is_some_and(u8::is_ascii_digit) {
+            end += 1;
+        }
+    }
+
+    text.split_at(end)
+}
+
+/// Splits the first whitespace-separated field off `text`. Backslash escapes
+/// (`\ `) do not end the field.
+fn split_field(text: &str) -> (&str, &str) {
+    let bytes = text.as_bytes();
+    let mut index = 0;
+
+    while index < bytes.len() && !bytes[index].is_ascii_whitespace() {
+        index += if bytes[index] == b'\\' { 2 } else { 1 };
+    }
+
+    let index = index.min(bytes.len());
+
+    (&text[..index], text[index..].trim_start())
+}
+
+/// Decodes the C-like escapes of a string test.
+pub(crate) fn unescape(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut output = Vec::with_capacity(bytes.len());
+    let mut index = 0;
+
+    while index < bytes.len() {
+        if bytes[index] != b'\\' || index + 1 == bytes.len() {
+            output.push(bytes[index]);
+            index += 1;
+            continue;
+        }
+
+        index += 1;
+        let escaped = bytes[index];
+        index += 1;
+
+        match escaped {
+            b'n' => output.push(b'\n'),
+            b'r' => output.push(b'\r'),
+            b't' => output.push(b'\t'),
+            b'v' => output.push(0x0B),
+            b'f' => output.push(0x0C),
+            b'a' => output.push(0x07),
+            b'b' => output.push(0x08),
+            b'x' => {
+                let digits = bytes[index..]
+                    .iter()
+                    .take(2)
+                    .take_while(|byte| byte.is_ascii_hexdigit())
+                    .count();
+
+                if digits == 0 {
+                    output.push(b'x');
+                } else {
+                    let hex = core::str::from_utf8(&bytes[index..index + digits]).unwrap_or("0");
+                    output.push(u8::from_str_radix(hex, 16).unwrap_or_default());
+                    index += digits;
+                }
+            }
+            b'0'..=b'7' => {
+                let digits = 1 + bytes[index..]
+                    .iter()
+                    .take(2)
+                    .take_while(|byte| (b'0'..=b'7').contains(byte))
+                    .count();
+                let octal =
+                    core::str::from_utf8(&bytes[index - 1..index - 1 + digits]).unwrap_or("0");
+
+                #[allow(clippy::cast_possible_truncation)]
+                output.push(u16::from_str_radix(octal, 8).unwrap_or_default() as u8);
+                index += digits - 1;
+            }
+            other => output.push(other),
+        }
+    }
+
+    output
+}
+
+fn parse_width(name: &str) -> Option<Width> {
+    let (name, signed) = match name.strip_prefix('u') {
+        Some(
+            rest @ ("byte" | "short" | "long" | "quad" | "beshort" | "belong" | "bequad"
+            | "leshort" | "lelong" | "lequad"),
+        ) => (rest, false),
+        _ => (name, true),
+    };
+
+    /* Native endianness is taken as little-endian. */
+    let (endian, name) = name.strip_prefix("be").map_or_else(
+        || (Endian::Little, name.strip_prefix("le").unwrap_or(name)),
+        |name| (Endian::Big, name),
+    );
+
+    let size = match name {
+        "byte" => 1,
+        "short" => 2,
+        "long" => 4,
+        "quad" => 8,
+        _ => return None,
+    };
+
+    Some(Width {
+        size,
+        endian,
+        signed,
+    })
+}
+
+/// Type names of magic(5) that this parser knows but does not implement.
+const UNSUPPORTED_TYPES: &[&str] = &[
+    "pstring",
+    "date",
+    "ldate",
+    "qdate",
+    "qldate",
+    "qwdate",
+    "bedate",
+    "beldate",
+    "beqdate",
+    "beqldate",
+    "beqwdate",
+    "ledate",
+    "leldate",
+    "leqdate",
+    "leqldate",
+    "leqwdate",
+    "medate",
+    "meldate",
+    "melong",
+    "float",
+    "befloat",
+    "lefloat",
+    "double",
+    "bedouble",
+    "ledouble",
+    "bestring16",
+    "lestring16",
+    "indirect",
+    "name",
+    "use",
+    "der",
+    "guid",
+    "offset",
+    "octal",
+    "msdosdate",
+    "lemsdosdate",
+    "bemsdosdate",
+    "msdostime",
+    "lemsdostime",
+    "bemsdostime",
+];
+
+fn parse_indirect_width(symbol: u8) -> Result<Width, MagicFileErrorKind> {
+    let (size, endian) = match symbol {
+        b'b' | b'c' | b'B' | b'C' => (1, Endian::Little),
+        b's' | b'h' => (2, Endian::Little),
+        b'S' | b'H' => (2, Endian::Big),
+        b'l' => (4, Endian::Little),
+        b'L' => (4, Endian::Big),
+        b'q' => (8, Endian::Little),
+        b'Q' => (8, Endian::Big),
+        b'm' | b'e' | b'f' | b'g' | b'E' | b'F' | b'G' | b'i' | b'I' => {
+            return Err(unsupported(format!(
+                "indirect offset type `.{}`",
+                symbol as char
+            )));
+        }
+        other => {
+            return Err(invalid(format!(
+                "unknown indirect offset type `.{}`",
+                other as char
+            )));
+        }
+    };
+
+    Ok(Width {
+        size,
+        endian,
+        signed: false,
+    })
+}
+
+fn parse_offset(text: &str) -> Result<Offset, MagicFileErrorKind> {
+    let (relative, text) = text
+        .strip_prefix('&')
+        .map_or((false, text), |rest| (true, rest));
+
+    let Some(inner) = text.strip_prefix('(') else {
+        let value =
+            parse_number(text).ok_or_else(|| invalid(format!("invalid offset `{text}`")))?;
+
+        return Ok(if relative {
+            Offset::Relative(value)
+        } else if text.starts_with('-') {
+            Offset::FromEnd(value.wrapping_neg())
+        } else {
+            Offset::Absolute(value)
+        });
+    };
+
+    let close = inner
+        .find(')')
+        .ok_or_else(|| invalid("unclosed indirect offset"))?;
+    let (inner, outer) = (&inner[..close], &inner[close + 1..]);
+
+    let (base_relative, inner) = inner
+        .strip_prefix('&')
+        .map_or((false, inner), |rest| (true, rest));
+
+    let (base, rest) = split_number(inner);
+    let base = parse_number(base)
+        .ok_or_else(|| invalid(format!("invalid indirect offset `({inner})`")))?;
+
+    let (width, rest) = match rest.as_bytes() {
+        [separator @ (b'.' | b','), symbol, ..] => {
+            let mut width = parse_indirect_width(*symbol)?;
+            width.signed = *separator == b',';
+            (width, &rest[2..])
+        }
+        _ => (
+            Width {
+                size: 4,
+                endian: Endian::Little,
+                signed: false,
+            },
+            rest,
+        ),
+    };
+
+    let adjust = match rest.as_bytes().first() {
+        None => None,
+        Some(&symbol) => {
+            let arith = Arith::parse(symbol)
+                .ok_or_else(|| invalid(format!("invalid indirect offset `({inner})`")))?;
+
+            if rest[1..].starts_with('(') {
+                return Err(unsupported("nested indirect offset adjustment"));
+            }
+
+            let operand = parse_number(&rest[1..])
+                .ok_or_else(|| invalid(format!("invalid indirect offset `({inner})`")))?;
+
+            Some((arith, operand))
+        }
+    };
+
+    if !outer.is_empty() {
+        return Err(unsupported(format!(
+            "adjustment `{outer}` after an indirect offset"
+        )));
+    }
+
+    Ok(Offset::Indirect {
+        base,
+        base_relative,
+        width,
+        adjust,
+        relative,
+    })
+}
+
+fn parse_string_flags(letters: &str) -> Result<StringFlags, MagicFileErrorKind> {
+    let mut flags = StringFlags::default();
+
+    for letter in letters.chars() {
+        match letter {
+            'c' => flags.lower = true,
+            'C' => flags.upper = true,
+            /* Binary / text hints and trimming only affect libmagic's output. */
+            'b' | 't' | 'T' => {}
+            other => return Err(unsupported(format!("string flag `/{other}`"))),
+        }
+    }
+
+    Ok(flags)
+}
+
+/// Parses the `/N/flags` suffix of `string`, `search` and `regex` types.
+fn parse_suffix(suffix: &str) -> Result<(Option<usize>, String), MagicFileErrorKind> {
+    let mut range = None;
+    let mut letters = String::new();
+
+    for part in suffix.split('/').filter(|part| !part.is_empty()) {
+        let (number, rest) = split_number(part);
+
+        if !number.is_empty() {
+            let number = parse_number(number)
+                .and_then(|number| usize::try_from(number).ok())
+                .ok_or_else(|| invalid(format!("invalid range `{part}`")))?;
+            range = Some(number);
+        }
+
+        letters.push_str(rest);
+    }
+
+    Ok((range, letters))
+}
+
+fn parse_test(kind: &str, test: &str) -> Result<Test, MagicFileErrorKind> {
+    let (name, suffix) = kind.split_once('/').unwrap_or((kind, ""));
+
+    match name {
+        "default" => return Ok(Test::Default),
+        "clear" => return Ok(Test::Clear),
+        "string" => {
+            let (_, letters) = parse_suffix(suffix)?;
+            let flags = parse_string_flags(&letters)?;
+            let (compare, value) = Compare::parse(test, false);
+
+            return Ok(Test::String {
+                value: unescape(value),
+                compare,
+                flags,
+            });
+        }
+        "search" => {
+            let (range, letters) = parse_suffix(suffix)?;
+            let flags = parse_string_flags(&letters)?;
+            let (compare, value) = Compare::parse(test, false);
+
+            if compare != Compare::Equal {
+                return Err(unsupported("comparison operator on a `search` test"));
+            }
+
+            return Ok(Test::Search {
+                value: unescape(value),
+                range: range.unwrap_or(DEFAULT_SEARCH_RANGE),
+                flags,
+            });
+        }
+        "regex" => {
+            let (range, letters) = parse_suffix(suffix)?;
+            let (mut case_insensitive, mut lines, mut start) = (false, false, false);
+
+            for letter in letters.chars() {
+                match letter {
+                    'c' => case_insensitive = true,
+                    'l' => lines = true,
+                    's' => start = true,
+                    other => return Err(unsupported(format!("regex flag `/{other}`"))),
+                }
+            }
+
+            let regex = Regex::new(&unescape(test), case_insensitive)
+                .map_err(|error| invalid(format!("invalid regex `{test}`: {error}")))?;
+
+            return Ok(Test::Regex {
+                regex,
+                range: range.unwrap_or(DEFAULT_SEARCH_RANGE),
+                lines,
+                start,
+            });
+        }
+        _ => {}
+    }
+
+    let split = kind
+        .find(|char: char| "&|^+-*/%".contains(char))
+        .unwrap_or(kind.len());
+    let (name, mask) = kind.split_at(split);
+
+    let Some(width) = parse_width(name) else {
+        let base = name.trim_start_matches('u');
+
+        return Err(
+            if UNSUPPORTED_TYPES.contains(&name) || UNSUPPORTED_TYPES.contains(&base) {
+                unsupported(format!("type `{name}`"))
+            } else {
+                invalid(format!("unknown type `{name}`"))
+            },
+        );
+    };
+
+    let mask = match mask.as_bytes().first() {
+        None => None,
+        Some(&symbol) => {
+            let arith =
+                Arith::parse(symbol).ok_or_else(|| invalid(format!("invalid mask `{mask}`")))?;
+            let operand = parse_number(&mask[1..])
+                .ok_or_else(|| invalid(format!("invalid mask `{mask}`")))?;
+
+            Some((arith, operand))
+        }
+    };
+
+    let (compare, value) = Compare::parse(test, true);
+
+    let value = if compare == Compare::Any {
+        0
+    } else {
+        parse_number(value).ok_or_else(|| invalid(format!("invalid number `{value}`")))?
+    };
+
+    Ok(Test::Number {
+        width,
+        mask,
+        compare,
+        value: value & width.mask(),
+    })
+}
+
+fn parse_test_line(text: &str, line: usize) -> Result<MagicLine, MagicFileErrorKind> {
+    let level = text.bytes().take_while(|&byte| byte == b'>').count();
+    let text = &text[level..];
+
+    let (offset, rest) = split_field(text);
+    let (kind, rest) = split_field(rest);
+    let (value, message) = split_field(rest);
+
+    if kind.is_empty() {
+        return Err(invalid("missing type"));
+    }
+
+    if value.is_empty() && !matches!(kind, "default" | "clear") {
+        return Err(invalid("missing test value"));
+    }
+
+    Ok(MagicLine {
+        line,
+        level,
+        offset: parse_offset(offset)?,
+        test: parse_test(kind, value)?,
+        message: message.trim_end().to_owned(),
+        mime: None,
+        extensions: Vec::new(),
+        strength: None,
+    })
+}
+
+fn parse_annotation(text: &str, target: &mut MagicLine) -> Result<(), MagicFileErrorKind> {
+    let (name, value) = split_field(text);
+    let value = value.trim();
+
+    match name {
+        "mime" => target.mime = Some(value.to_owned()),
+        "ext" => target.extensions = value.split('/').map(str::to_owned).collect(),
+        "strength" => {
+            let value: String = value.split_whitespace().collect();
+            let arith = value
+                .bytes()
+                .next()
+                .and_then(Arith::parse)
+                .filter(|arith| matches!(arith, Arith::Add | Arith::Sub | Arith::Mul | Arith::Div))
+                .ok_or_else(|| invalid(format!("invalid strength `{value}`")))?;
+            let operand = parse_number(&value[1..])
+                .ok_or_else(|| invalid(format!("invalid strength `{value}`")))?;
+
+            target.strength = Some((arith, operand));
+        }
+        other => return Err(unsupported(format!("annotation `!:{other}`"))),
+    }
+
+    Ok(())
+}
+
+/// A set of runtime rules loaded from libmagic's magic(5) source format.
+///
+/// Supported:
+/// - Absolute (`16`), end-relative (`-128`), relative (`&4`) and indirect (`(0x3c.l+4)`,
+///   `&(4.S)`, `(&0.b)`) offsets.
+/// - `byte`, `short`, `long`, `quad` tests with `le`/`be` endianness, `u` (unsigned) prefix,
+///   masks (`belong&0xfffffff0`) and the `=`, `!`, `<`, `>`, `&`, `^`, `~` and `x` comparisons.
+/// - `string` (with `/c`, `/C`), `search/N` and `regex/N[c][l][s]` tests, `default` and `clear`.
+/// - Continuation levels (`>`, `>>`, ...) and messages with `printf`-like conversions
+///   (`%d`, `%u`, `%x`, `%s`, `%c`...), `\b` to join without a space.
+/// - `!:mime`, `!:ext` and `!:strength` annotations.
+///
+/// Native endianness (`short`, `long`, `quad`) is taken as little-endian.
+///
+/// Lines using anything else (`name`/`use`, `pstring`, dates, floats, `!:apple`...) are
+/// reported with their line number, see [`MagicFile::parse`] and [`MagicFile::parse_lossy`].
+///
+/// Entries are checked in file order, and the first matching entry wins.
+///
+/// ---
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::magic_file::MagicFile;
+///
+/// let magic = MagicFile::parse(r"
+/// 0       string      MAHOU       Magical girl record
+/// >5      byte        x           \b, version %d
+/// >6      string/c    wand        \b, with a wand
+/// !:mime  application/x-mahou
+/// ").unwrap();
+///
+/// let found = magic.match_types(b"MAHOU\x02WAND").unwrap();
+///
+/// assert_eq!(found.description, "Magical girl record, version 2, with a wand");
+/// assert_eq!(found.mime.as_deref(), Some("application/x-mahou"));
+/// assert!(magic.match_types(b"IDOL").is_none());
+/// ```
+///
+/// ---
+///
+/// # Standard Library Context:
+/// [`MagicFile`] requires the `std` feature.
+#[derive(Clone, Debug, Default)]
+pub struct MagicFile {
+    entries: Vec<MagicEntry>,
+}
+
+impl MagicFile {
+    /// Parses a magic(5) source.
+    ///
+    /// # Errors
+    ///
+    /// Returns every line that could not be loaded, unsupported or invalid,
+    /// if there is at least one. Use [`MagicFile::parse_lossy`] to load the rest anyway.
+    pub fn parse(source: &str) -> Result<Self, Vec<MagicFileError>> {
+        let (magic, errors) = Self::parse_lossy(source);
+
+        if errors.is_empty() {
+            Ok(magic)
+        } else {
+            Err(errors)
+        }
+    }
+
+    /// Parses a magic(5) source, skipping the lines that cannot be loaded.
+    ///
+    /// A skipped line takes its continuation lines with it (and a skipped top-level line,
+    /// its whole entry), since they cannot be evaluated without it. Every skipped line
+    /// that is itself unsupported or invalid is returned with its line number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::magic_file::{MagicFile, MagicFileErrorKind};
+    ///
+    /// let (magic, errors) = MagicFile::parse_lossy("
+    /// 0   string  SHOUJO  Shoujo manga
+    /// 0   name    idol
+    /// 0   belong  0xCAFED00D  Cute data
+    /// ");
+    ///
+    /// assert_eq!(magic.len(), 2);
+    /// assert_eq!(errors.len(), 1);
+    /// assert_eq!(errors[0].line, 3);
+    /// assert!(matches!(errors[0].kind, MagicFileErrorKind::Unsupported(_)));
+    /// ```
+    #[must_use]
+    pub fn parse_lossy(source: &str) -> (Self, Vec<MagicFileError>) {
+        let mut entries: Vec<MagicEntry> = Vec::new();
+        let mut errors = Vec::new();
+        /* Level of the last skipped line: deeper lines that follow are skipped too. */
+        let mut skipped_level: Option<usize> = None;
+        /* Level of the last loaded line of the current entry. */
+        let mut last_level: Option<usize> = None;
+
+        for (index, text) in source.lines().enumerate() {
+            let line = index + 1;
+            let text = text.trim_start();
+
+            if text.is_empty() || text.starts_with('#') {
+                continue;
+            }
+
+            if let Some(annotation) = text.strip_prefix("!:") {
+                let target = if skipped_level.is_some() {
+                    None
+                } else {
+                    entries.last_mut().and_then(|entry| entry.lines.last_mut())
+                };
+
+                /* Annotations of skipped lines are still checked, into a throwaway line. */
+                let mut scratch = MagicLine {
+                    line,
+                    level: 0,
+                    offset: Offset::Absolute(0),
+                    test: Test::Default,
+                    message: String::new(),
+                    mime: None,
+                    extensions: Vec::new(),
+                    strength: None,
+                };
+
+                if let Err(kind) = parse_annotation(annotation, target.unwrap_or(&mut scratch)) {
+                    errors.push(MagicFileError { line, kind });
+                }
+
+                continue;
+            }
+
+            let parsed = parse_test_line(text, line).and_then(|parsed| {
+                let parent_missing = parsed.level > 0
+                    && last_level.is_none_or(|last_level| parsed.level > last_level + 1);
+
+                if parent_missing && skipped_level.is_none() {
+                    Err(invalid(format!(
+                        "continuation level {} without a level {} line",
+                        parsed.level,
+                        parsed.level - 1
+                    )))
+                } else {
+                    Ok(parsed)
+                }
+            });
+
+            let level = text.bytes().take_while(|&byte| byte == b'>').count();
+
+            if let Some(skipped) = skipped_level {
+                if level > skipped {
+                    if let Err(kind) = parsed {
+                        errors.push(MagicFileError { line, kind });
+                    }
+
+                    continue;
+                }
+
+                skipped_level = None;
+            }
+
+            match parsed {
+                Ok(parsed) => {
+                    last_level = Some(parsed.level);
+
+                    if parsed.level == 0 {
+                        entries.push(MagicEntry {
+                            lines: vec![parsed],
+                        });
+                    } else if let Some(entry) = entries.last_mut() {
+                        entry.lines.push(parsed);
+                    }
+                }
+                Err(kind) => {
+                    errors.push(MagicFileError { line, kind });
+                    skipped_level = Some(level);
+
+                    if level == 0 {
+                        last_level = None;
+                    }
+                }
+            }
+        }
+
+        (Self { entries }, errors)
+    }
+
+    /// Returns the number of top-level entries.
+    #[must_use]
+    pub const fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns `true` if no entry was loaded.
+    #[must_use]
+    pub const fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns the number of bytes to read from the beginning of a file
+    /// for every entry to be evaluated.
+    ///
+    /// Entries with relative, indirect or end-relative offsets cannot be bounded
+    /// ahead of time, they count as `DEFAULT_MAX_BYTES_READ`. End-relative offsets
+    /// (`-N`) count from the end of the bytes given, so pass the whole file for them.
+    #[must_use]
+    pub fn max_bytes_read(&self) -> usize {
+        self.entries
+            .iter()
+            .map(MagicEntry::max_bytes_read)
+            .max()
+            .unwrap_or_default()
+    }
+
+    /// Returns the first entry matching `bytes`, with its description.
+    #[must_use]
+    pub fn match_types(&self, bytes: &[u8]) -> Option<MagicMatch> {
+        self.entries.iter().find_map(|entry| entry.evaluate(bytes))
+    }
+
+    /// Returns every entry matching `bytes`, in file order.
+    #[must_use]
+    pub fn match_all_types(&self, bytes: &[u8]) -> Vec<MagicMatch> {
+        self.entries
+            .iter()
+            .filter_map(|entry| entry.evaluate(bytes))
+            .collect()
+    }
+
+    /// Turns every entry into a [`DynMagicCustom`] rule, so magic(5) rules can be
+    /// evaluated alongside hand-written dynamic rules with `match_dyn_types`.
+    ///
+    /// A rule matches when its top-level test matches. Its `kind` is a [`MagicMatch`]
+    /// holding the top-level message (conversions are not expanded), MIME type,
+    /// extensions and strength. Use [`MagicFile::match_types`] for full descriptions.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use magical_rs::magical::dyn_magic::{DynMagicCustom, match_dyn_types_as};
+    /// use magical_rs::magical::magic_file::{MagicFile, MagicMatch};
+    ///
+    /// let magic = MagicFile::parse("0 string MAHOU Magical girl record").unwrap();
+    /// let rules: Vec<DynMagicCustom> = magic.into_dyn_rules();
+    ///
+    /// let found = match_dyn_types_as::<MagicMatch>(b"MAHOU!", &rules).unwrap();
+    /// assert_eq!(found.description, "Magical girl record");
+    /// ```
+    ///
+    /// ---
+    ///
+    /// # Feature:
+    /// Requires the `magical_dyn` feature.
+    #[cfg(feature = "magical_dyn")]
+    #[must_use]
+    pub fn into_dyn_rules(self) -> Vec<DynMagicCustom> {
+        self.entries
+            .into_iter()
+            .map(|entry| {
+                let first = &entry.lines[0];
+                let mut description = String::new();
+                append_message(&mut description, &first.message, &Value::None);
+
+                let kind = MagicMatch {
+                    description,
+                    mime: first.mime.clone(),
+                    extensions: first.extensions.clone(),
+                    strength: entry.strength(),
+                    line: first.line,
+                };
+                let max_bytes_read = entry.max_bytes_read();
+                let entry = Arc::new(entry);
+
+                DynMagicCustom::new(
+                    move |bytes: &[u8]| entry.evaluate(bytes).is_some(),
+                    kind,
+                    max_bytes_read,
+                )
+            })
+            .collect()
+    }
+}
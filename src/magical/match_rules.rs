@@ -1,4 +1,221 @@
+use crate::magical::pattern::MaskedSignature;
+
 pub enum MatchRules {
     Default,
     WithFn(fn(bytes: &[u8]) -> bool),
+    /// Matches if any of the masked signatures matches at any of the rule's offsets.
+    /// Leave `signatures` as `&[]` with this rule.
+    Masked(&'static [MaskedSignature]),
+    /// Like `Default`, but offsets count back from the end of the file:
+    /// offset `22` means the signature starts 22 bytes before the end.
+    /// Used for trailers and footers (ZIP end of central directory, DMG `koly` block...).
+    FromEnd,
+    /// Like `WithFn`, but the function gets the first bytes (`head`) and the last bytes
+    /// (`tail`) of the file, for formats marked at both ends (Parquet `PAR1`).
+    /// End-anchored like `FromEnd`: `max_bytes_read` counts from the end of the file.
+    WithTailFn(fn(head: &[u8], tail: &[u8]) -> bool),
+}
+
+/// Checks whether `signature` starts `offset` bytes before the end of `tail`.
+///
+/// `tail` must be the last bytes of the file (or the whole file).
+#[must_use]
+#[inline]
+pub fn signature_at_end(tail: &[u8], signature: &[u8], offset: usize) -> bool {
+    tail.len()
+        .checked_sub(offset)
+        .and_then(|start| tail.get(start..start.checked_add(signature.len())?))
+        .is_some_and(|window| window == signature)
+}
+
+/// The highest strength a rule can have, also the highest confidence reported.
+pub const MAX_STRENGTH: u8 = 100;
+/// Strength gained per signature byte compared by a rule.
+pub const STRENGTH_PER_BYTE: u8 = 10;
+/// Strength of a rule decided by a single function pointer.
+pub const FN_STRENGTH: u8 = 60;
+/// Strength of a rule decided by several `OR`'d function pointers.
+pub const ANY_FN_STRENGTH: u8 = 40;
+/// Bits of signature worth one byte, for [`alternatives_penalty`].
+const BITS_PER_BYTE: u32 = 8;
+
+/// How strongly a matching rule identifies its kind.
+///
+/// Used to rank candidates when several rules match the same bytes,
+/// instead of relying only on the order of the rule table.
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::match_rules::Strength;
+///
+/// assert_eq!(Strength::Fixed(250).value_or(0), 100);
+/// assert_eq!(Strength::Auto.value_or(42), 42);
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum Strength {
+    /// Derived from the rule itself: the length of its shortest signature, less a little
+    /// for every signature and offset it may match at, or the number of functions that
+    /// must all pass. A function is a single check, whatever it looks at.
+    #[default]
+    Auto,
+    /// An explicit strength, clamped to [`MAX_STRENGTH`].
+    Fixed(u8),
+}
+
+impl Strength {
+    /// Returns the fixed value (clamped to [`MAX_STRENGTH`]), or `derived` for [`Strength::Auto`].
+    #[must_use]
+    #[inline]
+    pub const fn value_or(self, derived: u8) -> u8 {
+        match self {
+            Self::Auto => derived,
+            Self::Fixed(value) if value > MAX_STRENGTH => MAX_STRENGTH,
+            Self::Fixed(value) => value,
+        }
+    }
+}
+
+/// Derives a strength from the number of significant bytes a signature compares.
+#[must_use]
+#[inline]
+pub const fn bytes_strength(len: usize) -> u8 {
+    if len >= (MAX_STRENGTH / STRENGTH_PER_BYTE) as usize {
+        MAX_STRENGTH
+    } else {
+        #[allow(clippy::cast_possible_truncation)]
+        let strength = len as u8 * STRENGTH_PER_BYTE;
+
+        strength
+    }
+}
+
+/// Derives the strength lost by a rule that may match at `alternatives` positions
+/// (signatures times offsets).
+///
+/// Any of them is enough for a match, so each doubling gives random bytes one more bit of
+/// chance: an eighth of [`STRENGTH_PER_BYTE`].
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::magical::match_rules::alternatives_penalty;
+///
+/// assert_eq!(alternatives_penalty(1), 0);
+/// assert_eq!(alternatives_penalty(2), 1);
+/// assert_eq!(alternatives_penalty(8), 3);
+/// ```
+#[must_use]
+#[inline]
+pub const fn alternatives_penalty(alternatives: usize) -> u8 {
+    if alternatives <= 1 {
+        return 0;
+    }
+
+    let bits = usize::BITS - (alternatives - 1).leading_zeros();
+
+    #[allow(clippy::cast_possible_truncation)]
+    let penalty = (bits * STRENGTH_PER_BYTE as u32 / BITS_PER_BYTE) as u8;
+
+    penalty
+}
+
+/// Derives a strength from the shortest signature a rule compares, at `offsets` offsets.
+///
+/// Any of the signatures is enough for a match, so the rule is only as strong as the
+/// weakest one, less [`alternatives_penalty`] for the number of signatures and offsets.
+/// Two-byte signatures like `BM` or `MZ` get `20`, an 8-byte PNG signature gets `80`.
+#[must_use]
+#[inline]
+pub const fn signature_strength(signatures: &[&[u8]], offsets: usize) -> u8 {
+    if signatures.is_empty() {
+        return 0;
+    }
+
+    let mut shortest = usize::MAX;
+    let mut index = 0;
+
+    while index < signatures.len() {
+        if signatures[index].len() < shortest {
+            shortest = signatures[index].len();
+        }
+
+        index += 1;
+    }
+
+    bytes_strength(shortest).saturating_sub(alternatives_penalty(
+        signatures.len().saturating_mul(offsets),
+    ))
+}
+
+/// Derives a strength from the masked signature with the fewest significant bytes,
+/// at `offsets` offsets, like [`signature_strength`].
+///
+/// Wildcard bytes do not count, so `FF D8 FF ??` gets `30`.
+#[must_use]
+#[inline]
+pub const fn masked_strength(patterns: &[MaskedSignature], offsets: usize) -> u8 {
+    if patterns.is_empty() {
+        return 0;
+    }
+
+    let mut shortest = usize::MAX;
+    let mut index = 0;
+
+    while index < patterns.len() {
+        let significant = patterns[index].significant_len();
+
+        if significant < shortest {
+            shortest = significant;
+        }
+
+        index += 1;
+    }
+
+    bytes_strength(shortest)
+        .saturating_sub(alternatives_penalty(patterns.len().saturating_mul(offsets)))
+}
+
+/// Derives a strength from the number of function pointers that must all pass.
+#[must_use]
+#[inline]
+pub const fn all_fn_strength(count: usize) -> u8 {
+    let extra = if count > 4 {
+        4
+    } else {
+        count.saturating_sub(1)
+    };
+
+    #[allow(clippy::cast_possible_truncation)]
+    let strength = FN_STRENGTH + extra as u8 * STRENGTH_PER_BYTE;
+
+    strength
+}
+
+/// A matching candidate together with how confident the match is.
+///
+/// `confidence` ranges from `0` to [`MAX_STRENGTH`] and is the strength
+/// of the rule that produced the match.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RankedMatch<K> {
+    pub kind: K,
+    pub confidence: u8,
+}
+
+/// Sorts candidates by descending confidence and keeps the best one of each kind.
+///
+/// The sort is stable, so ties keep the order of the rule table.
+#[cfg(feature = "std")]
+pub(crate) fn rank<K: PartialEq>(mut candidates: Vec<RankedMatch<K>>) -> Vec<RankedMatch<K>> {
+    candidates.sort_by_key(|candidate| core::cmp::Reverse(candidate.confidence));
+
+    let mut ranked: Vec<RankedMatch<K>> = Vec::with_capacity(candidates.len());
+
+    for candidate in candidates {
+        if !ranked.iter().any(|seen| seen.kind == candidate.kind) {
+            ranked.push(candidate);
+        }
+    }
+
+    ranked
 }
@@ -0,0 +1,254 @@
+/// A signature with a per-byte mask, for formats with variable bytes.
+///
+/// A byte of the input matches when `input & mask == bytes & mask`, so:
+/// - a `0xFF` mask byte compares the whole byte,
+/// - a `0x00` mask byte is a wildcard,
+/// - anything in between compares only some bits (e.g. `0xF0` for a nibble).
+///
+/// Usable in both built-in rules (`MatchRules::Masked`) and custom rules
+/// ([`CustomMatchRules::Masked`]), at the offsets of the rule.
+///
+/// The easiest way to build one is the [`masked`] macro, which parses a
+/// hex pattern with `??` wildcards at compile time:
+///
+/// ```rust
+/// use magical_rs::masked;
+/// use magical_rs::magical::pattern::MaskedSignature;
+///
+/// const JPEG: MaskedSignature = masked!("FF D8 FF ??");
+///
+/// assert!(JPEG.matches_at(&[0xFF, 0xD8, 0xFF, 0xE1], 0));
+/// assert!(!JPEG.matches_at(&[0xFF, 0xD8, 0x00, 0xE1], 0));
+/// ```
+///
+/// It can also be written by hand:
+///
+/// ```rust
+/// use magical_rs::magical::pattern::MaskedSignature;
+///
+/// static RIFF_WAVE: MaskedSignature = MaskedSignature::new(
+///     b"RIFF\0\0\0\0WAVE",
+///     &[0xFF, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0xFF, 0xFF, 0xFF, 0xFF],
+/// );
+///
+/// assert!(RIFF_WAVE.matches_at(b"RIFF\x24\x08\0\0WAVEfmt ", 0));
+/// ```
+///
+/// # No Standard Library Context:
+/// Fully `const` and `no_std`, it only requires Rust's [`core`].
+///
+/// [`CustomMatchRules::Masked`]: crate::magical::magic_custom::CustomMatchRules::Masked
+/// [`masked`]: crate::masked
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MaskedSignature {
+    /// Expected bytes. Bits outside `mask` are ignored.
+    pub bytes: &'static [u8],
+    /// Bits of each byte that must match, same length as `bytes`.
+    pub mask: &'static [u8],
+}
+
+impl MaskedSignature {
+    /// Creates a masked signature.
+    ///
+    /// # Panics
+    ///
+    /// Panics (at compile time in a `const` context) if `bytes` and `mask`
+    /// do not have the same length.
+    #[must_use]
+    pub const fn new(bytes: &'static [u8], mask: &'static [u8]) -> Self {
+        assert!(
+            bytes.len() == mask.len(),
+            "masked signature bytes and mask must have the same length"
+        );
+
+        Self { bytes, mask }
+    }
+
+    /// Number of bytes covered by this signature, wildcards included.
+    #[must_use]
+    #[inline]
+    pub const fn len(&self) -> usize {
+        self.bytes.len()
+    }
+
+    /// Returns `true` if the signature covers no byte at all.
+    #[must_use]
+    #[inline]
+    pub const fn is_empty(&self) -> bool {
+        self.bytes.is_empty()
+    }
+
+    /// Number of bytes that are not full wildcards.
+    #[must_use]
+    pub const fn significant_len(&self) -> usize {
+        let mut count = 0;
+        let mut index = 0;
+
+        while index < self.mask.len() {
+            if self.mask[index] != 0 {
+                count += 1;
+            }
+
+            index += 1;
+        }
+
+        count
+    }
+
+    /// Returns the first byte if it is fully significant (mask `0xFF`).
+    #[must_use]
+    pub const fn exact_first_byte(&self) -> Option<u8> {
+        match (self.bytes.first(), self.mask.first()) {
+            (Some(&byte), Some(&0xFF)) => Some(byte),
+            _ => None,
+        }
+    }
+
+    /// Checks whether this signature matches `bytes` at `offset`.
+    ///
+    /// Returns `false` if `bytes` is too short, never panics.
+    #[must_use]
+    #[inline]
+    pub fn matches_at(&self, bytes: &[u8], offset: usize) -> bool {
+        let Some(window) = offset
+            .checked_add(self.len())
+            .and_then(|end| bytes.get(offset..end))
+        else {
+            return false;
+        };
+
+        window
+            .iter()
+            .zip(self.bytes.iter().zip(self.mask))
+            .all(|(&input, (&expected, &mask))| input & mask == expected & mask)
+    }
+}
+
+/// Value of an ASCII hex digit, or `None` for `?`.
+const fn nibble(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        b'?' => None,
+        _ => panic!("invalid character in hex pattern, expected 0-9, a-f, A-F or ?"),
+    }
+}
+
+/// Returns the number of bytes in a hex pattern like `"FF D8 FF ??"`.
+///
+/// Used by the [`masked`](crate::masked) macro to size its arrays.
+///
+/// # Panics
+///
+/// Panics (at compile time in a `const` context) on a malformed pattern.
+#[must_use]
+pub const fn hex_pattern_len(pattern: &str) -> usize {
+    let source = pattern.as_bytes();
+    let mut len = 0;
+    let mut index = 0;
+
+    while index < source.len() {
+        if source[index].is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        assert!(
+            index + 1 < source.len() && !source[index + 1].is_ascii_whitespace(),
+            "hex pattern bytes must be written with two digits"
+        );
+
+        nibble(source[index]);
+        nibble(source[index + 1]);
+
+        len += 1;
+        index += 2;
+    }
+
+    len
+}
+
+/// Parses a hex pattern like `"FF D8 FF ??"` into `(bytes, mask)`.
+///
+/// Every byte is written as two hex digits. `?` is a wildcard nibble,
+/// so `??` matches any byte and `E?` matches `0xE0..=0xEF`.
+/// Whitespace between bytes is optional.
+///
+/// # Panics
+///
+/// Panics (at compile time in a `const` context) on a malformed pattern,
+/// or if `N` is not the number of bytes in `pattern`.
+#[must_use]
+pub const fn parse_hex_pattern<const N: usize>(pattern: &str) -> ([u8; N], [u8; N]) {
+    assert!(
+        hex_pattern_len(pattern) == N,
+        "hex pattern length does not match"
+    );
+
+    let source = pattern.as_bytes();
+    let mut bytes = [0u8; N];
+    let mut mask = [0u8; N];
+    let mut index = 0;
+    let mut out = 0;
+
+    while index < source.len() {
+        if source[index].is_ascii_whitespace() {
+            index += 1;
+            continue;
+        }
+
+        if let Some(high) = nibble(source[index]) {
+            bytes[out] |= high << 4;
+            mask[out] |= 0xF0;
+        }
+
+        if let Some(low) = nibble(source[index + 1]) {
+            bytes[out] |= low;
+            mask[out] |= 0x0F;
+        }
+
+        out += 1;
+        index += 2;
+    }
+
+    (bytes, mask)
+}
+
+/// Builds a [`MaskedSignature`] from a hex pattern at compile time.
+///
+/// Every byte is written as two hex digits, `??` is a wildcard byte and
+/// `?` alone is a wildcard nibble (e.g. `E?`).
+///
+/// # Examples
+///
+/// ```rust
+/// use magical_rs::masked;
+/// use magical_rs::magical::pattern::MaskedSignature;
+///
+/// const MPEG1_FRAME_SYNC: MaskedSignature = masked!("FF F?");
+///
+/// assert!(MPEG1_FRAME_SYNC.matches_at(&[0xFF, 0xFB], 0));
+/// assert!(!MPEG1_FRAME_SYNC.matches_at(&[0xFF, 0x1B], 0));
+/// ```
+///
+/// A malformed pattern is a compile error:
+///
+/// ```compile_fail
+/// use magical_rs::masked;
+/// use magical_rs::magical::pattern::MaskedSignature;
+///
+/// const BROKEN: MaskedSignature = masked!("FF D");
+/// ```
+#[macro_export]
+macro_rules! masked {
+    ($pattern:expr) => {{
+        const __LEN: usize = $crate::magical::pattern::hex_pattern_len($pattern);
+        const __PARSED: ([u8; __LEN], [u8; __LEN]) =
+            $crate::magical::pattern::parse_hex_pattern::<__LEN>($pattern);
+        const __BYTES: [u8; __LEN] = __PARSED.0;
+        const __MASK: [u8; __LEN] = __PARSED.1;
+
+        $crate::magical::pattern::MaskedSignature::new(&__BYTES, &__MASK)
+    }};
+}
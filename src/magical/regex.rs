@@ -0,0 +1,571 @@
+//! A small, dependency-free regular expression engine over bytes.
+//!
+//! It supports the POSIX extended syntax used by magic(5) `regex` tests:
+//! literals, `.`, bracket expressions (ranges, negation, `[:alpha:]`-like classes),
+//! `^` and `$` (matching at line boundaries), groups, `|`, and the `*`, `+`, `?`,
+//! `{m}`, `{m,}`, `{m,n}` repetitions. `\d`, `\s` and `\w` (and their negations)
+//! are accepted as well.
+//!
+//! Patterns are compiled to a Thompson NFA and run on a Pike VM, so matching
+//! runs in linear time and never recurses, whatever the pattern.
+
+/// A set of bytes, one bit per byte value.
+type ByteSet = [u64; 4];
+
+/// Upper bound on the number of instructions of a compiled pattern.
+/// Counted repetitions are expanded, so `(a{100}){100}` would be huge.
+const MAX_PROGRAM_LEN: usize = 10_000;
+
+/// Upper bound on the nesting of groups. Parsing and compiling recurse
+/// once per group, so `((((...))))` must not run out of stack.
+const MAX_NESTING: usize = 100;
+
+#[derive(Clone, Debug)]
+enum Ast {
+    Set(ByteSet),
+    LineStart,
+    LineEnd,
+    Concat(Vec<Self>),
+    Alternate(Vec<Self>),
+    Repeat(Box<Self>, usize, Option<usize>),
+}
+
+#[derive(Clone, Copy, Debug)]
+enum Inst {
+    Set(usize),
+    Split(usize, usize),
+    Jump(usize),
+    LineStart,
+    LineEnd,
+    Match,
+}
+
+/// A compiled regular expression.
+#[derive(Clone, Debug)]
+pub struct Regex {
+    program: Vec<Inst>,
+    sets: Vec<ByteSet>,
+}
+
+const fn set_contains(set: &ByteSet, byte: u8) -> bool {
+    set[(byte / 64) as usize] & (1 << (byte % 64)) != 0
+}
+
+const fn set_insert(set: &mut ByteSet, byte: u8) {
+    set[(byte / 64) as usize] |= 1 << (byte % 64);
+}
+
+fn set_of(predicate: impl Fn(u8) -> bool) -> ByteSet {
+    let mut set = [0; 4];
+
+    for byte in 0..=u8::MAX {
+        if predicate(byte) {
+            set_insert(&mut set, byte);
+        }
+    }
+
+    set
+}
+
+const fn negate(set: &ByteSet) -> ByteSet {
+    [!set[0], !set[1], !set[2], !set[3]]
+}
+
+fn fold_case(set: &ByteSet) -> ByteSet {
+    set_of(|byte| {
+        set_contains(set, byte)
+            || set_contains(set, byte.to_ascii_lowercase())
+            || set_contains(set, byte.to_ascii_uppercase())
+    })
+}
+
+fn named_class(name: &str) -> Option<ByteSet> {
+    let predicate: fn(u8) -> bool = match name {
+        "alpha" => |byte| byte.is_ascii_alphabetic(),
+        "digit" => |byte| byte.is_ascii_digit(),
+        "alnum" => |byte| byte.is_ascii_alphanumeric(),
+        "upper" => |byte| byte.is_ascii_uppercase(),
+        "lower" => |byte| byte.is_ascii_lowercase(),
+        "space" => |byte| byte.is_ascii_whitespace() || byte == 0x0B,
+        "blank" => |byte| byte == b' ' || byte == b'\t',
+        "punct" => |byte| byte.is_ascii_punctuation(),
+        "print" => |byte| byte.is_ascii_graphic() || byte == b' ',
+        "graph" => |byte| byte.is_ascii_graphic(),
+        "cntrl" => |byte| byte.is_ascii_control(),
+        "xdigit" => |byte| byte.is_ascii_hexdigit(),
+        _ => return None,
+    };
+
+    Some(set_of(predicate))
+}
+
+/// Sets for the `\d`, `\s`, `\w` escapes (and their upper case negations).
+fn escape_class(escape: u8) -> Option<ByteSet> {
+    let set = match escape.to_ascii_lowercase() {
+        b'd' => named_class("digit")?,
+        b's' => named_class("space")?,
+        b'w' => set_of(|byte| byte.is_ascii_alphanumeric() || byte == b'_'),
+        _ => return None,
+    };
+
+    Some(if escape.is_ascii_uppercase() {
+        negate(&set)
+    } else {
+        set
+    })
+}
+
+struct Parser<'p> {
+    pattern: &'p [u8],
+    position: usize,
+    case_insensitive: bool,
+    depth: usize,
+}
+
+impl Parser<'_> {
+    fn peek(&self) -> Option<u8> {
+        self.pattern.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.peek()?;
+        self.position += 1;
+
+        Some(byte)
+    }
+
+    fn literal(&self, byte: u8) -> Ast {
+        let mut set = [0; 4];
+        set_insert(&mut set, byte);
+
+        Ast::Set(if self.case_insensitive {
+            fold_case(&set)
+        } else {
+            set
+        })
+    }
+
+    fn alternation(&mut self) -> Result<Ast, String> {
+        let mut branches = vec![self.concatenation()?];
+
+        while self.peek() == Some(b'|') {
+            self.position += 1;
+            branches.push(self.concatenation()?);
+        }
+
+        Ok(if branches.len() == 1 {
+            branches.remove(0)
+        } else {
+            Ast::Alternate(branches)
+        })
+    }
+
+    fn concatenation(&mut self) -> Result<Ast, String> {
+        let mut items = Vec::new();
+
+        while let Some(byte) = self.peek() {
+            if byte == b'|' || byte == b')' {
+                break;
+            }
+
+            let atom = self.atom()?;
+            items.push(self.repetition(atom)?);
+        }
+
+        Ok(Ast::Concat(items))
+    }
+
+    fn atom(&mut self) -> Result<Ast, String> {
+        match self.next() {
+            Some(b'(') => {
+                if self.depth >= MAX_NESTING {
+                    return Err("pattern nested too deeply".to_owned());
+                }
+
+                self.depth += 1;
+                let inner = self.alternation()?;
+                self.depth -= 1;
+
+                if self.next() != Some(b')') {
+                    return Err("unclosed group".to_owned());
+                }
+
+                Ok(inner)
+            }
+            Some(b'[') => self.bracket(),
+            Some(b'.') => Ok(Ast::Set(negate(&set_of(|byte| byte == b'\n')))),
+            Some(b'^') => Ok(Ast::LineStart),
+            Some(b'$') => Ok(Ast::LineEnd),
+            Some(b'\\') => {
+                let escaped = self.next().ok_or("trailing backslash")?;
+
+                Ok(escape_class(escaped).map_or_else(|| self.literal(escaped), Ast::Set))
+            }
+            Some(byte @ (b'*' | b'+' | b'?')) => {
+                Err(format!("nothing to repeat before `{}`", byte as char))
+            }
+            Some(byte) => Ok(self.literal(byte)),
+            None => Err("unexpected end of pattern".to_owned()),
+        }
+    }
+
+    fn bracket(&mut self) -> Result<Ast, String> {
+        let mut set = [0; 4];
+        let negated = self.peek() == Some(b'^');
+
+        if negated {
+            self.position += 1;
+        }
+
+        let mut first = true;
+
+        loop {
+            let byte = self.next().ok_or("unclosed bracket expression")?;
+
+            if byte == b']' && !first {
+                break;
+            }
+
+            first = false;
+
+            if byte == b'[' && self.peek() == Some(b':') {
+                let rest = &self.pattern[self.position + 1..];
+                let end = rest
+                    .windows(2)
+                    .position(|window| window == b":]")
+                    .ok_or("unclosed character class")?;
+                let name = String::from_utf8_lossy(&rest[..end]).into_owned();
+                let class =
+                    named_class(&name).ok_or_else(|| format!("unknown class [:{name}:]"))?;
+
+                for byte in 0..=u8::MAX {
+                    if set_contains(&class, byte) {
+                        set_insert(&mut set, byte);
+                    }
+                }
+
+                self.position += end + 3;
+                continue;
+            }
+
+            let low = if byte == b'\\' {
+                self.next().ok_or("unclosed bracket expression")?
+            } else {
+                byte
+            };
+
+            if self.peek() == Some(b'-') && self.pattern.get(self.position + 1) != Some(&b']') {
+                self.position += 1;
+                let high = self.next().ok_or("unclosed bracket expression")?;
+
+                if high < low {
+                    return Err("invalid range in bracket expression".to_owned());
+                }
+
+                for byte in low..=high {
+                    set_insert(&mut set, byte);
+                }
+            } else {
+                set_insert(&mut set, low);
+            }
+        }
+
+        if self.case_insensitive {
+            set = fold_case(&set);
+        }
+
+        Ok(Ast::Set(if negated { negate(&set) } else { set }))
+    }
+
+    fn number(&mut self) -> Option<usize> {
+        let start = self.position;
+
+        while self.peek().is_some_and(|byte| byte.is_ascii_digit()) {
+            self.position += 1;
+        }
+
+        core::str::from_utf8(&self.pattern[start..self.position])
+            .ok()?
+            .parse()
+            .ok()
+    }
+
+    fn repetition(&mut self, mut atom: Ast) -> Result<Ast, String> {
+        /* `a****` nests as deeply as `((((a)*)*)*)*`. */
+        let mut nesting = self.depth;
+
+        loop {
+            let (min, max) = match self.peek() {
+                Some(b'*') => (0, None),
+                Some(b'+') => (1, None),
+                Some(b'?') => (0, Some(1)),
+                Some(b'{') => {
+                    let start = self.position;
+                    self.position += 1;
+
+                    let Some(min) = self.number() else {
+                        /* Not a bound, `{` is a literal. */
+                        self.position = start;
+                        return Ok(atom);
+                    };
+
+                    let max = if self.peek() == Some(b',') {
+                        self.position += 1;
+                        self.number()
+                    } else {
+                        Some(min)
+                    };
+
+                    if self.peek() != Some(b'}') {
+                        return Err("unclosed repetition bound".to_owned());
+                    }
+
+                    if max.is_some_and(|max| max < min) {
+                        return Err("invalid repetition bound".to_owned());
+                    }
+
+                    (min, max)
+                }
+                _ => return Ok(atom),
+            };
+
+            if nesting >= MAX_NESTING {
+                return Err("pattern nested too deeply".to_owned());
+            }
+
+            nesting += 1;
+            self.position += 1;
+            atom = Ast::Repeat(Box::new(atom), min, max);
+        }
+    }
+}
+
+struct Compiler {
+    program: Vec<Inst>,
+    sets: Vec<ByteSet>,
+}
+
+impl Compiler {
+    fn push(&mut self, inst: Inst) -> Result<usize, String> {
+        if self.program.len() >= MAX_PROGRAM_LEN {
+            return Err("pattern is too large".to_owned());
+        }
+
+        self.program.push(inst);
+
+        Ok(self.program.len() - 1)
+    }
+
+    fn compile(&mut self, ast: &Ast) -> Result<(), String> {
+        match ast {
+            Ast::Set(set) => {
+                let index = if let Some(index) = self.sets.iter().position(|known| known == set) {
+                    index
+                } else {
+                    self.sets.push(*set);
+                    self.sets.len() - 1
+                };
+
+                self.push(Inst::Set(index))?;
+            }
+            Ast::LineStart => {
+                self.push(Inst::LineStart)?;
+            }
+            Ast::LineEnd => {
+                self.push(Inst::LineEnd)?;
+            }
+            Ast::Concat(items) => {
+                for item in items {
+                    self.compile(item)?;
+                }
+            }
+            Ast::Alternate(branches) => {
+                let mut jumps = Vec::new();
+
+                for (index, branch) in branches.iter().enumerate() {
+                    if index + 1 < branches.len() {
+                        let split = self.push(Inst::Split(0, 0))?;
+                        self.compile(branch)?;
+                        jumps.push(self.push(Inst::Jump(0))?);
+                        self.program[split] = Inst::Split(split + 1, self.program.len());
+                    } else {
+                        self.compile(branch)?;
+                    }
+                }
+
+                let end = self.program.len();
+
+                for jump in jumps {
+                    self.program[jump] = Inst::Jump(end);
+                }
+            }
+            Ast::Repeat(inner, min, max) => {
+                for _ in 0..*min {
+                    self.compile(inner)?;
+                }
+
+                if let Some(max) = max {
+                    let mut splits = Vec::new();
+
+                    for _ in *min..*max {
+                        splits.push(self.push(Inst::Split(0, 0))?);
+                        self.compile(inner)?;
+                    }
+
+                    let end = self.program.len();
+
+                    for split in splits {
+                        self.program[split] = Inst::Split(split + 1, end);
+                    }
+                } else {
+                    let split = self.push(Inst::Split(0, 0))?;
+                    self.compile(inner)?;
+                    self.push(Inst::Jump(split))?;
+                    self.program[split] = Inst::Split(split + 1, self.program.len());
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl Regex {
+    /// Compiles `pattern`.
+    ///
+    /// # Errors
+    /// Returns a description of the problem if `pattern` is not a valid expression.
+    pub fn new(pattern: &[u8], case_insensitive: bool) -> Result<Self, String> {
+        let mut parser = Parser {
+            pattern,
+            position: 0,
+            case_insensitive,
+            depth: 0,
+        };
+
+        let ast = parser.alternation()?;
+
+        if parser.position != pattern.len() {
+            return Err("unmatched `)`".to_owned());
+        }
+
+        let mut compiler = Compiler {
+            program: Vec::new(),
+            sets: Vec::new(),
+        };
+
+        compiler.compile(&ast)?;
+        compiler.push(Inst::Match)?;
+
+        Ok(Self {
+            program: compiler.program,
+            sets: compiler.sets,
+        })
+    }
+
+    /// Adds the thread at `pc` (started at `start`) and every thread reachable from it
+    /// without consuming input, in priority order.
+    fn add_thread(
+        &self,
+        threads: &mut Threads,
+        stack: &mut Vec<usize>,
+        pc: usize,
+        start: usize,
+        input: &[u8],
+        position: usize,
+    ) {
+        stack.push(pc);
+
+        while let Some(pc) = stack.pop() {
+            if threads.seen[pc] {
+                continue;
+            }
+
+            threads.seen[pc] = true;
+
+            match self.program[pc] {
+                Inst::Jump(target) => stack.push(target),
+                Inst::Split(first, second) => {
+                    stack.push(second);
+                    stack.push(first);
+                }
+                Inst::LineStart => {
+                    if position == 0 || input[position - 1] == b'\n' {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::LineEnd => {
+                    if position == input.len() || input[position] == b'\n' {
+                        stack.push(pc + 1);
+                    }
+                }
+                Inst::Set(_) | Inst::Match => threads.list.push((pc, start)),
+            }
+        }
+    }
+
+    /// Returns the start and end of the leftmost match in `input`.
+    ///
+    /// Among matches starting at the same position, the one preferred by greedy
+    /// repetitions and by the first alternatives wins, like Perl-style engines.
+    #[must_use]
+    pub fn find(&self, input: &[u8]) -> Option<(usize, usize)> {
+        let mut current = Threads::new(self.program.len());
+        let mut next = Threads::new(self.program.len());
+        let mut stack = Vec::new();
+        let mut found = None;
+
+        for position in 0..=input.len() {
+            if found.is_none() {
+                self.add_thread(&mut current, &mut stack, 0, position, input, position);
+            } else if current.list.is_empty() {
+                break;
+            }
+
+            for &(pc, start) in &current.list {
+                match self.program[pc] {
+                    Inst::Match => {
+                        found = Some((start, position));
+                        /* Lower priority threads are cut. */
+                        break;
+                    }
+                    Inst::Set(set)
+                        if input
+                            .get(position)
+                            .is_some_and(|&byte| set_contains(&self.sets[set], byte)) =>
+                    {
+                        self.add_thread(&mut next, &mut stack, pc + 1, start, input, position + 1);
+                    }
+                    _ => {}
+                }
+            }
+
+            core::mem::swap(&mut current, &mut next);
+            next.clear();
+        }
+
+        found
+    }
+}
+
+/// The threads of the Pike VM at one input position.
+struct Threads {
+    /// `(pc, start)` pairs, highest priority first.
+    list: Vec<(usize, usize)>,
+    /// Instructions already added at this position.
+    seen: Vec<bool>,
+}
+
+impl Threads {
+    fn new(program_len: usize) -> Self {
+        Self {
+            list: Vec::new(),
+            seen: vec![false; program_len],
+        }
+    }
+
+    fn clear(&mut self) {
+        self.list.clear();
+        self.seen.fill(false);
+    }
+}
@@ -1,23 +1,58 @@
 use crate::magical::bytes_read::{
-    DEFAULT_MAX_BYTES_READ, DEFAULT_OFFSET, ISO_MAX_BYTES_READ, ISO_OFFSETS, TAR_MAX_BYTES_READ,
-    TAR_OFFSETS,
+    BTRFS_MAX_BYTES_READ, BTRFS_OFFSETS, DEFAULT_MAX_BYTES_READ, DEFAULT_OFFSET,
+    DMG_TRAILER_OFFSETS, DMG_TRAILER_TAIL_BYTES_READ, EXFAT_MAX_BYTES_READ, EXT_MAX_BYTES_READ,
+    FAT_MAX_BYTES_READ, GPT_MAX_BYTES_READ, GPT_OFFSETS, HDF5_MAX_BYTES_READ, HDF5_OFFSETS,
+    ID3V1_OFFSETS, ID3V1_TAIL_BYTES_READ, ISO_MAX_BYTES_READ, ISO_OFFSETS, NTFS_MAX_BYTES_READ,
+    OEM_ID_OFFSETS, PARQUET_TRAILER_TAIL_BYTES_READ, TAR_OFFSETS, UDF_MAX_BYTES_READ, UDF_OFFSETS,
+    VDI_MAX_BYTES_READ, VDI_OFFSETS, VHD_FOOTER_OFFSETS, VHD_FOOTER_TAIL_BYTES_READ,
+    ZIP_EOCD_OFFSETS, ZIP_EOCD_TAIL_BYTES_READ,
+};
+use crate::magical::exe_info::{
+    is_dll, is_dot_net_assembly, is_java_class, is_mach_o, is_mach_o_fat, is_ms_dos, is_pe32,
+    is_pe32_plus, is_windows_driver,
+};
+use crate::magical::ext_fn::audio::{is_aac, is_flac, is_mp3, is_opus, is_speex, is_vorbis};
+use crate::magical::ext_fn::compression::{is_lz4, is_lzma, is_unix_compress, is_zstd};
+use crate::magical::ext_fn::data::is_parquet;
+use crate::magical::ext_fn::disk::{is_mbr, is_qcow2};
+use crate::magical::ext_fn::filesystem::{is_ext2, is_ext3, is_ext4, is_fat12, is_fat16, is_fat32};
+use crate::magical::ext_fn::isobmff::{
+    is_3gpp, is_3gpp2, is_avif, is_heic, is_heif, is_m4a, is_m4v, is_mp4, is_quicktime,
+};
+use crate::magical::ext_fn::riff::{
+    is_ani_cursor, is_avi, is_corel_draw, is_qcp, is_rmi, is_wav, is_webp,
+};
+use crate::magical::ext_fn::video::{is_flash_video, is_matroska, is_mpeg_ps, is_mpeg_ts, is_webm};
+use crate::magical::ext_fn::zip::{
+    is_apk, is_docx, is_epub, is_jar, is_nupkg, is_open_document_presentation,
+    is_open_document_spreadsheet, is_open_document_text, is_pptx, is_vsix, is_xlsx, is_xpi,
 };
-use crate::magical::ext_fn::webp::is_webp;
 use crate::magical::magic::FileKind;
-use crate::magical::match_rules::MatchRules;
+use crate::magical::match_rules::{
+    FN_STRENGTH, MatchRules, Strength, masked_strength, signature_at_end, signature_strength,
+};
+use crate::magical::pattern::MaskedSignature;
+use crate::masked;
 
 const PNG_SIGNATURE: &[u8] = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
 const GZIP_SIGNATURE: &[u8] = &[0x1F, 0x8B];
-const BZIP_SIGNATURE: &[u8] = &[0x42, 0x5A];
+/// `BZh`, then the block size from `1` (100 kB) to `9` (900 kB).
+const BZIP_SIGNATURE: &[&[u8]] = &[
+    &[0x42, 0x5A, 0x68, 0x31],
+    &[0x42, 0x5A, 0x68, 0x32],
+    &[0x42, 0x5A, 0x68, 0x33],
+    &[0x42, 0x5A, 0x68, 0x34],
+    &[0x42, 0x5A, 0x68, 0x35],
+    &[0x42, 0x5A, 0x68, 0x36],
+    &[0x42, 0x5A, 0x68, 0x37],
+    &[0x42, 0x5A, 0x68, 0x38],
+    &[0x42, 0x5A, 0x68, 0x39],
+];
 const PKG_ZIP_SIGNATURE: &[u8] = &[0x50, 0x4B, 0x03, 0x04];
 const BITMAP_SIGNATURE: &[u8] = &[0x42, 0x4D];
 const TAR_SIGNATURE: &[u8] = &[0x75, 0x73, 0x74, 0x61, 0x72];
 const MS_DOS_SIGNATURE: &[u8] = &[0x4D, 0x5A];
-const JPG_SIGNATURE: &[u8] = &[0xFF, 0xD8, 0xFF, 0xE0];
-const CLASS_SIGNATURE: &[u8] = &[0xCA, 0xFE, 0xBA, 0xBE];
-const MP3_SIGNATURE_1: &[u8] = &[0xFF, 0xFB];
-const MP3_SIGNATURE_2: &[u8] = &[0xFF, 0xF3];
-const MP3_SIGNATURE_3: &[u8] = &[0xFF, 0xF2];
+const JPG_SIGNATURE: MaskedSignature = masked!("FF D8 FF ??");
 const ISO_SIGNATURE: &[u8] = &[0x43, 0x44, 0x30, 0x30, 0x31];
 const RPM_SIGNATURE: &[u8] = &[0xED, 0xAB, 0xEE, 0xDB];
 const SQLITE_SIGNATURE: &[u8] = &[
@@ -66,24 +101,104 @@ const JPEG_2000_SIGNATURE: &[&[u8]] = &[
 ];
 const PDF_SIGNATURE: &[u8] = &[0x25, 0x50, 0x44, 0x46, 0x2D];
 const APPLE_DISK_IMAGE_SIGNATURE: &[u8] = &[0x6B, 0x6F, 0x6C, 0x79];
+const ZIP_END_OF_CENTRAL_DIRECTORY_SIGNATURE: &[u8] = &[0x50, 0x4B, 0x05, 0x06];
+const ID3V1_SIGNATURE: &[u8] = &[0x54, 0x41, 0x47];
 const CABINET_SIGNATURE: &[u8] = &[0x4D, 0x53, 0x43, 0x46];
-const MATROSKA_MEDIA_CONTAINER_SIGNATURE: &[u8] = &[0x1A, 0x45, 0xDF, 0xA3];
 const RICHTEXT_FORMAT_SIGNATURE: &[u8] = &[0x7B, 0x5C, 0x72, 0x74, 0x66, 0x31];
 const PHOTOCAP_TEMPLATE_SIGNATURE: &[u8] = &[0x78, 0x56, 0x34];
 const ACE_COMPRESSED_SIGNATURE: &[u8] = &[0x2A, 0x2A, 0x41, 0x43, 0x45, 0x2A, 0x2A];
-const FLASH_VIDEO_SIGNATURE: &[u8] = &[0x46, 0x4C, 0x56];
-const VMDK_FILE_SIGNATURE: &[u8] = &[0x4B, 0x44, 0x4D];
+/// Sparse extents (`KDM`) and text descriptors (`# Disk DescriptorFile`).
+const VMDK_FILE_SIGNATURE: &[&[u8]] = &[
+    &[0x4B, 0x44, 0x4D],
+    &[
+        0x23, 0x20, 0x44, 0x69, 0x73, 0x6B, 0x20, 0x44, 0x65, 0x73, 0x63, 0x72, 0x69, 0x70, 0x74,
+        0x6F, 0x72, 0x46, 0x69, 0x6C, 0x65,
+    ],
+];
+/// `conectix`, the cookie of the Virtual PC disk footer (and of its copy in dynamic disks).
+const VHD_SIGNATURE: &[u8] = &[0x63, 0x6F, 0x6E, 0x65, 0x63, 0x74, 0x69, 0x78];
+const VHDX_SIGNATURE: &[u8] = &[0x76, 0x68, 0x64, 0x78, 0x66, 0x69, 0x6C, 0x65];
+const VDI_SIGNATURE: &[u8] = &[0x7F, 0x10, 0xDA, 0xBE];
+const GPT_SIGNATURE: &[u8] = &[0x45, 0x46, 0x49, 0x20, 0x50, 0x41, 0x52, 0x54];
+const ARROW_IPC_SIGNATURE: &[u8] = &[0x41, 0x52, 0x52, 0x4F, 0x57, 0x31, 0x00, 0x00];
+const FEATHER_SIGNATURE: &[u8] = &[0x46, 0x45, 0x41, 0x31];
+const ORC_SIGNATURE: &[u8] = &[0x4F, 0x52, 0x43];
+const AVRO_OBJECT_CONTAINER_SIGNATURE: &[u8] = &[0x4F, 0x62, 0x6A, 0x01];
+const HDF5_SIGNATURE: &[u8] = &[0x89, 0x48, 0x44, 0x46, 0x0D, 0x0A, 0x1A, 0x0A];
+/// Classic (`CDF\x01`), 64-bit offset (`CDF\x02`) and 64-bit data (`CDF\x05`).
+const NETCDF_SIGNATURE: &[&[u8]] = &[
+    &[0x43, 0x44, 0x46, 0x01],
+    &[0x43, 0x44, 0x46, 0x02],
+    &[0x43, 0x44, 0x46, 0x05],
+];
+/// `SIMPLE  =`, then `T` right-justified at column 30.
+const FITS_SIGNATURE: &[u8] = &[
+    0x53, 0x49, 0x4D, 0x50, 0x4C, 0x45, 0x20, 0x20, 0x3D, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20,
+    0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x20, 0x54,
+];
+const NUMPY_NPY_SIGNATURE: &[u8] = &[0x93, 0x4E, 0x55, 0x4D, 0x50, 0x59];
+/// `MATLAB 5.0 MAT-file`, `MATLAB 7.3 MAT-file`... at the start of the text header.
+const MATLAB_MAT_SIGNATURE: &[MaskedSignature] = &[masked!(
+    "4D 41 54 4C 41 42 20 ?? 2E ?? 20 4D 41 54 2D 66 69 6C 65"
+)];
+const NTFS_SIGNATURE: &[u8] = &[0x4E, 0x54, 0x46, 0x53, 0x20, 0x20, 0x20, 0x20];
+const EXFAT_SIGNATURE: &[u8] = &[0x45, 0x58, 0x46, 0x41, 0x54, 0x20, 0x20, 0x20];
+/// `hsqs` on little-endian images, `sqsh` on big-endian ones.
+const SQUASHFS_SIGNATURE: &[&[u8]] = &[&[0x68, 0x73, 0x71, 0x73], &[0x73, 0x71, 0x73, 0x68]];
+const BTRFS_SIGNATURE: &[u8] = &[0x5F, 0x42, 0x48, 0x52, 0x66, 0x53, 0x5F, 0x4D];
+const XFS_SIGNATURE: &[u8] = &[0x58, 0x46, 0x53, 0x42];
+const UDF_SIGNATURE: &[&[u8]] = &[
+    &[0x4E, 0x53, 0x52, 0x30, 0x32],
+    &[0x4E, 0x53, 0x52, 0x30, 0x33],
+];
 const GOOGLE_CHROME_EXTENSION_SIGNATURE: &[u8] = &[0x43, 0x72, 0x32, 0x34];
+const XZ_SIGNATURE: &[u8] = &[0xFD, 0x37, 0x7A, 0x58, 0x5A, 0x00];
+const SEVEN_ZIP_SIGNATURE: &[u8] = &[0x37, 0x7A, 0xBC, 0xAF, 0x27, 0x1C];
+const LZIP_SIGNATURE: &[u8] = &[0x4C, 0x5A, 0x49, 0x50];
+const LZOP_SIGNATURE: &[u8] = &[0x89, 0x4C, 0x5A, 0x4F, 0x00, 0x0D, 0x0A, 0x1A, 0x0A];
+/// Old ASCII (`070707`), new ASCII (`070701`) and new ASCII with checksums (`070702`).
+const CPIO_ASCII_SIGNATURE: &[&[u8]] = &[
+    &[0x30, 0x37, 0x30, 0x37, 0x30, 0x37],
+    &[0x30, 0x37, 0x30, 0x37, 0x30, 0x31],
+    &[0x30, 0x37, 0x30, 0x37, 0x30, 0x32],
+];
+/// Old binary format, `070707` octal in either byte order.
+const CPIO_BINARY_SIGNATURE: &[&[u8]] = &[&[0xC7, 0x71], &[0x71, 0xC7]];
+/// An IFF `FORM` of type `AIFF`, or `AIFC` for compressed audio.
+const AIFF_SIGNATURE: &[MaskedSignature] = &[
+    masked!("46 4F 52 4D ?? ?? ?? ?? 41 49 46 46"),
+    masked!("46 4F 52 4D ?? ?? ?? ?? 41 49 46 43"),
+];
+const MIDI_SIGNATURE: &[u8] = &[0x4D, 0x54, 0x68, 0x64, 0x00, 0x00, 0x00, 0x06];
+const APE_SIGNATURE: &[u8] = &[0x4D, 0x41, 0x43, 0x20];
+const WAVPACK_SIGNATURE: &[u8] = &[0x77, 0x76, 0x70, 0x6B];
+/// Narrowband (`#!AMR\n`) and wideband (`#!AMR-WB\n`), both also starting like a script.
+const AMR_SIGNATURE: &[&[u8]] = &[
+    &[0x23, 0x21, 0x41, 0x4D, 0x52, 0x0A],
+    &[0x23, 0x21, 0x41, 0x4D, 0x52, 0x2D, 0x57, 0x42, 0x0A],
+];
+/// The ASF header object GUID, `75B22630-668E-11CF-A6D9-00AA0062CE6C`.
+const ASF_SIGNATURE: &[u8] = &[
+    0x30, 0x26, 0xB2, 0x75, 0x8E, 0x66, 0xCF, 0x11, 0xA6, 0xD9, 0x00, 0xAA, 0x00, 0x62, 0xCE, 0x6C,
+];
 
 pub struct Magic {
     pub signatures: &'static [&'static [u8]],
     pub offsets: &'static [usize],
     pub max_bytes_read: usize,
     pub kind: FileKind,
+    /// How strongly a match of this rule identifies `kind`.
+    /// Leave it as [`Strength::Auto`] unless the derived value is misleading.
+    pub strength: Strength,
     pub rules: MatchRules,
 }
 
 impl Magic {
+    /// Checks this rule against the first bytes of a file.
+    ///
+    /// End-anchored rules ([`MatchRules::FromEnd`], [`MatchRules::WithTailFn`]) never match
+    /// here, they need the end of the file, see [`Magic::matches_with_tail`].
+    /// Function rules ([`MatchRules::WithFn`]) see at most `max_bytes_read` bytes.
     #[must_use]
     #[inline]
     pub fn matches(&self, bytes: &[u8]) -> bool {
@@ -95,52 +210,310 @@ impl Magic {
                     bytes.len() >= offset_end && &bytes[offset..offset_end] == signature
                 })
             }),
-            MatchRules::WithFn(func) => func(bytes),
+            /*
+             * `detect_reader` only reads the first `max_bytes_read` bytes contiguously,
+             * past them the buffer holds zeros between the windows of other rules.
+             */
+            MatchRules::WithFn(func) => func(&bytes[..bytes.len().min(self.max_bytes_read)]),
+            MatchRules::Masked(patterns) => patterns.iter().any(|pattern| {
+                self.offsets
+                    .iter()
+                    .any(|&offset| pattern.matches_at(bytes, offset))
+            }),
+            MatchRules::FromEnd | MatchRules::WithTailFn(_) => false,
+        }
+    }
+
+    /// Returns the strength of this rule, from `0` to `MAX_STRENGTH`.
+    ///
+    /// With [`Strength::Auto`] it is derived from the shortest signature and the number of
+    /// signatures and offsets for signature rules, and is [`FN_STRENGTH`] for function rules.
+    #[must_use]
+    #[inline]
+    pub const fn strength(&self) -> u8 {
+        let derived = match &self.rules {
+            MatchRules::Default | MatchRules::FromEnd => {
+                signature_strength(self.signatures, self.offsets.len())
+            }
+            MatchRules::WithFn(_) | MatchRules::WithTailFn(_) => FN_STRENGTH,
+            MatchRules::Masked(patterns) => masked_strength(patterns, self.offsets.len()),
+        };
+
+        self.strength.value_or(derived)
+    }
+
+    /// Returns `true` if this rule needs the end of the file
+    /// ([`MatchRules::FromEnd`], [`MatchRules::WithTailFn`]).
+    #[must_use]
+    #[inline]
+    pub const fn is_end_anchored(&self) -> bool {
+        matches!(self.rules, MatchRules::FromEnd | MatchRules::WithTailFn(_))
+    }
+
+    /// Checks this rule against the last bytes of a file.
+    ///
+    /// `tail` must end exactly at the end of the file; it can also be the whole file.
+    /// Only [`MatchRules::FromEnd`] rules can match a tail alone, [`MatchRules::WithTailFn`]
+    /// rules also need the head, see [`Magic::matches_with_tail`].
+    #[must_use]
+    #[inline]
+    pub fn matches_tail(&self, tail: &[u8]) -> bool {
+        matches!(self.rules, MatchRules::FromEnd)
+            && self.signatures.iter().any(|signature| {
+                self.offsets
+                    .iter()
+                    .any(|&offset| signature_at_end(tail, signature, offset))
+            })
+    }
+
+    /// Checks this rule against a file given its first bytes (`head`) and its last bytes (`tail`).
+    #[must_use]
+    #[inline]
+    pub fn matches_with_tail(&self, head: &[u8], tail: &[u8]) -> bool {
+        match &self.rules {
+            MatchRules::FromEnd => self.matches_tail(tail),
+            /* The head read contiguously by every detection, see `Magic::matches`. */
+            MatchRules::WithTailFn(func) => func(
+                &head[..head.len().min(DEFAULT_MAX_BYTES_READ)],
+                &tail[tail.len().saturating_sub(self.max_bytes_read)..],
+            ),
+            _ => self.matches(head),
         }
     }
 }
 
-pub static SIGNATURE_KIND: &[Magic] = &[
+/// Built-in rules, in precedence order.
+///
+/// Kept as a `const` so the dispatch index in `dispatch.rs` can be built from it at compile time.
+pub(crate) const SIGNATURES: &[Magic] = &[
     Magic {
         signatures: &[PNG_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Png,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* Class files and Mach-O fat binaries share `CAFEBABE` (see `exe_info.rs`). */
     Magic {
-        signatures: &[CLASS_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Class,
-        rules: MatchRules::Default,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_java_class),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MachOFat,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mach_o_fat),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MachO,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mach_o),
     },
     Magic {
-        signatures: &[JPG_SIGNATURE],
+        signatures: &[],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Jpg,
-        rules: MatchRules::Default,
+        strength: Strength::Auto,
+        rules: MatchRules::Masked(&[JPG_SIGNATURE]),
     },
     Magic {
         signatures: &[GZIP_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Gzip,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
-        signatures: &[BZIP_SIGNATURE],
+        signatures: BZIP_SIGNATURE,
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Bzip,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[XZ_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Xz,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[SEVEN_ZIP_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::SevenZip,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[LZIP_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Lzip,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[LZOP_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Lzop,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    Magic {
+        signatures: CPIO_ASCII_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Cpio,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /* zstd and LZ4 may start with skippable frames, see `ext_fn/compression.rs`. */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Zstd,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_zstd),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Lz4,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_lz4),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::UnixCompress,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_unix_compress),
+    },
+    /*
+     * ZIP based formats, told apart by their entry names (see `ext_fn/zip.rs`).
+     * Kept before the generic `PkgZip` rule.
+     */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Docx,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_docx),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Xlsx,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_xlsx),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Pptx,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_pptx),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::OpenDocumentText,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_open_document_text),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::OpenDocumentSpreadsheet,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_open_document_spreadsheet),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::OpenDocumentPresentation,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_open_document_presentation),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Epub,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_epub),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Jar,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_jar),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Apk,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_apk),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Xpi,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_xpi),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Nupkg,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_nupkg),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Vsix,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_vsix),
+    },
     Magic {
         signatures: &[PKG_ZIP_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::PkgZip,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -148,27 +521,130 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Bitmap,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* Executables behind an `MZ` header (see `exe_info.rs`), a bare `MZ` comes last. */
     Magic {
-        signatures: &[MS_DOS_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::DotNetAssembly,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_dot_net_assembly),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::WindowsDriver,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_windows_driver),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Dll,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_dll),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Pe32Plus,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_pe32_plus),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Pe32,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_pe32),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::MSDOS,
-        rules: MatchRules::Default,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_ms_dos),
     },
     Magic {
         signatures: &[TAR_SIGNATURE],
         offsets: TAR_OFFSETS,
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Tar,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* Audio streams, possibly behind an ID3v2 tag (see `ext_fn/audio.rs`). */
     Magic {
-        signatures: &[MP3_SIGNATURE_1, MP3_SIGNATURE_2, MP3_SIGNATURE_3],
-        offsets: &[DEFAULT_OFFSET],
-        max_bytes_read: TAR_MAX_BYTES_READ,
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Flac,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_flac),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::MP3,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mp3),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Aac,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_aac),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Aiff,
+        strength: Strength::Auto,
+        rules: MatchRules::Masked(AIFF_SIGNATURE),
+    },
+    Magic {
+        signatures: &[MIDI_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Midi,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[APE_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Ape,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[WAVPACK_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::WavPack,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /* A UDF volume may also have ISO 9660 descriptors (UDF bridge discs). */
+    Magic {
+        signatures: UDF_SIGNATURE,
+        offsets: UDF_OFFSETS,
+        max_bytes_read: UDF_MAX_BYTES_READ,
+        kind: FileKind::Udf,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -176,6 +652,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: ISO_OFFSETS,
         max_bytes_read: ISO_MAX_BYTES_READ,
         kind: FileKind::ISO,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -183,6 +660,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::RPM,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -190,6 +668,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::SQLite,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -197,6 +676,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::XML,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -204,6 +684,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::ICO,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -211,6 +692,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::WASM,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -218,6 +700,15 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Deb,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: AMR_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Amr,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -225,6 +716,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::ScriptExecute,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -232,6 +724,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::RAR,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -239,13 +732,40 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::ELF,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* Ogg streams, by the codec of their first packet. */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Opus,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_opus),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Vorbis,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_vorbis),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Speex,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_speex),
+    },
     Magic {
         signatures: &[OGG_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::OGG,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -253,6 +773,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::_8BPS,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -260,6 +781,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::BLENDER,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -267,6 +789,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::TrueTypeFont,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -274,6 +797,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::OpenTypeFont,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -281,6 +805,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::ModuleForEvenvironmentModules,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -288,6 +813,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::WindowImagingFormat,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -295,6 +821,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Slob,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -302,6 +829,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::SerializedJavaData,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -309,6 +837,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::CreativeVoiceFile,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -316,6 +845,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::AuAudioFileFormat,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -323,6 +853,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::OpenGLIrisPerformer,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -330,6 +861,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::NoodlesoftHazel,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -337,6 +869,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::VBScriptEncoded,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -344,55 +877,136 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::WEBP,
+        strength: Strength::Auto,
         rules: MatchRules::WithFn(is_webp),
     },
     Magic {
-        signatures: &[APPLE_ICON_IMAGE_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
-        kind: FileKind::AppleIconImage,
-        rules: MatchRules::Default,
+        kind: FileKind::Wav,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_wav),
     },
     Magic {
-        signatures: GIF_SIGNATURE,
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
-        kind: FileKind::GIF,
-        rules: MatchRules::Default,
+        kind: FileKind::Avi,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_avi),
     },
     Magic {
-        signatures: JPEG_2000_SIGNATURE,
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
-        kind: FileKind::JPEG2000,
-        rules: MatchRules::Default,
+        kind: FileKind::AniCursor,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_ani_cursor),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Rmi,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_rmi),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Qcp,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_qcp),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::CorelDraw,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_corel_draw),
+    },
+    Magic {
+        signatures: &[APPLE_ICON_IMAGE_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::AppleIconImage,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: GIF_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::GIF,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: JPEG_2000_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::JPEG2000,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
     },
     Magic {
         signatures: &[PDF_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::PDF,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
         signatures: &[APPLE_DISK_IMAGE_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
-        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        offsets: DMG_TRAILER_OFFSETS,
+        max_bytes_read: DMG_TRAILER_TAIL_BYTES_READ,
         kind: FileKind::AppleDiskImage,
-        rules: MatchRules::Default,
+        strength: Strength::Auto,
+        rules: MatchRules::FromEnd,
     },
     Magic {
         signatures: &[CABINET_SIGNATURE],
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Cabinet,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* EBML files, told apart by their `DocType` (see `ext_fn/video.rs`). */
     Magic {
-        signatures: &[MATROSKA_MEDIA_CONTAINER_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Webm,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_webm),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::MatroskaMediaContainer,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_matroska),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MpegPs,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mpeg_ps),
+    },
+    Magic {
+        signatures: &[ASF_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Asf,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -400,6 +1014,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::RichTextFormat,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -407,6 +1022,7 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::PhotoCapTemplate,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -414,20 +1030,129 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::AceCompressed,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
-        signatures: &[FLASH_VIDEO_SIGNATURE],
-        offsets: &[DEFAULT_OFFSET],
+        signatures: &[],
+        offsets: &[],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::FlashVideo,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_flash_video),
+    },
+    /* Columnar and scientific data. MATLAB 7.3 files are HDF5 files behind a MAT header. */
+    Magic {
+        signatures: &[ARROW_IPC_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::ArrowIpc,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[FEATHER_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Feather,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[ORC_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Orc,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[AVRO_OBJECT_CONTAINER_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::AvroObjectContainer,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MatlabMat,
+        strength: Strength::Auto,
+        rules: MatchRules::Masked(MATLAB_MAT_SIGNATURE),
+    },
+    Magic {
+        signatures: &[HDF5_SIGNATURE],
+        offsets: HDF5_OFFSETS,
+        max_bytes_read: HDF5_MAX_BYTES_READ,
+        kind: FileKind::Hdf5,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: NETCDF_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::NetCdf,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[FITS_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Fits,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[NUMPY_NPY_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::NumpyNpy,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /* Virtual machine disk images. */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Qcow2,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_qcow2),
+    },
+    Magic {
+        signatures: &[VHD_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Vhd,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[VHDX_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Vhdx,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
-        signatures: &[VMDK_FILE_SIGNATURE],
+        signatures: &[VDI_SIGNATURE],
+        offsets: VDI_OFFSETS,
+        max_bytes_read: VDI_MAX_BYTES_READ,
+        kind: FileKind::Vdi,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: VMDK_FILE_SIGNATURE,
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::Vmdk,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
     Magic {
@@ -435,6 +1160,1077 @@ pub static SIGNATURE_KIND: &[Magic] = &[
         offsets: &[DEFAULT_OFFSET],
         max_bytes_read: DEFAULT_MAX_BYTES_READ,
         kind: FileKind::GoogleChromeExtension,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /* ISO base media files, told apart by their `ftyp` brands (see `ext_fn/isobmff.rs`). */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Mp4,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mp4),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::M4v,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_m4v),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::M4a,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_m4a),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::QuickTime,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_quicktime),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Heic,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_heic),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Heif,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_heif),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Avif,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_avif),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::ThreeGpp,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_3gpp),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::ThreeGpp2,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_3gpp2),
+    },
+    /*
+     * Raw disks. The footer of a fixed VHD comes first, the disk it holds starts like any
+     * other one. A GPT comes after a protective MBR, so it is checked before MBRs, and so
+     * are filesystems.
+     */
+    Magic {
+        signatures: &[VHD_SIGNATURE],
+        offsets: VHD_FOOTER_OFFSETS,
+        max_bytes_read: VHD_FOOTER_TAIL_BYTES_READ,
+        kind: FileKind::Vhd,
+        strength: Strength::Auto,
+        rules: MatchRules::FromEnd,
+    },
+    Magic {
+        signatures: &[GPT_SIGNATURE],
+        offsets: GPT_OFFSETS,
+        max_bytes_read: GPT_MAX_BYTES_READ,
+        kind: FileKind::Gpt,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /* Filesystems, whose boot sector may look like an MBR (see `ext_fn/filesystem.rs`). */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: EXT_MAX_BYTES_READ,
+        kind: FileKind::Ext2,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_ext2),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: EXT_MAX_BYTES_READ,
+        kind: FileKind::Ext3,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_ext3),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: EXT_MAX_BYTES_READ,
+        kind: FileKind::Ext4,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_ext4),
+    },
+    Magic {
+        signatures: &[NTFS_SIGNATURE],
+        offsets: OEM_ID_OFFSETS,
+        max_bytes_read: NTFS_MAX_BYTES_READ,
+        kind: FileKind::Ntfs,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[EXFAT_SIGNATURE],
+        offsets: OEM_ID_OFFSETS,
+        max_bytes_read: EXFAT_MAX_BYTES_READ,
+        kind: FileKind::ExFat,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: FAT_MAX_BYTES_READ,
+        kind: FileKind::Fat12,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_fat12),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: FAT_MAX_BYTES_READ,
+        kind: FileKind::Fat16,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_fat16),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: FAT_MAX_BYTES_READ,
+        kind: FileKind::Fat32,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_fat32),
+    },
+    Magic {
+        signatures: SQUASHFS_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Squashfs,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[BTRFS_SIGNATURE],
+        offsets: BTRFS_OFFSETS,
+        max_bytes_read: BTRFS_MAX_BYTES_READ,
+        kind: FileKind::Btrfs,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[XFS_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Xfs,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Mbr,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mbr),
+    },
+    /* Formats without a real magic number, behind everything with one. */
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MpegTs,
+        strength: Strength::Auto,
+        rules: MatchRules::WithFn(is_mpeg_ts),
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Lzma,
+        strength: Strength::Fixed(30),
+        rules: MatchRules::WithFn(is_lzma),
+    },
+    Magic {
+        signatures: CPIO_BINARY_SIGNATURE,
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::Cpio,
+        strength: Strength::Auto,
         rules: MatchRules::Default,
     },
+    /* Anything else starting with `MZ`: text, damaged or truncated executables. */
+    Magic {
+        signatures: &[MS_DOS_SIGNATURE],
+        offsets: &[DEFAULT_OFFSET],
+        max_bytes_read: DEFAULT_MAX_BYTES_READ,
+        kind: FileKind::MSDOS,
+        strength: Strength::Auto,
+        rules: MatchRules::Default,
+    },
+    /*
+     * Trailer fallbacks, only checked when the end of the file is available.
+     * Kept last so that a header signature always wins (e.g. self-extracting archives).
+     */
+    Magic {
+        signatures: &[ZIP_END_OF_CENTRAL_DIRECTORY_SIGNATURE],
+        offsets: ZIP_EOCD_OFFSETS,
+        max_bytes_read: ZIP_EOCD_TAIL_BYTES_READ,
+        kind: FileKind::PkgZip,
+        strength: Strength::Auto,
+        rules: MatchRules::FromEnd,
+    },
+    Magic {
+        signatures: &[ID3V1_SIGNATURE],
+        offsets: ID3V1_OFFSETS,
+        max_bytes_read: ID3V1_TAIL_BYTES_READ,
+        kind: FileKind::MP3,
+        strength: Strength::Auto,
+        rules: MatchRules::FromEnd,
+    },
+    Magic {
+        signatures: &[],
+        offsets: &[],
+        max_bytes_read: PARQUET_TRAILER_TAIL_BYTES_READ,
+        kind: FileKind::Parquet,
+        strength: Strength::Auto,
+        rules: MatchRules::WithTailFn(is_parquet),
+    },
+];
+
+pub static SIGNATURE_KIND: &[Magic] = SIGNATURES;
+
+/// Human-facing metadata of a built-in [`FileKind`].
+///
+/// One entry per variant lives in [`KIND_INFO`], right next to the signatures
+/// themselves, so adding a new kind means touching a single file.
+pub struct KindInfo {
+    pub kind: FileKind,
+    /// IANA media type (or the most common de-facto one).
+    pub mime_type: &'static str,
+    /// Canonical file extensions without the leading dot, preferred one first.
+    pub extensions: &'static [&'static str],
+    /// Short human readable description.
+    pub description: &'static str,
+}
+
+pub static KIND_INFO: &[KindInfo] = &[
+    KindInfo {
+        kind: FileKind::Png,
+        mime_type: "image/png",
+        extensions: &["png"],
+        description: "Portable Network Graphics",
+    },
+    KindInfo {
+        kind: FileKind::Bitmap,
+        mime_type: "image/bmp",
+        extensions: &["bmp", "dib"],
+        description: "Windows Bitmap image",
+    },
+    KindInfo {
+        kind: FileKind::Gzip,
+        mime_type: "application/gzip",
+        extensions: &["gz", "tgz"],
+        description: "Gzip compressed data",
+    },
+    KindInfo {
+        kind: FileKind::Bzip,
+        mime_type: "application/x-bzip2",
+        extensions: &["bz2", "tbz2"],
+        description: "Bzip2 compressed data",
+    },
+    KindInfo {
+        kind: FileKind::PkgZip,
+        mime_type: "application/zip",
+        extensions: &["zip"],
+        description: "Zip archive",
+    },
+    KindInfo {
+        kind: FileKind::Tar,
+        mime_type: "application/x-tar",
+        extensions: &["tar"],
+        description: "POSIX tar archive",
+    },
+    KindInfo {
+        kind: FileKind::MSDOS,
+        mime_type: "application/x-msdownload",
+        extensions: &["exe", "com"],
+        description: "MS-DOS executable",
+    },
+    KindInfo {
+        kind: FileKind::Jpg,
+        mime_type: "image/jpeg",
+        extensions: &["jpg", "jpeg", "jpe", "jfif"],
+        description: "JPEG image",
+    },
+    KindInfo {
+        kind: FileKind::Class,
+        mime_type: "application/java-vm",
+        extensions: &["class"],
+        description: "Java class file",
+    },
+    KindInfo {
+        kind: FileKind::MP3,
+        mime_type: "audio/mpeg",
+        extensions: &["mp3"],
+        description: "MPEG audio layer 3",
+    },
+    KindInfo {
+        kind: FileKind::ISO,
+        mime_type: "application/x-iso9660-image",
+        extensions: &["iso"],
+        description: "ISO 9660 CD-ROM filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::RPM,
+        mime_type: "application/x-rpm",
+        extensions: &["rpm"],
+        description: "RPM package",
+    },
+    KindInfo {
+        kind: FileKind::SQLite,
+        mime_type: "application/vnd.sqlite3",
+        extensions: &["sqlite", "sqlite3", "db"],
+        description: "SQLite 3 database",
+    },
+    KindInfo {
+        kind: FileKind::XML,
+        mime_type: "application/xml",
+        extensions: &["xml"],
+        description: "XML document",
+    },
+    KindInfo {
+        kind: FileKind::ICO,
+        mime_type: "image/vnd.microsoft.icon",
+        extensions: &["ico"],
+        description: "Windows icon",
+    },
+    KindInfo {
+        kind: FileKind::WASM,
+        mime_type: "application/wasm",
+        extensions: &["wasm"],
+        description: "WebAssembly binary module",
+    },
+    KindInfo {
+        kind: FileKind::Deb,
+        mime_type: "application/vnd.debian.binary-package",
+        extensions: &["deb"],
+        description: "Debian binary package",
+    },
+    KindInfo {
+        kind: FileKind::RAR,
+        mime_type: "application/vnd.rar",
+        extensions: &["rar"],
+        description: "RAR archive",
+    },
+    KindInfo {
+        kind: FileKind::ScriptExecute,
+        mime_type: "text/x-shellscript",
+        extensions: &["sh"],
+        description: "Script with shebang line",
+    },
+    KindInfo {
+        kind: FileKind::ELF,
+        mime_type: "application/x-elf",
+        extensions: &["elf", "so", "o"],
+        description: "Executable and Linkable Format",
+    },
+    KindInfo {
+        kind: FileKind::OGG,
+        mime_type: "application/ogg",
+        extensions: &["ogg", "ogx"],
+        description: "Ogg container",
+    },
+    KindInfo {
+        kind: FileKind::_8BPS,
+        mime_type: "image/vnd.adobe.photoshop",
+        extensions: &["psd"],
+        description: "Adobe Photoshop document",
+    },
+    KindInfo {
+        kind: FileKind::BLENDER,
+        mime_type: "application/x-blender",
+        extensions: &["blend"],
+        description: "Blender project",
+    },
+    KindInfo {
+        kind: FileKind::TrueTypeFont,
+        mime_type: "font/ttf",
+        extensions: &["ttf", "tte", "dfont"],
+        description: "TrueType font",
+    },
+    KindInfo {
+        kind: FileKind::OpenTypeFont,
+        mime_type: "font/otf",
+        extensions: &["otf"],
+        description: "OpenType font",
+    },
+    KindInfo {
+        kind: FileKind::ModuleForEvenvironmentModules,
+        mime_type: "text/x-modulefile",
+        extensions: &["module"],
+        description: "Environment Modules modulefile",
+    },
+    KindInfo {
+        kind: FileKind::WindowImagingFormat,
+        mime_type: "application/x-ms-wim",
+        extensions: &["wim", "swm", "esd"],
+        description: "Windows Imaging Format",
+    },
+    KindInfo {
+        kind: FileKind::Slob,
+        mime_type: "application/x-slob",
+        extensions: &["slob"],
+        description: "Slob dictionary",
+    },
+    KindInfo {
+        kind: FileKind::SerializedJavaData,
+        mime_type: "application/x-java-serialized-object",
+        extensions: &["ser"],
+        description: "Serialized Java data",
+    },
+    KindInfo {
+        kind: FileKind::CreativeVoiceFile,
+        mime_type: "audio/x-voc",
+        extensions: &["voc"],
+        description: "Creative Voice File",
+    },
+    KindInfo {
+        kind: FileKind::AuAudioFileFormat,
+        mime_type: "audio/basic",
+        extensions: &["au", "snd"],
+        description: "Sun/NeXT audio",
+    },
+    KindInfo {
+        kind: FileKind::OpenGLIrisPerformer,
+        mime_type: "image/x-sgi",
+        extensions: &["iv"],
+        description: "OpenGL Iris Performer",
+    },
+    KindInfo {
+        kind: FileKind::NoodlesoftHazel,
+        mime_type: "application/x-hazel",
+        extensions: &["hazelrules"],
+        description: "Noodlesoft Hazel rules",
+    },
+    KindInfo {
+        kind: FileKind::VBScriptEncoded,
+        mime_type: "text/vbscript.encode",
+        extensions: &["vbe"],
+        description: "Encoded VBScript",
+    },
+    KindInfo {
+        kind: FileKind::WEBP,
+        mime_type: "image/webp",
+        extensions: &["webp"],
+        description: "WebP image",
+    },
+    KindInfo {
+        kind: FileKind::AppleIconImage,
+        mime_type: "image/icns",
+        extensions: &["icns"],
+        description: "Apple Icon Image",
+    },
+    KindInfo {
+        kind: FileKind::GIF,
+        mime_type: "image/gif",
+        extensions: &["gif"],
+        description: "Graphics Interchange Format",
+    },
+    KindInfo {
+        kind: FileKind::JPEG2000,
+        mime_type: "image/jp2",
+        extensions: &["jp2", "j2k", "jpf", "jpx", "jpm", "mj2"],
+        description: "JPEG 2000 image",
+    },
+    KindInfo {
+        kind: FileKind::PDF,
+        mime_type: "application/pdf",
+        extensions: &["pdf"],
+        description: "Portable Document Format",
+    },
+    KindInfo {
+        kind: FileKind::AppleDiskImage,
+        mime_type: "application/x-apple-diskimage",
+        extensions: &["dmg"],
+        description: "Apple Disk Image",
+    },
+    KindInfo {
+        kind: FileKind::Cabinet,
+        mime_type: "application/vnd.ms-cab-compressed",
+        extensions: &["cab"],
+        description: "Microsoft Cabinet archive",
+    },
+    KindInfo {
+        kind: FileKind::MatroskaMediaContainer,
+        mime_type: "video/x-matroska",
+        extensions: &["mkv", "mka", "mks", "mk3d"],
+        description: "Matroska media container",
+    },
+    KindInfo {
+        kind: FileKind::RichTextFormat,
+        mime_type: "application/rtf",
+        extensions: &["rtf"],
+        description: "Rich Text Format",
+    },
+    KindInfo {
+        kind: FileKind::PhotoCapTemplate,
+        mime_type: "application/x-photocap-template",
+        extensions: &["pct"],
+        description: "PhotoCap template",
+    },
+    KindInfo {
+        kind: FileKind::AceCompressed,
+        mime_type: "application/x-ace-compressed",
+        extensions: &["ace"],
+        description: "ACE archive",
+    },
+    KindInfo {
+        kind: FileKind::FlashVideo,
+        mime_type: "video/x-flv",
+        extensions: &["flv"],
+        description: "Flash Video",
+    },
+    KindInfo {
+        kind: FileKind::Vmdk,
+        mime_type: "application/x-vmdk",
+        extensions: &["vmdk"],
+        description: "VMware virtual disk",
+    },
+    KindInfo {
+        kind: FileKind::GoogleChromeExtension,
+        mime_type: "application/x-chrome-extension",
+        extensions: &["crx"],
+        description: "Google Chrome extension",
+    },
+    KindInfo {
+        kind: FileKind::Docx,
+        mime_type: "application/vnd.openxmlformats-officedocument.wordprocessingml.document",
+        extensions: &["docx", "docm"],
+        description: "Microsoft Word document (Office Open XML)",
+    },
+    KindInfo {
+        kind: FileKind::Xlsx,
+        mime_type: "application/vnd.openxmlformats-officedocument.spreadsheetml.sheet",
+        extensions: &["xlsx", "xlsm"],
+        description: "Microsoft Excel spreadsheet (Office Open XML)",
+    },
+    KindInfo {
+        kind: FileKind::Pptx,
+        mime_type: "application/vnd.openxmlformats-officedocument.presentationml.presentation",
+        extensions: &["pptx", "pptm"],
+        description: "Microsoft PowerPoint presentation (Office Open XML)",
+    },
+    KindInfo {
+        kind: FileKind::OpenDocumentText,
+        mime_type: "application/vnd.oasis.opendocument.text",
+        extensions: &["odt"],
+        description: "OpenDocument text document",
+    },
+    KindInfo {
+        kind: FileKind::OpenDocumentSpreadsheet,
+        mime_type: "application/vnd.oasis.opendocument.spreadsheet",
+        extensions: &["ods"],
+        description: "OpenDocument spreadsheet",
+    },
+    KindInfo {
+        kind: FileKind::OpenDocumentPresentation,
+        mime_type: "application/vnd.oasis.opendocument.presentation",
+        extensions: &["odp"],
+        description: "OpenDocument presentation",
+    },
+    KindInfo {
+        kind: FileKind::Epub,
+        mime_type: "application/epub+zip",
+        extensions: &["epub"],
+        description: "EPUB electronic publication",
+    },
+    KindInfo {
+        kind: FileKind::Jar,
+        mime_type: "application/java-archive",
+        extensions: &["jar", "war", "ear"],
+        description: "Java archive",
+    },
+    KindInfo {
+        kind: FileKind::Apk,
+        mime_type: "application/vnd.android.package-archive",
+        extensions: &["apk"],
+        description: "Android application package",
+    },
+    KindInfo {
+        kind: FileKind::Xpi,
+        mime_type: "application/x-xpinstall",
+        extensions: &["xpi"],
+        description: "Mozilla extension",
+    },
+    KindInfo {
+        kind: FileKind::Nupkg,
+        mime_type: "application/x-nupkg",
+        extensions: &["nupkg"],
+        description: "NuGet package",
+    },
+    KindInfo {
+        kind: FileKind::Vsix,
+        mime_type: "application/vsix",
+        extensions: &["vsix"],
+        description: "Visual Studio extension",
+    },
+    KindInfo {
+        kind: FileKind::Mp4,
+        mime_type: "video/mp4",
+        extensions: &["mp4", "m4p"],
+        description: "MPEG-4 video",
+    },
+    KindInfo {
+        kind: FileKind::M4v,
+        mime_type: "video/x-m4v",
+        extensions: &["m4v"],
+        description: "MPEG-4 video (iTunes)",
+    },
+    KindInfo {
+        kind: FileKind::M4a,
+        mime_type: "audio/mp4",
+        extensions: &["m4a", "m4b"],
+        description: "MPEG-4 audio",
+    },
+    KindInfo {
+        kind: FileKind::QuickTime,
+        mime_type: "video/quicktime",
+        extensions: &["mov", "qt"],
+        description: "QuickTime movie",
+    },
+    KindInfo {
+        kind: FileKind::Heic,
+        mime_type: "image/heic",
+        extensions: &["heic"],
+        description: "HEIC image (HEVC in HEIF)",
+    },
+    KindInfo {
+        kind: FileKind::Heif,
+        mime_type: "image/heif",
+        extensions: &["heif"],
+        description: "High Efficiency Image File Format",
+    },
+    KindInfo {
+        kind: FileKind::Avif,
+        mime_type: "image/avif",
+        extensions: &["avif"],
+        description: "AV1 Image File Format",
+    },
+    KindInfo {
+        kind: FileKind::ThreeGpp,
+        mime_type: "video/3gpp",
+        extensions: &["3gp"],
+        description: "3GPP multimedia",
+    },
+    KindInfo {
+        kind: FileKind::ThreeGpp2,
+        mime_type: "video/3gpp2",
+        extensions: &["3g2"],
+        description: "3GPP2 multimedia",
+    },
+    KindInfo {
+        kind: FileKind::Wav,
+        mime_type: "audio/wav",
+        extensions: &["wav"],
+        description: "Waveform audio",
+    },
+    KindInfo {
+        kind: FileKind::Avi,
+        mime_type: "video/x-msvideo",
+        extensions: &["avi"],
+        description: "AVI video",
+    },
+    KindInfo {
+        kind: FileKind::AniCursor,
+        mime_type: "application/x-navi-animation",
+        extensions: &["ani"],
+        description: "Windows animated cursor",
+    },
+    KindInfo {
+        kind: FileKind::Rmi,
+        mime_type: "audio/mid",
+        extensions: &["rmi"],
+        description: "RIFF MIDI music",
+    },
+    KindInfo {
+        kind: FileKind::Qcp,
+        mime_type: "audio/qcelp",
+        extensions: &["qcp"],
+        description: "QCELP audio",
+    },
+    KindInfo {
+        kind: FileKind::CorelDraw,
+        mime_type: "application/vnd.corel-draw",
+        extensions: &["cdr"],
+        description: "CorelDRAW drawing",
+    },
+    KindInfo {
+        kind: FileKind::MachO,
+        mime_type: "application/x-mach-binary",
+        extensions: &["dylib", "bundle"],
+        description: "Mach-O binary",
+    },
+    KindInfo {
+        kind: FileKind::MachOFat,
+        mime_type: "application/x-mach-binary",
+        extensions: &["dylib"],
+        description: "Mach-O universal binary",
+    },
+    KindInfo {
+        kind: FileKind::Pe32,
+        mime_type: "application/vnd.microsoft.portable-executable",
+        extensions: &["exe"],
+        description: "PE32 executable",
+    },
+    KindInfo {
+        kind: FileKind::Pe32Plus,
+        mime_type: "application/vnd.microsoft.portable-executable",
+        extensions: &["exe"],
+        description: "PE32+ executable",
+    },
+    KindInfo {
+        kind: FileKind::Dll,
+        mime_type: "application/vnd.microsoft.portable-executable",
+        extensions: &["dll"],
+        description: "Windows dynamic-link library",
+    },
+    KindInfo {
+        kind: FileKind::DotNetAssembly,
+        mime_type: "application/vnd.microsoft.portable-executable",
+        extensions: &["exe", "dll"],
+        description: ".NET assembly",
+    },
+    KindInfo {
+        kind: FileKind::WindowsDriver,
+        mime_type: "application/vnd.microsoft.portable-executable",
+        extensions: &["sys"],
+        description: "Windows driver",
+    },
+    KindInfo {
+        kind: FileKind::Xz,
+        mime_type: "application/x-xz",
+        extensions: &["xz", "txz"],
+        description: "XZ compressed data",
+    },
+    KindInfo {
+        kind: FileKind::Zstd,
+        mime_type: "application/zstd",
+        extensions: &["zst", "tzst"],
+        description: "Zstandard compressed data",
+    },
+    KindInfo {
+        kind: FileKind::Lz4,
+        mime_type: "application/x-lz4",
+        extensions: &["lz4"],
+        description: "LZ4 compressed data",
+    },
+    KindInfo {
+        kind: FileKind::SevenZip,
+        mime_type: "application/x-7z-compressed",
+        extensions: &["7z"],
+        description: "7-Zip archive",
+    },
+    KindInfo {
+        kind: FileKind::Lzip,
+        mime_type: "application/x-lzip",
+        extensions: &["lz", "tlz"],
+        description: "Lzip compressed data",
+    },
+    KindInfo {
+        kind: FileKind::Lzma,
+        mime_type: "application/x-lzma",
+        extensions: &["lzma"],
+        description: "LZMA compressed data (legacy LZMA-Alone)",
+    },
+    KindInfo {
+        kind: FileKind::UnixCompress,
+        mime_type: "application/x-compress",
+        extensions: &["Z", "taz"],
+        description: "Unix compress (LZW) data",
+    },
+    KindInfo {
+        kind: FileKind::Lzop,
+        mime_type: "application/x-lzop",
+        extensions: &["lzo"],
+        description: "Lzop compressed data",
+    },
+    KindInfo {
+        kind: FileKind::Cpio,
+        mime_type: "application/x-cpio",
+        extensions: &["cpio"],
+        description: "Cpio archive",
+    },
+    KindInfo {
+        kind: FileKind::Flac,
+        mime_type: "audio/flac",
+        extensions: &["flac"],
+        description: "Free Lossless Audio Codec",
+    },
+    KindInfo {
+        kind: FileKind::Aiff,
+        mime_type: "audio/aiff",
+        extensions: &["aiff", "aif", "aifc"],
+        description: "Audio Interchange File Format",
+    },
+    KindInfo {
+        kind: FileKind::Midi,
+        mime_type: "audio/midi",
+        extensions: &["mid", "midi"],
+        description: "Standard MIDI file",
+    },
+    KindInfo {
+        kind: FileKind::Aac,
+        mime_type: "audio/aac",
+        extensions: &["aac", "adts"],
+        description: "AAC audio in ADTS frames",
+    },
+    KindInfo {
+        kind: FileKind::Opus,
+        mime_type: "audio/opus",
+        extensions: &["opus"],
+        description: "Opus audio in Ogg",
+    },
+    KindInfo {
+        kind: FileKind::Vorbis,
+        mime_type: "audio/ogg",
+        extensions: &["oga"],
+        description: "Vorbis audio in Ogg",
+    },
+    KindInfo {
+        kind: FileKind::Speex,
+        mime_type: "audio/x-speex",
+        extensions: &["spx"],
+        description: "Speex audio in Ogg",
+    },
+    KindInfo {
+        kind: FileKind::Ape,
+        mime_type: "audio/x-ape",
+        extensions: &["ape"],
+        description: "Monkey's Audio",
+    },
+    KindInfo {
+        kind: FileKind::WavPack,
+        mime_type: "audio/x-wavpack",
+        extensions: &["wv"],
+        description: "WavPack audio",
+    },
+    KindInfo {
+        kind: FileKind::Amr,
+        mime_type: "audio/amr",
+        extensions: &["amr"],
+        description: "Adaptive Multi-Rate audio",
+    },
+    KindInfo {
+        kind: FileKind::Webm,
+        mime_type: "video/webm",
+        extensions: &["webm"],
+        description: "WebM media container",
+    },
+    KindInfo {
+        kind: FileKind::MpegTs,
+        mime_type: "video/mp2t",
+        extensions: &["ts", "m2ts", "mts"],
+        description: "MPEG transport stream",
+    },
+    KindInfo {
+        kind: FileKind::MpegPs,
+        mime_type: "video/mpeg",
+        extensions: &["mpg", "mpeg", "vob"],
+        description: "MPEG program stream",
+    },
+    KindInfo {
+        kind: FileKind::Asf,
+        mime_type: "video/x-ms-asf",
+        extensions: &["asf", "wmv", "wma"],
+        description: "Advanced Systems Format (WMV/WMA)",
+    },
+    KindInfo {
+        kind: FileKind::Qcow2,
+        mime_type: "application/x-qemu-disk",
+        extensions: &["qcow2", "qcow"],
+        description: "QEMU copy-on-write disk image",
+    },
+    KindInfo {
+        kind: FileKind::Vhd,
+        mime_type: "application/x-vhd",
+        extensions: &["vhd"],
+        description: "Virtual PC virtual hard disk",
+    },
+    KindInfo {
+        kind: FileKind::Vhdx,
+        mime_type: "application/x-vhdx",
+        extensions: &["vhdx"],
+        description: "Hyper-V virtual hard disk",
+    },
+    KindInfo {
+        kind: FileKind::Vdi,
+        mime_type: "application/x-virtualbox-vdi",
+        extensions: &["vdi"],
+        description: "VirtualBox disk image",
+    },
+    KindInfo {
+        kind: FileKind::Gpt,
+        mime_type: "application/x-raw-disk-image",
+        extensions: &["img", "raw"],
+        description: "Raw disk with a GUID partition table",
+    },
+    KindInfo {
+        kind: FileKind::Mbr,
+        mime_type: "application/x-raw-disk-image",
+        extensions: &["img", "raw"],
+        description: "Raw disk with an MBR partition table",
+    },
+    KindInfo {
+        kind: FileKind::Ext2,
+        mime_type: "application/x-ext2-image",
+        extensions: &["ext2", "img"],
+        description: "ext2 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Ext3,
+        mime_type: "application/x-ext3-image",
+        extensions: &["ext3", "img"],
+        description: "ext3 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Ext4,
+        mime_type: "application/x-ext4-image",
+        extensions: &["ext4", "img"],
+        description: "ext4 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Fat12,
+        mime_type: "application/x-fat-image",
+        extensions: &["img", "ima", "flp"],
+        description: "FAT12 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Fat16,
+        mime_type: "application/x-fat-image",
+        extensions: &["img"],
+        description: "FAT16 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Fat32,
+        mime_type: "application/x-fat-image",
+        extensions: &["img"],
+        description: "FAT32 filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::ExFat,
+        mime_type: "application/x-exfat-image",
+        extensions: &["img"],
+        description: "exFAT filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Ntfs,
+        mime_type: "application/x-ntfs-image",
+        extensions: &["img", "ntfs"],
+        description: "NTFS filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Squashfs,
+        mime_type: "application/vnd.squashfs",
+        extensions: &["sqsh", "squashfs", "snap"],
+        description: "SquashFS filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Btrfs,
+        mime_type: "application/x-btrfs-image",
+        extensions: &["img", "btrfs"],
+        description: "Btrfs filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Xfs,
+        mime_type: "application/x-xfs-image",
+        extensions: &["img", "xfs"],
+        description: "XFS filesystem image",
+    },
+    KindInfo {
+        kind: FileKind::Udf,
+        mime_type: "application/x-udf-image",
+        extensions: &["udf", "iso"],
+        description: "Universal Disk Format image",
+    },
+    KindInfo {
+        kind: FileKind::Parquet,
+        mime_type: "application/vnd.apache.parquet",
+        extensions: &["parquet"],
+        description: "Apache Parquet columnar data",
+    },
+    KindInfo {
+        kind: FileKind::ArrowIpc,
+        mime_type: "application/vnd.apache.arrow.file",
+        extensions: &["arrow", "feather"],
+        description: "Apache Arrow IPC file (Feather v2)",
+    },
+    KindInfo {
+        kind: FileKind::Feather,
+        mime_type: "application/x-feather",
+        extensions: &["fea"],
+        description: "Feather v1 data frame",
+    },
+    KindInfo {
+        kind: FileKind::Orc,
+        mime_type: "application/vnd.apache.orc",
+        extensions: &["orc"],
+        description: "Apache ORC columnar data",
+    },
+    KindInfo {
+        kind: FileKind::AvroObjectContainer,
+        mime_type: "application/vnd.apache.avro",
+        extensions: &["avro"],
+        description: "Apache Avro object container file",
+    },
+    KindInfo {
+        kind: FileKind::Hdf5,
+        mime_type: "application/x-hdf5",
+        extensions: &["h5", "hdf5", "he5"],
+        description: "Hierarchical Data Format 5",
+    },
+    KindInfo {
+        kind: FileKind::NetCdf,
+        mime_type: "application/x-netcdf",
+        extensions: &["nc", "cdf"],
+        description: "NetCDF classic or 64-bit offset data",
+    },
+    KindInfo {
+        kind: FileKind::Fits,
+        mime_type: "image/fits",
+        extensions: &["fits", "fit", "fts"],
+        description: "Flexible Image Transport System",
+    },
+    KindInfo {
+        kind: FileKind::NumpyNpy,
+        mime_type: "application/x-npy",
+        extensions: &["npy"],
+        description: "NumPy array",
+    },
+    KindInfo {
+        kind: FileKind::MatlabMat,
+        mime_type: "application/x-matlab-data",
+        extensions: &["mat"],
+        description: "MATLAB MAT-file",
+    },
 ];
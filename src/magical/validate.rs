@@ -0,0 +1,290 @@
+use crate::magical::magic::FileKind;
+
+use Validation::{Malformed, Truncated, Valid};
+
+/// The outcome of a structural check, see [`FileKind::validate`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Validation {
+    /// Every checked field holds a sensible value.
+    Valid,
+    /// The checked fields are sensible so far, but `bytes` ends before all of them.
+    Truncated,
+    /// The signature or a field behind it is wrong: the header is forged or damaged.
+    Malformed,
+}
+
+const PNG_SIGNATURE: &[u8; 8] = b"\x89PNG\r\n\x1A\n";
+/// Length, type, 13 bytes of data and CRC, right after the signature.
+const PNG_IHDR_END: usize = 33;
+const PNG_IHDR_LEN: u32 = 13;
+
+/// Signature, logical screen descriptor.
+const GIF_HEADER_LEN: usize = 13;
+const GIF_GLOBAL_COLOR_TABLE: u8 = 0x80;
+
+/// File header, then the size of the DIB header.
+const BMP_FILE_HEADER_LEN: usize = 14;
+const BMP_CORE_HEADER_LEN: u32 = 12;
+
+const ELF_MAGIC: &[u8; 4] = b"\x7FELF";
+/// `e_ident`, `e_type`, `e_machine` and `e_version`.
+const ELF_VERSION_END: usize = 24;
+
+const ZIP_LOCAL_FILE_HEADER: &[u8; 4] = b"PK\x03\x04";
+const ZIP_LOCAL_FILE_HEADER_LEN: usize = 30;
+/// Version 6.3 of the APPNOTE, the latest one.
+const ZIP_MAX_VERSION: u8 = 63;
+
+const SQLITE_MAGIC: &[u8; 16] = b"SQLite format 3\0";
+/// Up to the payload fractions, which are fixed.
+const SQLITE_HEADER_LEN: usize = 24;
+
+/// CRC-32 as used by PNG (and ZIP, gzip...), reflected polynomial `0xEDB88320`.
+const CRC_TABLE: [u32; 256] = {
+    let mut table = [0; 256];
+    let mut n = 0;
+
+    while n < 256 {
+        #[allow(clippy::cast_possible_truncation)]
+        let mut crc = n as u32;
+        let mut bit = 0;
+
+        while bit < 8 {
+            crc = if crc & 1 == 1 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+            bit += 1;
+        }
+
+        table[n] = crc;
+        n += 1;
+    }
+
+    table
+};
+
+fn crc32(bytes: &[u8]) -> u32 {
+    !bytes.iter().fold(u32::MAX, |crc, &byte| {
+        CRC_TABLE[((crc ^ u32::from(byte)) & 0xFF) as usize] ^ (crc >> 8)
+    })
+}
+
+fn read_array<const N: usize>(bytes: &[u8], offset: usize) -> Option<[u8; N]> {
+    bytes.get(offset..offset + N)?.try_into().ok()
+}
+
+/// Checks the `IHDR` chunk, which must come first: length, CRC and field values.
+fn validate_png(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(PNG_SIGNATURE) {
+        return Malformed;
+    }
+
+    let (Some(len), Some(chunk_type)) = (read_array(bytes, 8), bytes.get(12..16)) else {
+        return Truncated;
+    };
+
+    if u32::from_be_bytes(len) != PNG_IHDR_LEN || chunk_type != b"IHDR" {
+        return Malformed;
+    }
+
+    let Some(crc) = read_array(bytes, PNG_IHDR_END - 4) else {
+        return Truncated;
+    };
+
+    if crc32(&bytes[12..PNG_IHDR_END - 4]) != u32::from_be_bytes(crc) {
+        return Malformed;
+    }
+
+    let width = u32::from_be_bytes([bytes[16], bytes[17], bytes[18], bytes[19]]);
+    let height = u32::from_be_bytes([bytes[20], bytes[21], bytes[22], bytes[23]]);
+    let (depth, color_type) = (bytes[24], bytes[25]);
+
+    let valid_depth = match color_type {
+        0 => matches!(depth, 1 | 2 | 4 | 8 | 16),
+        3 => matches!(depth, 1 | 2 | 4 | 8),
+        2 | 4 | 6 => matches!(depth, 8 | 16),
+        _ => false,
+    };
+
+    if width == 0 || height == 0 || !valid_depth {
+        return Malformed;
+    }
+
+    Valid
+}
+
+/// Checks the logical screen descriptor, and that a block follows the global color table.
+fn validate_gif(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(b"GIF87a") && !bytes.starts_with(b"GIF89a") {
+        return Malformed;
+    }
+
+    let Some(&flags) = bytes.get(10) else {
+        return Truncated;
+    };
+
+    let color_table_len = if flags & GIF_GLOBAL_COLOR_TABLE == 0 {
+        0
+    } else {
+        3 << ((flags & 0x07) + 1)
+    };
+
+    /* An image descriptor, an extension or the trailer. */
+    match bytes.get(GIF_HEADER_LEN + color_table_len) {
+        Some(b',' | b'!' | b';') => Valid,
+        Some(_) => Malformed,
+        None => Truncated,
+    }
+}
+
+/// Checks that the DIB header size is a known variant, and fits before the pixel data.
+fn validate_bitmap(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(b"BM") {
+        return Malformed;
+    }
+
+    let (Some(data_offset), Some(header_len)) = (read_array(bytes, 10), read_array(bytes, 14))
+    else {
+        return Truncated;
+    };
+
+    let header_len = u32::from_le_bytes(header_len);
+    let data_offset = u32::from_le_bytes(data_offset);
+
+    /* Core, OS/2 v2 (short and full), info, v2, v3, v4 and v5 headers. */
+    if !matches!(header_len, 12 | 16 | 40 | 52 | 56 | 64 | 108 | 124)
+        || u64::from(data_offset) < BMP_FILE_HEADER_LEN as u64 + u64::from(header_len)
+    {
+        return Malformed;
+    }
+
+    /* The color planes field is always 1. */
+    let planes_offset = if header_len == BMP_CORE_HEADER_LEN {
+        22
+    } else {
+        26
+    };
+
+    match read_array(bytes, planes_offset) {
+        Some(planes) if u16::from_le_bytes(planes) == 1 => Valid,
+        Some(_) => Malformed,
+        None => Truncated,
+    }
+}
+
+/// Checks `e_ident` (class, data encoding, version) and `e_version`.
+fn validate_elf(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(ELF_MAGIC) {
+        return Malformed;
+    }
+
+    let Some(&[class, data, version]) = bytes.get(4..7) else {
+        return Truncated;
+    };
+
+    if !matches!(class, 1 | 2) || !matches!(data, 1 | 2) || version != 1 {
+        return Malformed;
+    }
+
+    let Some(e_version) = read_array(bytes, ELF_VERSION_END - 4) else {
+        return Truncated;
+    };
+
+    let e_version = if data == 1 {
+        u32::from_le_bytes(e_version)
+    } else {
+        u32::from_be_bytes(e_version)
+    };
+
+    if e_version == 1 { Valid } else { Malformed }
+}
+
+/// Checks the first local file header: version, compression method and file name.
+fn validate_zip(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(ZIP_LOCAL_FILE_HEADER) {
+        return Malformed;
+    }
+
+    let (Some([version, _]), Some(method), Some(name_len)) = (
+        read_array(bytes, 4),
+        read_array(bytes, 8),
+        read_array(bytes, 26),
+    ) else {
+        return Truncated;
+    };
+
+    /* Stored, the legacy methods, deflate(64), PKWARE DCL, bzip2, LZMA, IBM TERSE and LZ77,
+     * zstd, MP3, xz, JPEG, WavPack, PPMd and AES. */
+    let known_method = matches!(
+        u16::from_le_bytes(method),
+        0..=10 | 12 | 14 | 18 | 19 | 93..=99
+    );
+    let name_len = u16::from_le_bytes(name_len) as usize;
+
+    if version > ZIP_MAX_VERSION || !known_method || name_len == 0 {
+        return Malformed;
+    }
+
+    let name_start = ZIP_LOCAL_FILE_HEADER_LEN;
+
+    match bytes.get(name_start..name_start + name_len) {
+        Some(name) if name.contains(&0) => Malformed,
+        Some(_) => Valid,
+        None => Truncated,
+    }
+}
+
+/// Checks the page size (a power of two, 1 standing for 65536), file format versions
+/// and the fixed payload fractions.
+fn validate_sqlite(bytes: &[u8]) -> Validation {
+    if !bytes.starts_with(SQLITE_MAGIC) {
+        return Malformed;
+    }
+
+    let Some(header) = bytes.get(16..SQLITE_HEADER_LEN) else {
+        return Truncated;
+    };
+
+    let page_size = u16::from_be_bytes([header[0], header[1]]);
+    let valid_page_size = page_size == 1 || (page_size >= 512 && page_size.is_power_of_two());
+
+    if !valid_page_size
+        || !matches!(header[2], 1 | 2)
+        || !matches!(header[3], 1 | 2)
+        || header[5..8] != [64, 32, 32]
+    {
+        return Malformed;
+    }
+
+    Valid
+}
+
+/// Runs the structural check of `kind` on `bytes`, the head of the file.
+///
+/// Returns [`None`] if `kind` has no structural check.
+#[must_use]
+pub fn validate(kind: FileKind, bytes: &[u8]) -> Option<Validation> {
+    match kind {
+        FileKind::Png => Some(validate_png(bytes)),
+        FileKind::GIF => Some(validate_gif(bytes)),
+        FileKind::Bitmap => Some(validate_bitmap(bytes)),
+        FileKind::ELF => Some(validate_elf(bytes)),
+        FileKind::SQLite => Some(validate_sqlite(bytes)),
+        FileKind::PkgZip
+        | FileKind::Docx
+        | FileKind::Xlsx
+        | FileKind::Pptx
+        | FileKind::OpenDocumentText
+        | FileKind::OpenDocumentSpreadsheet
+        | FileKind::OpenDocumentPresentation
+        | FileKind::Epub
+        | FileKind::Jar
+        | FileKind::Apk
+        | FileKind::Xpi
+        | FileKind::Nupkg
+        | FileKind::Vsix => Some(validate_zip(bytes)),
+        _ => None,
+    }
+}
@@ -0,0 +1,351 @@
+//! A small, dependency-free XML reader.
+//!
+//! It reads well-formed documents such as freedesktop.org's shared-mime-info databases
+//! into a tree of elements: attributes, text, comments, CDATA sections, the XML
+//! declaration, processing instructions and `DOCTYPE` (skipped), the predefined
+//! entities and character references. It does not validate and does not expand
+//! entities declared by a DTD.
+//!
+//! Nesting is limited to `MAX_DEPTH` elements, so walking the tree recursively
+//! cannot overflow the call stack.
+
+use std::string::String;
+use std::vec::Vec;
+
+/// Deepest element nesting accepted.
+pub const MAX_DEPTH: usize = 256;
+
+/// An element, with its attributes, children and text.
+#[derive(Clone, Debug, Default)]
+pub struct Element {
+    /// The name, with its namespace prefix if any.
+    pub name: String,
+    pub attributes: Vec<(String, String)>,
+    pub children: Vec<Self>,
+    /// The text directly inside the element, without that of its children.
+    pub text: String,
+    /// Line number of the start tag, starting at `1`.
+    pub line: usize,
+}
+
+impl Element {
+    /// Returns the name without its namespace prefix.
+    pub fn local_name(&self) -> &str {
+        self.name
+            .rsplit_once(':')
+            .map_or(self.name.as_str(), |(_, name)| name)
+    }
+
+    /// Returns the value of an attribute.
+    pub fn attribute(&self, name: &str) -> Option<&str> {
+        self.attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    /// Returns the children with the given local name.
+    pub fn children_named<'a>(&'a self, name: &'a str) -> impl Iterator<Item = &'a Self> + 'a {
+        self.children
+            .iter()
+            .filter(move |child| child.local_name() == name)
+    }
+}
+
+/// A syntax error, with the line it was found on.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct XmlError {
+    pub line: usize,
+    pub reason: String,
+}
+
+struct Reader<'a> {
+    source: &'a str,
+    position: usize,
+    line: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn rest(&self) -> &'a str {
+        &self.source[self.position..]
+    }
+
+    fn error(&self, reason: impl Into<String>) -> XmlError {
+        XmlError {
+            line: self.line,
+            reason: reason.into(),
+        }
+    }
+
+    fn advance(&mut self, len: usize) {
+        self.line += self.source[self.position..self.position + len]
+            .bytes()
+            .filter(|&byte| byte == b'\n')
+            .count();
+        self.position += len;
+    }
+
+    /// Skips past `terminator`, returning what came before it.
+    fn skip_past(&mut self, terminator: &str, what: &str) -> Result<&'a str, XmlError> {
+        let end = self
+            .rest()
+            .find(terminator)
+            .ok_or_else(|| self.error(format!("unclosed {what}")))?;
+        let skipped = &self.rest()[..end];
+
+        self.advance(end + terminator.len());
+
+        Ok(skipped)
+    }
+
+    fn skip_whitespace(&mut self) {
+        let len = self.rest().len() - self.rest().trim_start().len();
+        self.advance(len);
+    }
+
+    fn name(&mut self) -> Result<&'a str, XmlError> {
+        let len = self
+            .rest()
+            .find(|char: char| char.is_whitespace() || matches!(char, '/' | '>' | '=' | '<'))
+            .unwrap_or_else(|| self.rest().len());
+
+        if len == 0 {
+            return Err(self.error("expected a name"));
+        }
+
+        let name = &self.rest()[..len];
+        self.advance(len);
+
+        Ok(name)
+    }
+
+    /// Reads a start tag, after its `<`. Returns the element and whether it is empty (`/>`).
+    fn start_tag(&mut self) -> Result<(Element, bool), XmlError> {
+        let line = self.line;
+        let name = self.name()?.to_owned();
+        let mut attributes = Vec::new();
+
+        loop {
+            self.skip_whitespace();
+
+            if self.rest().starts_with("/>") {
+                self.advance(2);
+                break Ok((Element::new(name, attributes, line), true));
+            }
+
+            if self.rest().starts_with('>') {
+                self.advance(1);
+                break Ok((Element::new(name, attributes, line), false));
+            }
+
+            let attribute = self.name()?.to_owned();
+            self.skip_whitespace();
+
+            if !self.rest().starts_with('=') {
+                return Err(self.error(format!("expected `=` after attribute `{attribute}`")));
+            }
+
+            self.advance(1);
+            self.skip_whitespace();
+
+            let Some(quote @ ('"' | '\'')) = self.rest().chars().next() else {
+                return Err(self.error(format!("unquoted value of attribute `{attribute}`")));
+            };
+
+            self.advance(1);
+            let value = self.skip_past(if quote == '"' { "\"" } else { "'" }, "attribute value")?;
+            let value = decode(value).map_err(|reason| self.error(reason))?;
+
+            attributes.push((attribute, value));
+        }
+    }
+}
+
+impl Element {
+    const fn new(name: String, attributes: Vec<(String, String)>, line: usize) -> Self {
+        Self {
+            name,
+            attributes,
+            children: Vec::new(),
+            text: String::new(),
+            line,
+        }
+    }
+}
+
+/// Replaces entity and character references.
+fn decode(text: &str) -> Result<String, String> {
+    if !text.contains('&') {
+        return Ok(text.to_owned());
+    }
+
+    let mut output = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(start) = rest.find('&') {
+        output.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+
+        let end = rest.find(';').ok_or("unterminated entity reference")?;
+        let entity = &rest[..end];
+        rest = &rest[end + 1..];
+
+        let char = match entity {
+            "lt" => '<',
+            "gt" => '>',
+            "amp" => '&',
+            "quot" => '"',
+            "apos" => '\'',
+            _ => {
+                let code = entity
+                    .strip_prefix("#x")
+                    .or_else(|| entity.strip_prefix("#X"))
+                    .map_or_else(
+                        || {
+                            entity
+                                .strip_prefix('#')
+                                .and_then(|decimal| decimal.parse().ok())
+                        },
+                        |hex| u32::from_str_radix(hex, 16).ok(),
+                    );
+
+                code.and_then(char::from_u32)
+                    .ok_or_else(|| format!("unknown entity `&{entity};`"))?
+            }
+        };
+
+        output.push(char);
+    }
+
+    output.push_str(rest);
+
+    Ok(output)
+}
+
+/// Parses a document, returning its root element.
+pub fn parse(source: &str) -> Result<Element, XmlError> {
+    let mut reader = Reader {
+        source: source.strip_prefix('\u{FEFF}').unwrap_or(source),
+        position: 0,
+        line: 1,
+    };
+
+    /* Open elements, innermost last. The root is pushed into `root` once closed. */
+    let mut open: Vec<Element> = Vec::new();
+    let mut root: Option<Box<Element>> = None;
+
+    while !reader.rest().is_empty() {
+        let rest = reader.rest();
+
+        if rest.starts_with("<?") {
+            reader.skip_past("?>", "processing instruction")?;
+        } else if rest.starts_with("<!--") {
+            reader.skip_past("-->", "comment")?;
+        } else if rest.starts_with("<![CDATA[") {
+            reader.advance("<![CDATA[".len());
+            let text = reader.skip_past("]]>", "CDATA section")?;
+
+            match open.last_mut() {
+                Some(element) => element.text.push_str(text),
+                None => return Err(reader.error("CDATA section outside of the root element")),
+            }
+        } else if rest.starts_with("<!") {
+            skip_declaration(&mut reader)?;
+        } else if rest.starts_with("</") {
+            reader.advance(2);
+            let name = reader.name()?;
+            reader.skip_whitespace();
+
+            if !reader.rest().starts_with('>') {
+                return Err(reader.error(format!("expected `>` after `</{name}`")));
+            }
+
+            reader.advance(1);
+
+            let element = open
+                .pop()
+                .ok_or_else(|| reader.error(format!("unexpected `</{name}>`")))?;
+
+            if element.name != name {
+                return Err(reader.error(format!(
+                    "`</{name}>` closes `<{}>` opened on line {}",
+                    element.name, element.line
+                )));
+            }
+
+            close(&mut open, &mut root, element, &reader)?;
+        } else if rest.starts_with('<') {
+            reader.advance(1);
+            let (element, empty) = reader.start_tag()?;
+
+            if empty {
+                close(&mut open, &mut root, element, &reader)?;
+            } else if open.len() == MAX_DEPTH {
+                return Err(reader.error(format!("elements nested deeper than {MAX_DEPTH}")));
+            } else {
+                open.push(element);
+            }
+        } else {
+            let len = rest.find('<').unwrap_or(rest.len());
+            let text = &rest[..len];
+
+            match open.last_mut() {
+                Some(element) => {
+                    let text = decode(text).map_err(|reason| reader.error(reason))?;
+                    element.text.push_str(&text);
+                }
+                None if text.trim().is_empty() => {}
+                None => return Err(reader.error("text outside of the root element")),
+            }
+
+            reader.advance(len);
+        }
+    }
+
+    if let Some(element) = open.last() {
+        return Err(XmlError {
+            line: element.line,
+            reason: format!("unclosed `<{}>`", element.name),
+        });
+    }
+
+    root.map(|root| *root)
+        .ok_or_else(|| reader.error("no root element"))
+}
+
+/// Attaches a closed element to its parent, or makes it the root.
+fn close(
+    open: &mut [Element],
+    root: &mut Option<Box<Element>>,
+    element: Element,
+    reader: &Reader<'_>,
+) -> Result<(), XmlError> {
+    if let Some(parent) = open.last_mut() {
+        parent.children.push(element);
+    } else if root.is_some() {
+        return Err(reader.error(format!("second root element `<{}>`", element.name)));
+    } else {
+        *root = Some(Box::new(element));
+    }
+
+    Ok(())
+}
+
+/// Skips a `<!DOCTYPE ...>` declaration, including an internal subset in brackets.
+fn skip_declaration(reader: &mut Reader<'_>) -> Result<(), XmlError> {
+    let mut depth = 0usize;
+
+    for (index, char) in reader.rest().char_indices() {
+        match char {
+            '[' => depth += 1,
+            ']' => depth = depth.saturating_sub(1),
+            '>' if depth == 0 => {
+                reader.advance(index + 1);
+                return Ok(());
+            }
+            _ => {}
+        }
+    }
+
+    Err(reader.error("unclosed declaration"))
+}
@@ -0,0 +1,110 @@
+/// Repeats an MPEG audio or ADTS `header` into frames of `len` bytes.
+fn frames(header: &[u8], len: usize, count: usize) -> Vec<u8> {
+    let mut frame = header.to_vec();
+    frame.resize(len, 0);
+
+    frame.repeat(count)
+}
+
+#[test]
+fn test_audio_mp3_frames() {
+    use magical_rs::magical::magic::FileKind;
+
+    // MPEG-1 layer III, 128 kbit/s, 44.1 kHz: 417 bytes per frame.
+    let mp3 = frames(b"\xFF\xFB\x90\x64", 417, 3);
+    assert_eq!(FileKind::match_types(&mp3), Some(FileKind::MP3));
+
+    let mut tagged = b"ID3\x04\0\0\0\0\0\x20".to_vec();
+    tagged.resize(10 + 0x20, 0);
+    tagged.extend_from_slice(&mp3);
+    assert_eq!(FileKind::match_types(&tagged), Some(FileKind::MP3));
+
+    // The second frame header is not where the first one says.
+    let mut broken = mp3.clone();
+    broken[417] = b'x';
+    assert_eq!(FileKind::match_types(&broken), None);
+
+    // Layer I, and a reserved sample rate.
+    assert_eq!(
+        FileKind::match_types(&frames(b"\xFF\xFF\x90\x64", 417, 3)),
+        None
+    );
+    assert_eq!(
+        FileKind::match_types(&frames(b"\xFF\xFB\x9C\x64", 417, 3)),
+        None
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_audio_mp3_large_tag() {
+    use magical_rs::magical::bytes_read::detect_reader;
+    use magical_rs::magical::magic::FileKind;
+    use std::io::Cursor;
+
+    // A 60 KB tag, as cover art makes them: the frames start past every window read.
+    let mut mp3 = b"ID3\x04\0\0\x00\x03\x54\x60".to_vec();
+    mp3.resize(10 + 60000, 0x55);
+    mp3.extend_from_slice(&frames(b"\xFF\xFB\x90\x64", 417, 100));
+
+    assert_eq!(FileKind::match_types(&mp3), Some(FileKind::MP3));
+    assert_eq!(
+        detect_reader(Cursor::new(mp3)).unwrap(),
+        Some(FileKind::MP3)
+    );
+}
+
+#[test]
+fn test_audio_adts_and_flac() {
+    use magical_rs::magical::magic::FileKind;
+
+    // AAC LC, 44.1 kHz, stereo, 100 bytes per frame.
+    let aac = frames(b"\xFF\xF1\x50\x80\x0C\x9F\xFC", 100, 3);
+    assert_eq!(FileKind::match_types(&aac), Some(FileKind::Aac));
+
+    let mut flac = b"ID3\x03\0\0\0\0\0\0".to_vec();
+    flac.extend_from_slice(b"fLaC\x80\0\0\x22");
+    assert_eq!(FileKind::match_types(&flac), Some(FileKind::Flac));
+    assert_eq!(FileKind::match_types(b"fLaC\x04\0\0\x22"), None);
+}
+
+#[test]
+fn test_audio_ogg_codecs() {
+    use magical_rs::magical::magic::FileKind;
+
+    let page = |packet: &[u8]| {
+        let mut page = b"OggS\0\x02".to_vec();
+        page.resize(26, 0);
+        page.push(1);
+        page.push(u8::try_from(packet.len()).unwrap());
+        page.extend_from_slice(packet);
+        page
+    };
+
+    for (packet, kind) in [
+        (&b"OpusHead\x01\x02"[..], FileKind::Opus),
+        (b"\x01vorbis\0\0\0\0", FileKind::Vorbis),
+        (b"Speex   1.2", FileKind::Speex),
+        (b"\x7FFLAC\x01\0", FileKind::OGG),
+    ] {
+        assert_eq!(FileKind::match_types(&page(packet)), Some(kind), "{kind:?}");
+    }
+}
+
+#[test]
+fn test_audio_magic_numbers() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (bytes, kind) in [
+        (&b"FORM\0\0\x10\0AIFFCOMM"[..], FileKind::Aiff),
+        (b"FORM\0\0\x10\0AIFCFVER", FileKind::Aiff),
+        (b"MThd\0\0\0\x06\0\x01\0\x02", FileKind::Midi),
+        (b"MAC \x96\x0F", FileKind::Ape),
+        (b"wvpk\x20\0\0\0", FileKind::WavPack),
+        (b"#!AMR\n\x3C", FileKind::Amr),
+        (b"#!AMR-WB\n\x04", FileKind::Amr),
+        (b"#!/bin/sh\n", FileKind::ScriptExecute),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "cli")]
+
+fn magical(args: &[&str]) -> (String, i32) {
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_magical"))
+        .args(args)
+        .output()
+        .unwrap();
+
+    (
+        String::from_utf8(output.stdout).unwrap(),
+        output.status.code().unwrap(),
+    )
+}
+
+#[test]
+fn test_cli_default_output() {
+    let (stdout, code) = magical(&["tests/1.png", "tests/2.iso"]);
+
+    assert_eq!(code, 0);
+    assert_eq!(
+        stdout,
+        "tests/1.png: Portable Network Graphics (Png, image/png, .png)\n\
+         tests/2.iso: ISO 9660 CD-ROM filesystem image (ISO, application/x-iso9660-image, .iso)\n"
+    );
+}
+
+#[test]
+fn test_cli_brief_mime() {
+    let (stdout, code) = magical(&["-b", "--mime", "tests/1.png", "tests/4.webp"]);
+
+    assert_eq!(code, 0);
+    assert_eq!(stdout, "image/png\nimage/webp\n");
+}
+
+#[test]
+fn test_cli_json() {
+    let (stdout, code) = magical(&["--json", "--all-matches", "tests/3.class", "tests/missing"]);
+    let lines: Vec<&str> = stdout.lines().collect();
+
+    assert_eq!(code, 1);
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].starts_with(r#"{"path":"tests/3.class","status":"known","kind":"Class","#));
+    assert!(lines[0].contains(r#""matches":[{"kind":"Class","#));
+    assert!(lines[1].starts_with(r#"{"path":"tests/missing","status":"error","error":"#));
+}
+
+#[test]
+fn test_cli_walks_directories() {
+    let (stdout, code) = magical(&["--brief", "--mime", "tests"]);
+
+    assert_eq!(code, 0);
+    assert!(stdout.lines().any(|line| line == "image/png"));
+    assert!(
+        stdout
+            .lines()
+            .any(|line| line == "application/x-iso9660-image")
+    );
+}
+
+#[test]
+fn test_cli_usage_error() {
+    assert_eq!(magical(&[]).1, 2);
+    assert_eq!(magical(&["--unknown", "tests/1.png"]).1, 2);
+    assert_eq!(magical(&["--help"]).1, 0);
+}
@@ -0,0 +1,153 @@
+#[cfg(feature = "std")]
+fn leak<T>(value: Vec<T>) -> &'static [T] {
+    Box::leak(value.into_boxed_slice())
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_compiled_rules_same_as_match_types_custom() {
+    use magical_rs::magical::compiled_rules::CompiledRules;
+    use magical_rs::magical::magic_custom::{
+        CustomMatchRules, MagicCustom, match_all_types_custom, match_types_custom,
+    };
+    use magical_rs::magical::match_rules::Strength;
+
+    fn starts_with_zero(bytes: &[u8]) -> bool {
+        bytes.first() == Some(&0)
+    }
+
+    fn is_long(bytes: &[u8]) -> bool {
+        bytes.len() > 40
+    }
+
+    let mut seed: u64 = 7;
+    let mut next = move || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 56) as u8
+    };
+
+    let mut rules: Vec<MagicCustom<usize>> = Vec::new();
+
+    for index in 0..400 {
+        let rule = match index % 50 {
+            7 => MagicCustom {
+                signatures: &[],
+                offsets: &[],
+                max_bytes_read: 64,
+                kind: index,
+                strength: Strength::Auto,
+                rules: CustomMatchRules::WithFn(starts_with_zero),
+            },
+            13 => MagicCustom {
+                signatures: &[],
+                offsets: &[],
+                max_bytes_read: 64,
+                kind: index,
+                strength: Strength::Auto,
+                rules: CustomMatchRules::AllMatches(&[starts_with_zero, is_long]),
+            },
+            _ => {
+                /* Tiny alphabet so that signatures overlap and share prefixes. */
+                let signatures = (0..=index % 3)
+                    .map(|_| {
+                        let len = 1 + usize::from(next() % 4);
+                        leak((0..len).map(|_| next() % 4).collect())
+                    })
+                    .collect();
+                let offsets = (0..=index % 2).map(|_| usize::from(next() % 8)).collect();
+
+                MagicCustom {
+                    signatures: leak(signatures),
+                    offsets: leak(offsets),
+                    max_bytes_read: 64,
+                    kind: index,
+                    strength: Strength::Auto,
+                    rules: CustomMatchRules::Default,
+                }
+            }
+        };
+
+        rules.push(rule);
+    }
+
+    let compiled = CompiledRules::new(&rules);
+
+    for round in 0..2000 {
+        let len = round % 48;
+        let bytes: Vec<u8> = (0..len).map(|_| next() % 4).collect();
+
+        assert_eq!(
+            compiled.match_types(&bytes, usize::MAX),
+            match_types_custom(&bytes, &rules, usize::MAX)
+        );
+        assert_eq!(
+            compiled.match_all_types(&bytes),
+            match_all_types_custom(&bytes, &rules)
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_compiled_rules_second_stage_precedence() {
+    use magical_rs::magical::compiled_rules::CompiledRules;
+    use magical_rs::with_fn_matches;
+    use magical_rs::{magic_custom, magical::magic_custom::CustomMatchRules};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum ShoujoKind {
+        FirstFn,
+        Signature,
+        LastFn,
+        Unknown,
+    }
+
+    fn always(_bytes: &[u8]) -> bool {
+        true
+    }
+
+    fn never(_bytes: &[u8]) -> bool {
+        false
+    }
+
+    let rules = [
+        magic_custom!(
+            signatures: [],
+            offsets: [],
+            max_bytes_read: 8,
+            kind: ShoujoKind::FirstFn,
+            rules: with_fn_matches!(never)
+        ),
+        magic_custom!(
+            signatures: [b"Shoujo"],
+            offsets: [0],
+            max_bytes_read: 8,
+            kind: ShoujoKind::Signature,
+            rules: CustomMatchRules::Default
+        ),
+        magic_custom!(
+            signatures: [],
+            offsets: [],
+            max_bytes_read: 8,
+            kind: ShoujoKind::LastFn,
+            rules: with_fn_matches!(always)
+        ),
+    ];
+
+    let compiled = CompiledRules::new(&rules);
+
+    assert_eq!(
+        compiled.match_types(b"Shoujo", ShoujoKind::Unknown),
+        ShoujoKind::Signature
+    );
+    assert_eq!(
+        compiled.match_types(b"Idol", ShoujoKind::Unknown),
+        ShoujoKind::LastFn
+    );
+    assert_eq!(
+        compiled.match_all_types(b"Shoujo"),
+        vec![ShoujoKind::Signature, ShoujoKind::LastFn]
+    );
+}
@@ -0,0 +1,79 @@
+#[test]
+fn test_compression_magic_numbers() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (bytes, kind) in [
+        (&b"BZh91AY&SY"[..], FileKind::Bzip),
+        (b"\xFD7zXZ\0\0\x04\xE6\xD6\xB4\x46", FileKind::Xz),
+        (b"7z\xBC\xAF\x27\x1C\0\x04", FileKind::SevenZip),
+        (b"LZIP\x01\x0C\0", FileKind::Lzip),
+        (b"\x89LZO\0\r\n\x1A\n\x10\x40", FileKind::Lzop),
+        (b"070701000000000000", FileKind::Cpio),
+        (b"\xC7\x71\x0A\0", FileKind::Cpio),
+        (b"\x1F\x9D\x90\x68\x65", FileKind::UnixCompress),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+
+    // `BZ` followed by anything but a block size is not bzip2.
+    assert_eq!(FileKind::match_types(b"BZh0"), None);
+    assert_eq!(FileKind::match_types(b"BZ is for Brazil"), None);
+
+    // A code width of 17 bits is out of range.
+    assert_eq!(FileKind::match_types(b"\x1F\x9D\x91"), None);
+}
+
+#[test]
+fn test_compression_skippable_frames() {
+    use magical_rs::magical::ext_fn::compression::{is_lz4, is_zstd};
+    use magical_rs::magical::magic::FileKind;
+
+    let zstd = b"\x28\xB5\x2F\xFD\x24\x05\x29\0\0hello";
+    assert_eq!(FileKind::match_types(zstd), Some(FileKind::Zstd));
+
+    let mut lz4 = b"\x5A\x2A\x4D\x18\x04\0\0\0skip".to_vec();
+    lz4.extend_from_slice(b"\x04\x22\x4D\x18\x64\x40\xA7");
+    assert!(is_lz4(&lz4));
+    assert!(!is_zstd(&lz4));
+    assert_eq!(FileKind::match_types(&lz4), Some(FileKind::Lz4));
+
+    let legacy = b"\x02\x21\x4C\x18\x10\0\0\0";
+    assert_eq!(FileKind::match_types(legacy), Some(FileKind::Lz4));
+
+    // The frame after the skippable one is past the bytes read.
+    let mut seekable = b"\x50\x2A\x4D\x18\0\x10\0\0".to_vec();
+    seekable.resize(64, 0);
+    assert!(is_zstd(&seekable));
+    assert!(!is_lz4(&seekable));
+
+    // A skippable frame followed by something that is neither zstd nor LZ4.
+    let text = b"\x50\x2A\x4D\x18\x02\0\0\0\0\0plain text, not a frame";
+    assert!(!is_zstd(text));
+    assert_ne!(FileKind::match_types(text), Some(FileKind::Zstd));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_compression_lzma() {
+    use magical_rs::magical::magic::FileKind;
+
+    // `lc=3 lp=0 pb=2`, 8 MiB dictionary, unknown size.
+    let lzma = b"\x5D\0\0\x80\0\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF\0\x33";
+    let ranked = FileKind::match_types_ranked(lzma);
+    assert_eq!(ranked[0].kind, FileKind::Lzma);
+    assert_eq!(ranked[0].confidence, 30);
+
+    // 12 MiB dictionary and a known size.
+    let lzma = b"\x5D\0\0\xC0\0\x00\x10\0\0\0\0\0\0";
+    assert_eq!(FileKind::match_types(lzma), Some(FileKind::Lzma));
+
+    // Properties out of range, or a dictionary size no encoder writes.
+    assert_eq!(
+        FileKind::match_types(b"\xE1\0\0\x80\0\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"),
+        None
+    );
+    assert_eq!(
+        FileKind::match_types(b"\x5D\x01\0\x80\0\xFF\xFF\xFF\xFF\xFF\xFF\xFF\xFF"),
+        None
+    );
+}
@@ -0,0 +1,87 @@
+#[test]
+fn test_data_columnar_formats() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (bytes, kind) in [
+        (&b"ARROW1\0\0\xFF\xFF\xFF\xFF"[..], FileKind::ArrowIpc),
+        (b"FEA1\0\0\0\0", FileKind::Feather),
+        (b"ORC\x0A\x03", FileKind::Orc),
+        (b"Obj\x01\x04\x14avro.codec", FileKind::AvroObjectContainer),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+
+    // `PAR1` is needed at both ends.
+    let parquet = b"PAR1\x15\x04\x15\x10\0\0\0\0\x2A\x01\0\0PAR1";
+    assert_eq!(FileKind::match_types(parquet), None);
+    assert_eq!(
+        FileKind::match_types_with_tail(parquet, parquet),
+        Some(FileKind::Parquet)
+    );
+
+    let text = b"PAR1 some text that is not parquet";
+    assert_eq!(FileKind::match_types_with_tail(text, text), None);
+
+    // Only the footer is available, e.g. after a truncated download.
+    let footer = b"\0\0\0\0\x2A\x01\0\0PAR1";
+    assert_eq!(FileKind::match_types_with_tail(b"\x15\x04", footer), None);
+}
+
+#[test]
+fn test_data_hdf5_user_block() {
+    use magical_rs::magical::magic::FileKind;
+
+    for offset in [0, 512, 4096, 65536] {
+        let mut hdf5 = vec![0; offset];
+        hdf5.extend_from_slice(b"\x89HDF\r\n\x1A\n\0\0\0\0");
+
+        assert_eq!(
+            FileKind::match_types(&hdf5),
+            Some(FileKind::Hdf5),
+            "{offset}"
+        );
+    }
+
+    // Not a power of two.
+    let mut hdf5 = vec![0; 768];
+    hdf5.extend_from_slice(b"\x89HDF\r\n\x1A\n");
+    assert_eq!(FileKind::match_types(&hdf5), None);
+
+    // MATLAB 7.3: a MAT header in the 512 bytes user block of an HDF5 file.
+    let mut mat = b"MATLAB 7.3 MAT-file, Platform: GLNXA64, Created on: Fri Oct 17".to_vec();
+    mat.resize(512, b' ');
+    mat.extend_from_slice(b"\x89HDF\r\n\x1A\n");
+    assert_eq!(FileKind::match_types(&mat), Some(FileKind::MatlabMat));
+    assert_eq!(
+        FileKind::match_all_types(&mat)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [FileKind::MatlabMat, FileKind::Hdf5]
+    );
+
+    let mat = b"MATLAB 5.0 MAT-file, Platform: PCWIN64";
+    assert_eq!(FileKind::match_types(mat), Some(FileKind::MatlabMat));
+}
+
+#[test]
+fn test_data_scientific_formats() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut fits = b"SIMPLE  =                    T / conforms to FITS standard".to_vec();
+    fits.resize(80, b' ');
+
+    for (bytes, kind) in [
+        (&fits[..], FileKind::Fits),
+        (b"CDF\x01\0\0\0\x0A", FileKind::NetCdf),
+        (b"CDF\x02\0\0\0\0", FileKind::NetCdf),
+        (b"\x93NUMPY\x01\0\x76\0{'descr': '<f8'", FileKind::NumpyNpy),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+
+    assert_eq!(
+        FileKind::match_types(b"SIMPLE  =                    F"),
+        None
+    );
+    assert_eq!(FileKind::match_types(b"CDF\x03\0\0\0\0"), None);
+}
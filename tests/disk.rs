@@ -0,0 +1,91 @@
+/// A sector with the `55 AA` boot signature and the given partition entries.
+fn mbr(entries: &[[u8; 16]]) -> Vec<u8> {
+    let mut sector = vec![0; 512];
+
+    for (index, entry) in entries.iter().enumerate() {
+        sector[446 + index * 16..462 + index * 16].copy_from_slice(entry);
+    }
+
+    sector[510..].copy_from_slice(b"\x55\xAA");
+    sector
+}
+
+const LINUX: [u8; 16] = *b"\x80\x20\x21\0\x83\xFE\xFF\xFF\0\x08\0\0\0\xF8\x7F\0";
+const PROTECTIVE: [u8; 16] = *b"\0\0\x02\0\xEE\xFF\xFF\xFF\x01\0\0\0\xFF\xFF\xFF\xFF";
+
+#[test]
+fn test_disk_vm_images() {
+    use magical_rs::magical::ext_fn::disk::qcow_version;
+    use magical_rs::magical::magic::FileKind;
+
+    let qcow2 = b"QFI\xFB\0\0\0\x03\0\0\0\0\0\0\0\0";
+    assert_eq!(FileKind::match_types(qcow2), Some(FileKind::Qcow2));
+    assert_eq!(qcow_version(b"QFI\xFB\0\0\0\x01"), Some(1));
+    assert_eq!(FileKind::match_types(b"QFI\xFB\0\0\0\x01"), None);
+
+    let mut vdi = b"<<< Oracle VM VirtualBox Disk Image >>>\n".to_vec();
+    vdi.resize(64, 0);
+    vdi.extend_from_slice(b"\x7F\x10\xDA\xBE\x01\0\x01\0");
+    assert_eq!(FileKind::match_types(&vdi), Some(FileKind::Vdi));
+
+    for (bytes, kind) in [
+        (&b"conectix\0\0\0\x02\0\x01\0\0"[..], FileKind::Vhd),
+        (b"vhdxfile\0\0", FileKind::Vhdx),
+        (b"KDMV\x01\0\0\0", FileKind::Vmdk),
+        (b"# Disk DescriptorFile\nversion=1\n", FileKind::Vmdk),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+}
+
+#[test]
+fn test_disk_partition_tables() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::match_types(&mbr(&[LINUX])), Some(FileKind::Mbr));
+
+    // Boot signature alone, a bad status byte, or an empty partition.
+    assert_eq!(FileKind::match_types(&mbr(&[])), None);
+
+    let mut bad_status = LINUX;
+    bad_status[0] = 0x7F;
+    assert_eq!(FileKind::match_types(&mbr(&[bad_status])), None);
+
+    let mut empty = LINUX;
+    empty[12..].fill(0);
+    assert_eq!(FileKind::match_types(&mbr(&[empty])), None);
+
+    // A protective MBR in front of a GPT, with 512 and 4096 bytes sectors.
+    for sector in [512, 4096] {
+        let mut disk = mbr(&[PROTECTIVE]);
+        disk.resize(sector, 0);
+        disk.extend_from_slice(b"EFI PART\0\0\x01\0\x5C\0\0\0");
+
+        assert_eq!(
+            FileKind::match_types(&disk),
+            Some(FileKind::Gpt),
+            "{sector}"
+        );
+        assert_eq!(
+            FileKind::match_all_types(&disk)
+                .into_iter()
+                .collect::<Vec<_>>(),
+            [FileKind::Gpt]
+        );
+    }
+}
+
+#[test]
+fn test_disk_fixed_vhd_footer() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut disk = mbr(&[LINUX]);
+    disk.extend_from_slice(b"conectix\0\0\0\x02\0\x01\0\0");
+    disk.resize(1024, 0);
+
+    assert_eq!(FileKind::match_types(&disk), Some(FileKind::Mbr));
+    assert_eq!(
+        FileKind::match_types_with_tail(&disk, &disk),
+        Some(FileKind::Vhd)
+    );
+}
@@ -0,0 +1,72 @@
+fn linear_scan(bytes: &[u8]) -> Option<magical_rs::magical::magic::FileKind> {
+    use magical_rs::magical::signatures::SIGNATURE_KIND;
+
+    SIGNATURE_KIND
+        .iter()
+        .find(|magic| magic.matches(bytes))
+        .map(|magic| magic.kind)
+}
+
+#[test]
+fn test_dispatch_matches_linear_scan_on_signatures() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::signatures::SIGNATURE_KIND;
+
+    for magic in SIGNATURE_KIND {
+        for signature in magic.signatures {
+            for &offset in magic.offsets {
+                let mut bytes = vec![0u8; offset + signature.len() + 16];
+                bytes[offset..offset + signature.len()].copy_from_slice(signature);
+
+                for len in [0, 1, offset + signature.len(), bytes.len()] {
+                    let bytes = &bytes[..len];
+                    assert_eq!(FileKind::match_types(bytes), linear_scan(bytes));
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn test_dispatch_matches_linear_scan_on_noise() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut seed: u64 = 42;
+    let mut next = || {
+        seed = seed
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (seed >> 56) as u8
+    };
+
+    for first in 0..=255u8 {
+        for _ in 0..8 {
+            let mut bytes: Vec<u8> = (0..300).map(|_| next()).collect();
+            bytes[0] = first;
+
+            assert_eq!(FileKind::match_types(&bytes), linear_scan(&bytes));
+            assert_eq!(FileKind::match_types(&bytes[..2]), linear_scan(&bytes[..2]));
+        }
+    }
+
+    assert_eq!(FileKind::match_types(&[]), linear_scan(&[]));
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_dispatch_matches_linear_scan_on_files() {
+    use magical_rs::magical::bytes_read::{read_file_header, with_bytes_read};
+    use magical_rs::magical::magic::FileKind;
+
+    for file in [
+        "tests/1.png",
+        "tests/2.iso",
+        "tests/3.class",
+        "tests/4.webp",
+    ] {
+        let bytes = read_file_header(file, with_bytes_read()).unwrap();
+
+        assert_eq!(FileKind::match_types(&bytes), linear_scan(&bytes));
+        assert!(FileKind::match_types(&bytes).is_some());
+    }
+}
@@ -0,0 +1,169 @@
+/// Builds an `MZ` stub, a PE header and a PE32 (`0x10B`) or PE32+ (`0x20B`) optional header.
+fn pe(machine: u16, magic: u16, characteristics: u16, subsystem: u16, dot_net: bool) -> Vec<u8> {
+    let mut file = b"MZ\x90\0\x03\0\0\0\x04\0".to_vec();
+    file.resize(0x3C, 0);
+    file[0x18] = 0x40;
+    file.extend_from_slice(&0x40u32.to_le_bytes());
+
+    let optional_len: u16 = if magic == 0x20B { 240 } else { 224 };
+
+    file.extend_from_slice(b"PE\0\0");
+    file.extend_from_slice(&machine.to_le_bytes());
+    file.extend_from_slice(&[1, 0]);
+    file.extend_from_slice(&[0; 12]);
+    file.extend_from_slice(&optional_len.to_le_bytes());
+    file.extend_from_slice(&characteristics.to_le_bytes());
+
+    let optional = file.len();
+    file.resize(optional + optional_len as usize, 0);
+    file[optional..optional + 2].copy_from_slice(&magic.to_le_bytes());
+    file[optional + 68..optional + 70].copy_from_slice(&subsystem.to_le_bytes());
+
+    let directories = optional + if magic == 0x20B { 108 } else { 92 };
+    file[directories..directories + 4].copy_from_slice(&16u32.to_le_bytes());
+
+    if dot_net {
+        let clr = directories + 4 + 14 * 8;
+        file[clr..clr + 8].copy_from_slice(&[0x08, 0x20, 0, 0, 0x48, 0, 0, 0]);
+    }
+
+    file
+}
+
+#[test]
+fn test_exe_info_elf() {
+    use magical_rs::magical::exe_info::{
+        Bitness, Endianness, ExeFormat, ExeType, Machine, exe_info,
+    };
+
+    let elf = b"\x7FELF\x01\x02\x01\0\0\0\0\0\0\0\0\0\0\x02\0\x14\0\0\0\x01";
+    let info = exe_info(elf).unwrap();
+
+    assert_eq!(info.format, ExeFormat::Elf);
+    assert_eq!(info.bitness, Some(Bitness::Bits32));
+    assert_eq!(info.endianness, Some(Endianness::Big));
+    assert_eq!(info.machine, Machine::PowerPc);
+    assert_eq!(info.file_type, Some(ExeType::Executable));
+    assert_eq!(info.pe, None);
+
+    let core = b"\x7FELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x04\0\xB7\0\x01\0\0\0";
+    let info = exe_info(core).unwrap();
+
+    assert_eq!(info.machine, Machine::Aarch64);
+    assert_eq!(info.file_type, Some(ExeType::Core));
+    assert_eq!(exe_info(&core[..17]), None);
+}
+
+#[test]
+fn test_exe_info_pe() {
+    use magical_rs::magical::exe_info::{
+        Bitness, ExeFormat, ExeType, Machine, PeSubsystem, exe_info,
+    };
+
+    let info = exe_info(&pe(0x8664, 0x20B, 0x0022, 3, false)).unwrap();
+    let header = info.pe.unwrap();
+
+    assert_eq!(info.format, ExeFormat::Pe);
+    assert_eq!(info.bitness, Some(Bitness::Bits64));
+    assert_eq!(info.machine, Machine::X86_64);
+    assert_eq!(info.file_type, Some(ExeType::Executable));
+    assert_eq!(header.header_offset, 0x40);
+    assert_eq!(header.subsystem, Some(PeSubsystem::WindowsCui));
+    assert!(!header.dot_net);
+
+    let info = exe_info(&pe(0x14C, 0x10B, 0x2102, 2, true)).unwrap();
+    let header = info.pe.unwrap();
+
+    assert_eq!(info.bitness, Some(Bitness::Bits32));
+    assert_eq!(info.machine, Machine::X86);
+    assert_eq!(info.file_type, Some(ExeType::SharedLibrary));
+    assert_eq!(header.subsystem, Some(PeSubsystem::WindowsGui));
+    assert!(header.dot_net);
+
+    // `e_lfanew` pointing at anything but `PE\0\0`.
+    let mut dos = pe(0x14C, 0x10B, 0x0102, 2, false);
+    dos[0x40] = b'N';
+    assert_eq!(exe_info(&dos).unwrap().format, ExeFormat::MsDos);
+}
+
+#[test]
+fn test_exe_info_mach_o() {
+    use magical_rs::magical::exe_info::{
+        Bitness, Endianness, ExeFormat, ExeType, Machine, exe_info,
+    };
+    use magical_rs::magical::magic::FileKind;
+
+    let dylib = b"\xCF\xFA\xED\xFE\x0C\0\0\x01\0\0\0\0\x06\0\0\0";
+    let info = exe_info(dylib).unwrap();
+
+    assert_eq!(info.format, ExeFormat::MachO);
+    assert_eq!(info.bitness, Some(Bitness::Bits64));
+    assert_eq!(info.endianness, Some(Endianness::Little));
+    assert_eq!(info.machine, Machine::Aarch64);
+    assert_eq!(info.file_type, Some(ExeType::SharedLibrary));
+    assert_eq!(FileKind::match_types(dylib), Some(FileKind::MachO));
+
+    let ppc = b"\xFE\xED\xFA\xCE\0\0\0\x12\0\0\0\0\0\0\0\x02";
+    assert_eq!(exe_info(ppc).unwrap().machine, Machine::PowerPc);
+    assert_eq!(FileKind::match_types(ppc), Some(FileKind::MachO));
+}
+
+#[test]
+fn test_exe_info_fat_binary_or_class() {
+    use magical_rs::magical::exe_info::{Bitness, ExeFormat, Machine, exe_info};
+    use magical_rs::magical::magic::FileKind;
+
+    // x86_64 and arm64 slices.
+    let fat = b"\xCA\xFE\xBA\xBE\0\0\0\x02\x01\0\0\x07\0\0\0\x03\0\0\x40\0";
+    let info = exe_info(fat).unwrap();
+
+    assert_eq!(info.format, ExeFormat::MachOFat);
+    assert_eq!(info.architectures, 2);
+    assert_eq!(info.bitness, Some(Bitness::Bits64));
+    assert_eq!(info.machine, Machine::X86_64);
+    assert_eq!(info.endianness, None);
+    assert_eq!(FileKind::match_types(fat), Some(FileKind::MachOFat));
+    assert_eq!(
+        FileKind::match_all_types(fat)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [FileKind::MachOFat]
+    );
+
+    let class = std::fs::read("tests/3.class").unwrap();
+    assert_eq!(exe_info(&class), None);
+    assert_eq!(FileKind::match_types(&class), Some(FileKind::Class));
+
+    // Java 1.1 classes have a minor version.
+    let class = b"\xCA\xFE\xBA\xBE\0\x03\0\x2D";
+    assert_eq!(FileKind::match_types(class), Some(FileKind::Class));
+    assert_eq!(FileKind::match_types(b"\xCA\xFE\xBA\xBE"), None);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_exe_info_pe_kinds() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (file, kind) in [
+        (pe(0x14C, 0x10B, 0x0102, 2, false), FileKind::Pe32),
+        (pe(0x8664, 0x20B, 0x0022, 3, false), FileKind::Pe32Plus),
+        (pe(0x8664, 0x20B, 0x2022, 2, false), FileKind::Dll),
+        (pe(0x14C, 0x10B, 0x2102, 3, true), FileKind::DotNetAssembly),
+        (pe(0x8664, 0x20B, 0x0022, 1, false), FileKind::WindowsDriver),
+    ] {
+        assert_eq!(FileKind::match_types(&file), Some(kind));
+        assert_eq!(FileKind::match_types_ranked(&file)[0].kind, kind);
+    }
+
+    // A DOS program: valid MZ header, no PE header.
+    let mut dos = pe(0x14C, 0x10B, 0x0102, 2, false);
+    dos[0x40] = 0xB4;
+    assert_eq!(FileKind::match_types(&dos), Some(FileKind::MSDOS));
+    assert_eq!(FileKind::match_types_ranked(&dos)[0].confidence, 60);
+
+    // Anything else starting with `MZ` is a weak guess.
+    let text = b"MZ is not always an executable";
+    assert_eq!(FileKind::match_types(text), Some(FileKind::MSDOS));
+    assert_eq!(FileKind::match_types_ranked(text)[0].confidence, 20);
+}
@@ -0,0 +1,100 @@
+/// A boot sector with a jump, an OEM ID, the given BIOS parameter block and `55 AA`.
+fn boot_sector(oem_id: &[u8], bpb: &[u8]) -> Vec<u8> {
+    let mut sector = b"\xEB\x3C\x90".to_vec();
+    sector.extend_from_slice(oem_id);
+    sector.extend_from_slice(bpb);
+    sector.resize(510, 0);
+    sector.extend_from_slice(b"\x55\xAA");
+    sector
+}
+
+#[test]
+fn test_filesystem_ext() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut image = vec![0; 2048];
+    image[1080..1082].copy_from_slice(&[0x53, 0xEF]);
+    assert_eq!(FileKind::match_types(&image), Some(FileKind::Ext2));
+
+    image[1116] = 0x04;
+    assert_eq!(FileKind::match_types(&image), Some(FileKind::Ext3));
+
+    // `filetype` and `sparse_super` are fine on ext3, `huge_file` is not.
+    image[1120] = 0x02;
+    image[1124] = 0x01;
+    assert_eq!(FileKind::match_types(&image), Some(FileKind::Ext3));
+
+    image[1124] = 0x08;
+    assert_eq!(FileKind::match_types(&image), Some(FileKind::Ext4));
+
+    // A block size of 128 KiB does not exist.
+    image[1048] = 7;
+    assert_eq!(FileKind::match_types(&image), None);
+}
+
+#[test]
+fn test_filesystem_fat() {
+    use magical_rs::magical::magic::FileKind;
+
+    let floppy = boot_sector(b"MSDOS5.0", b"\0\x02\x01\x01\0\x02\xE0\0\x40\x0B\xF0\x09\0");
+    let fat16 = boot_sector(b"MSDOS5.0", b"\0\x02\x04\x01\0\x02\0\x02\x40\x9C\xF8\x28\0");
+
+    let mut fat32 = b"\0\x02\x08\x20\0\x02\0\0\0\0\xF8\0\0".to_vec();
+    fat32.resize(21, 0);
+    fat32.extend_from_slice(&2_000_000u32.to_le_bytes());
+    fat32.extend_from_slice(&2000u32.to_le_bytes());
+    let fat32 = boot_sector(b"MSWIN4.1", &fat32);
+
+    assert_eq!(FileKind::match_types(&floppy), Some(FileKind::Fat12));
+    assert_eq!(FileKind::match_types(&fat16), Some(FileKind::Fat16));
+    assert_eq!(FileKind::match_types(&fat32), Some(FileKind::Fat32));
+
+    // Three FATs, or no jump instruction.
+    let mut broken = floppy.clone();
+    broken[16] = 3;
+    assert_eq!(FileKind::match_types(&broken), None);
+
+    let mut broken = floppy;
+    broken[0] = 0;
+    assert_eq!(FileKind::match_types(&broken), None);
+}
+
+#[test]
+fn test_filesystem_magic_numbers() {
+    use magical_rs::magical::magic::FileKind;
+
+    let ntfs = boot_sector(b"NTFS    ", b"\0\x02\x08\0\0");
+    let exfat = boot_sector(b"EXFAT   ", &[0; 53]);
+
+    let mut btrfs = vec![0; 0x10048];
+    btrfs[0x10040..].copy_from_slice(b"_BHRfS_M");
+
+    for (bytes, kind) in [
+        (&ntfs[..], FileKind::Ntfs),
+        (&exfat, FileKind::ExFat),
+        (b"hsqs\x0C\0\0\0", FileKind::Squashfs),
+        (b"sqsh\0\0\0\x0C", FileKind::Squashfs),
+        (b"XFSB\0\0\x10\0", FileKind::Xfs),
+        (&btrfs, FileKind::Btrfs),
+    ] {
+        assert_eq!(FileKind::match_types(bytes), Some(kind), "{kind:?}");
+    }
+}
+
+#[test]
+fn test_filesystem_udf_before_iso() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut bridge = vec![0; 38 * 1024];
+    bridge[32769..32774].copy_from_slice(b"CD001");
+    bridge[34817..34822].copy_from_slice(b"BEA01");
+    bridge[36865..36870].copy_from_slice(b"NSR02");
+
+    assert_eq!(FileKind::match_types(&bridge), Some(FileKind::Udf));
+
+    bridge[36865..36870].copy_from_slice(b"TEA01");
+    assert_eq!(FileKind::match_types(&bridge), Some(FileKind::ISO));
+
+    let iso = std::fs::read("tests/2.iso").unwrap();
+    assert_eq!(FileKind::match_types(&iso), Some(FileKind::ISO));
+}
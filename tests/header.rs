@@ -0,0 +1,82 @@
+#![cfg(feature = "std")]
+
+fn temp_file(name: &str, contents: &[u8]) -> String {
+    let path = std::env::temp_dir().join(format!("magical_rs_{}_{name}", std::process::id()));
+    std::fs::write(&path, contents).unwrap();
+
+    path.to_str().unwrap().to_owned()
+}
+
+#[test]
+fn test_read_file_header_is_not_padded() {
+    use magical_rs::magical::bytes_read::{DEFAULT_MAX_BYTES_READ, read_file_header};
+    use magical_rs::magical::magic::FileKind;
+
+    // Would match ICO_SIGNATURE (`00 00 01 00`) with a zero padded buffer.
+    let path = temp_file("short", &[0x00, 0x00, 0x01]);
+    let bytes = read_file_header(&path, DEFAULT_MAX_BYTES_READ).unwrap();
+
+    assert_eq!(bytes, [0x00, 0x00, 0x01]);
+    assert_eq!(FileKind::match_types(&bytes), None);
+
+    std::fs::remove_file(path).unwrap();
+}
+
+#[test]
+fn test_read_header() {
+    use magical_rs::magical::bytes_read::{read_header, with_bytes_read};
+    use magical_rs::magical::magic::{Detection, FileKind};
+
+    let len = std::fs::metadata("tests/1.png").unwrap().len();
+
+    let header = read_header("tests/1.png", with_bytes_read()).unwrap();
+    assert_eq!(header.file_len, len);
+    assert_eq!(header.bytes.len() as u64, len.min(with_bytes_read() as u64));
+    assert_eq!(header.detect(), Detection::Known(FileKind::Png));
+
+    let header = read_header("tests/1.png", 8).unwrap();
+    assert_eq!(header.bytes.len(), 8);
+    assert!(header.truncated);
+}
+
+#[test]
+fn test_empty_file_is_not_unknown() {
+    use magical_rs::magical::bytes_read::{DEFAULT_MAX_BYTES_READ, read_header};
+    use magical_rs::magical::magic::Detection;
+
+    let empty = temp_file("empty", &[]);
+    let unknown = temp_file("unknown", b"moe moe kyun");
+
+    let header = read_header(&empty, DEFAULT_MAX_BYTES_READ).unwrap();
+    assert!(header.is_empty());
+    assert!(!header.truncated);
+    assert_eq!(header.detect(), Detection::Empty);
+    assert_eq!(header.detect().kind(), None);
+
+    let header = read_header(&unknown, DEFAULT_MAX_BYTES_READ).unwrap();
+    assert!(!header.is_empty());
+    assert!(!header.truncated);
+    assert_eq!(header.detect(), Detection::Unknown);
+
+    std::fs::remove_file(empty).unwrap();
+    std::fs::remove_file(unknown).unwrap();
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn test_read_header_without_file_len() {
+    use magical_rs::magical::bytes_read::read_header;
+    use magical_rs::magical::magic::Detection;
+
+    // procfs reports a length of 0 for files with content.
+    let header = read_header("/proc/self/status", 1 << 16).unwrap();
+    assert_eq!(header.file_len, 0);
+    assert!(!header.bytes.is_empty());
+    assert!(!header.is_empty());
+    assert!(!header.truncated);
+    assert_ne!(header.detect(), Detection::Empty);
+
+    let header = read_header("/proc/self/status", 16).unwrap();
+    assert_eq!(header.bytes.len(), 16);
+    assert!(header.truncated);
+}
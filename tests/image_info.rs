@@ -0,0 +1,133 @@
+#[test]
+fn test_image_info_files() {
+    use magical_rs::magical::image_info::{ColorType, image_info};
+    use magical_rs::magical::magic::FileKind;
+
+    let png = std::fs::read("tests/1.png").unwrap();
+    let info = image_info(FileKind::Png, &png).unwrap();
+
+    assert_eq!((info.width, info.height), (860, 245));
+    assert_eq!(info.bits_per_pixel, Some(32));
+    assert_eq!(info.color_type, Some(ColorType::Rgba));
+    assert_eq!(info.frames, Some(1));
+
+    // The header alone has the dimensions, not the chunks up to `IDAT`.
+    let info = image_info(FileKind::Png, &png[..33]).unwrap();
+    assert_eq!(info.width, 860);
+    assert_eq!(info.frames, None);
+
+    let webp = std::fs::read("tests/4.webp").unwrap();
+    let info = image_info(FileKind::WEBP, &webp).unwrap();
+
+    assert_eq!((info.width, info.height), (1600, 900));
+    assert_eq!(info.color_type, Some(ColorType::Rgb));
+    assert_eq!(image_info(FileKind::Png, &webp), None);
+}
+
+#[test]
+fn test_image_info_apng_and_gif_frames() {
+    use magical_rs::magical::image_info::image_info;
+    use magical_rs::magical::magic::FileKind;
+
+    let mut apng = b"\x89PNG\r\n\x1A\n\0\0\0\x0DIHDR\0\0\0\x10\0\0\0\x10\x08\x03\0\0\0".to_vec();
+    apng.extend_from_slice(&[0; 4]);
+    apng.extend_from_slice(b"\0\0\0\x08acTL\0\0\0\x03\0\0\0\0");
+
+    let info = image_info(FileKind::Png, &apng).unwrap();
+    assert_eq!(info.bits_per_pixel, Some(8));
+    assert_eq!(info.frames, Some(3));
+
+    // Global color table of 2 entries, a graphic control extension and two 1x1 images.
+    let mut gif = b"GIF89a\x02\0\x01\0\x80\0\0".to_vec();
+    gif.extend_from_slice(&[0, 0, 0, 0xFF, 0xFF, 0xFF]);
+
+    for _ in 0..2 {
+        gif.extend_from_slice(b"!\xF9\x04\0\x0A\0\0\0");
+        gif.extend_from_slice(b",\0\0\0\0\x01\0\x01\0\0\x02\x02\x44\x01\0");
+    }
+
+    let info = image_info(FileKind::GIF, &gif).unwrap();
+    assert_eq!((info.width, info.height), (2, 1));
+    assert_eq!(info.bits_per_pixel, Some(1));
+    assert_eq!(info.frames, None);
+
+    gif.push(b';');
+    assert_eq!(image_info(FileKind::GIF, &gif).unwrap().frames, Some(2));
+}
+
+#[test]
+fn test_image_info_jpeg() {
+    use magical_rs::magical::image_info::{ColorType, image_info};
+    use magical_rs::magical::magic::FileKind;
+
+    let mut jpeg = b"\xFF\xD8\xFF\xE0\0\x10JFIF\0\x01\x01\0\0\x01\0\x01\0\0".to_vec();
+    jpeg.extend_from_slice(b"\xFF\xDB\0\x04\0\0");
+    jpeg.extend_from_slice(
+        b"\xFF\xC2\0\x11\x08\x01\xE0\x02\x80\x03\x01\x22\0\x02\x11\x01\x03\x11\x01",
+    );
+
+    let info = image_info(FileKind::Jpg, &jpeg).unwrap();
+    assert_eq!((info.width, info.height), (640, 480));
+    assert_eq!(info.bits_per_pixel, Some(24));
+    assert_eq!(info.color_type, Some(ColorType::YCbCr));
+
+    // Start of scan before any frame header.
+    assert_eq!(image_info(FileKind::Jpg, b"\xFF\xD8\xFF\xDA\0\x02"), None);
+    assert_eq!(image_info(FileKind::Jpg, &jpeg[..30]), None);
+}
+
+#[test]
+fn test_image_info_bitmap_ico_psd() {
+    use magical_rs::magical::image_info::{ColorType, image_info};
+    use magical_rs::magical::magic::FileKind;
+
+    // Bottom-up is positive, top-down negative.
+    let mut bitmap =
+        b"BM\0\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0\x20\0\0\0\xF0\xFF\xFF\xFF\x01\0\x18\0".to_vec();
+    bitmap.extend_from_slice(&[0; 24]);
+
+    let info = image_info(FileKind::Bitmap, &bitmap).unwrap();
+    assert_eq!((info.width, info.height), (32, 16));
+    assert_eq!(info.color_type, Some(ColorType::Rgb));
+    assert_eq!(info.bits_per_pixel, Some(24));
+
+    let mut ico = b"\0\0\x01\0\x02\0".to_vec();
+    ico.extend_from_slice(b"\x10\x10\0\0\x01\0\x08\0\0\0\0\0\0\0\0\0");
+    ico.extend_from_slice(b"\0\0\0\0\x01\0\x20\0\0\0\0\0\0\0\0\0");
+
+    let info = image_info(FileKind::ICO, &ico).unwrap();
+    assert_eq!((info.width, info.height), (256, 256));
+    assert_eq!(info.color_type, Some(ColorType::Rgba));
+    assert_eq!(info.frames, Some(2));
+    assert_eq!(image_info(FileKind::ICO, &ico[..27]), None);
+
+    let psd = b"8BPS\0\x01\0\0\0\0\0\0\0\x04\0\0\x01\0\0\0\x02\0\0\x08\0\x03";
+    let info = image_info(FileKind::_8BPS, psd).unwrap();
+    assert_eq!((info.width, info.height), (512, 256));
+    assert_eq!(info.bits_per_pixel, Some(32));
+    assert_eq!(info.color_type, Some(ColorType::Rgba));
+}
+
+#[test]
+fn test_image_info_webp_chunks() {
+    use magical_rs::magical::image_info::{ColorType, image_info};
+    use magical_rs::magical::magic::FileKind;
+
+    let lossy = b"RIFF\x24\0\0\0WEBPVP8 \x18\0\0\0\x30\x01\0\x9D\x01\x2A\x40\x01\xF0\0";
+    let info = image_info(FileKind::WEBP, lossy).unwrap();
+    assert_eq!((info.width, info.height), (320, 240));
+
+    // Animated with alpha, canvas of 100x50, two frames.
+    let mut animated = b"RIFF\x3C\0\0\0WEBPVP8X\x0A\0\0\0\x12\0\0\0\x63\0\0\x31\0\0".to_vec();
+    animated.extend_from_slice(b"ANIM\x06\0\0\0\0\0\0\0\0\0");
+    animated.extend_from_slice(b"ANMF\x03\0\0\0\0\0\0\0");
+    animated.extend_from_slice(b"ANMF\x04\0\0\0\0\0\0\0");
+
+    let info = image_info(FileKind::WEBP, &animated).unwrap();
+    assert_eq!((info.width, info.height), (100, 50));
+    assert_eq!(info.color_type, Some(ColorType::Rgba));
+    assert_eq!(info.frames, Some(2));
+
+    let info = image_info(FileKind::WEBP, &animated[..40]).unwrap();
+    assert_eq!(info.frames, None);
+}
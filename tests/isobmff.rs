@@ -0,0 +1,85 @@
+fn ftyp(major: &[u8], compatible: &[&[u8; 4]]) -> Vec<u8> {
+    let len = u32::try_from(16 + compatible.len() * 4).unwrap();
+
+    let mut file = len.to_be_bytes().to_vec();
+    file.extend_from_slice(b"ftyp");
+    file.extend_from_slice(major);
+    file.extend_from_slice(&[0, 0, 2, 0]);
+
+    for brand in compatible {
+        file.extend_from_slice(*brand);
+    }
+
+    file.extend_from_slice(b"\x00\x00\x00\x08free");
+    file
+}
+
+#[test]
+fn test_isobmff_brands() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (major, compatible, kind) in [
+        (
+            b"isom",
+            &[b"isom", b"iso2", b"avc1", b"mp41"][..],
+            FileKind::Mp4,
+        ),
+        (b"mp42", &[b"mp42", b"isom"], FileKind::Mp4),
+        (
+            b"M4V ",
+            &[b"M4V ", b"M4A ", b"mp42", b"isom"],
+            FileKind::M4v,
+        ),
+        (b"M4A ", &[b"M4A ", b"mp42", b"isom"], FileKind::M4a),
+        (b"qt  ", &[b"qt  "], FileKind::QuickTime),
+        (b"heic", &[b"mif1", b"heic"], FileKind::Heic),
+        (b"mif1", &[b"mif1", b"heic"], FileKind::Heic),
+        (b"mif1", &[b"mif1", b"miaf"], FileKind::Heif),
+        (b"avif", &[b"avif", b"mif1", b"miaf"], FileKind::Avif),
+        (b"mif1", &[b"mif1", b"miaf", b"avif"], FileKind::Avif),
+        (b"3gp5", &[b"3gp5", b"isom"], FileKind::ThreeGpp),
+        (b"3g2a", &[b"3g2a"], FileKind::ThreeGpp2),
+        (b"abcd", &[b"abcd", b"isom"], FileKind::Mp4),
+    ] {
+        let file = ftyp(major, compatible);
+
+        assert_eq!(
+            FileKind::match_types(&file),
+            Some(kind),
+            "{}",
+            String::from_utf8_lossy(major)
+        );
+    }
+}
+
+#[test]
+fn test_isobmff_rejects_other_boxes() {
+    use magical_rs::magical::ext_fn::isobmff::isobmff_kind;
+
+    assert_eq!(isobmff_kind(&ftyp(b"abcd", &[b"efgh"])), None);
+    assert_eq!(
+        isobmff_kind(b"\x00\x00\x00\x10moovisom\x00\x00\x00\x00"),
+        None
+    );
+    assert_eq!(isobmff_kind(b"\x00\x00\x00\x08ftyp"), None);
+    assert_eq!(
+        isobmff_kind(b"\x00\x00\x00\x13ftypisom\x00\x00\x00\x00"),
+        None
+    );
+    assert_eq!(
+        isobmff_kind(b"\x7F\x00\x00\x10ftypisom\x00\x00\x00\x00"),
+        None
+    );
+    assert_eq!(isobmff_kind(b"\x00\x00\x00"), None);
+}
+
+#[test]
+fn test_isobmff_truncated_brands() {
+    use magical_rs::magical::ext_fn::isobmff::isobmff_kind;
+    use magical_rs::magical::magic::FileKind;
+
+    let file = ftyp(b"mif1", &[b"mif1", b"miaf", b"avif"]);
+
+    assert_eq!(isobmff_kind(&file[..24]), Some(FileKind::Heif));
+    assert_eq!(isobmff_kind(&file[..28]), Some(FileKind::Avif));
+}
@@ -0,0 +1,193 @@
+/*
+ * Exhaustive on purpose: adding a variant without updating
+ * `FileKind::ALL` and `KIND_INFO` must break this test.
+ */
+#[allow(clippy::too_many_lines)]
+const fn declared(kind: magical_rs::magical::magic::FileKind) -> bool {
+    use magical_rs::magical::magic::FileKind;
+
+    match kind {
+        FileKind::Png
+        | FileKind::Bitmap
+        | FileKind::Gzip
+        | FileKind::Bzip
+        | FileKind::PkgZip
+        | FileKind::Tar
+        | FileKind::MSDOS
+        | FileKind::Jpg
+        | FileKind::Class
+        | FileKind::MP3
+        | FileKind::ISO
+        | FileKind::RPM
+        | FileKind::SQLite
+        | FileKind::XML
+        | FileKind::ICO
+        | FileKind::WASM
+        | FileKind::Deb
+        | FileKind::RAR
+        | FileKind::ScriptExecute
+        | FileKind::ELF
+        | FileKind::OGG
+        | FileKind::_8BPS
+        | FileKind::BLENDER
+        | FileKind::TrueTypeFont
+        | FileKind::OpenTypeFont
+        | FileKind::ModuleForEvenvironmentModules
+        | FileKind::WindowImagingFormat
+        | FileKind::Slob
+        | FileKind::SerializedJavaData
+        | FileKind::CreativeVoiceFile
+        | FileKind::AuAudioFileFormat
+        | FileKind::OpenGLIrisPerformer
+        | FileKind::NoodlesoftHazel
+        | FileKind::VBScriptEncoded
+        | FileKind::WEBP
+        | FileKind::AppleIconImage
+        | FileKind::GIF
+        | FileKind::JPEG2000
+        | FileKind::PDF
+        | FileKind::AppleDiskImage
+        | FileKind::Cabinet
+        | FileKind::MatroskaMediaContainer
+        | FileKind::RichTextFormat
+        | FileKind::PhotoCapTemplate
+        | FileKind::AceCompressed
+        | FileKind::FlashVideo
+        | FileKind::Vmdk
+        | FileKind::GoogleChromeExtension
+        | FileKind::Docx
+        | FileKind::Xlsx
+        | FileKind::Pptx
+        | FileKind::OpenDocumentText
+        | FileKind::OpenDocumentSpreadsheet
+        | FileKind::OpenDocumentPresentation
+        | FileKind::Epub
+        | FileKind::Jar
+        | FileKind::Apk
+        | FileKind::Xpi
+        | FileKind::Nupkg
+        | FileKind::Vsix
+        | FileKind::Mp4
+        | FileKind::M4v
+        | FileKind::M4a
+        | FileKind::QuickTime
+        | FileKind::Heic
+        | FileKind::Heif
+        | FileKind::Avif
+        | FileKind::ThreeGpp
+        | FileKind::ThreeGpp2
+        | FileKind::Wav
+        | FileKind::Avi
+        | FileKind::AniCursor
+        | FileKind::Rmi
+        | FileKind::Qcp
+        | FileKind::CorelDraw
+        | FileKind::MachO
+        | FileKind::MachOFat
+        | FileKind::Pe32
+        | FileKind::Pe32Plus
+        | FileKind::DotNetAssembly
+        | FileKind::WindowsDriver
+        | FileKind::Dll
+        | FileKind::Xz
+        | FileKind::Zstd
+        | FileKind::Lz4
+        | FileKind::SevenZip
+        | FileKind::Lzip
+        | FileKind::Lzma
+        | FileKind::UnixCompress
+        | FileKind::Lzop
+        | FileKind::Cpio
+        | FileKind::Flac
+        | FileKind::Aiff
+        | FileKind::Midi
+        | FileKind::Aac
+        | FileKind::Opus
+        | FileKind::Vorbis
+        | FileKind::Speex
+        | FileKind::Ape
+        | FileKind::WavPack
+        | FileKind::Amr
+        | FileKind::Webm
+        | FileKind::MpegTs
+        | FileKind::MpegPs
+        | FileKind::Asf
+        | FileKind::Qcow2
+        | FileKind::Vhd
+        | FileKind::Vhdx
+        | FileKind::Vdi
+        | FileKind::Gpt
+        | FileKind::Mbr
+        | FileKind::Ext2
+        | FileKind::Ext3
+        | FileKind::Ext4
+        | FileKind::Fat12
+        | FileKind::Fat16
+        | FileKind::Fat32
+        | FileKind::ExFat
+        | FileKind::Ntfs
+        | FileKind::Squashfs
+        | FileKind::Btrfs
+        | FileKind::Xfs
+        | FileKind::Udf
+        | FileKind::Parquet
+        | FileKind::ArrowIpc
+        | FileKind::Feather
+        | FileKind::Orc
+        | FileKind::AvroObjectContainer
+        | FileKind::Hdf5
+        | FileKind::NetCdf
+        | FileKind::Fits
+        | FileKind::NumpyNpy
+        | FileKind::MatlabMat => true,
+    }
+}
+
+#[test]
+fn test_every_kind_has_info() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::signatures::KIND_INFO;
+
+    assert_eq!(FileKind::ALL.len(), KIND_INFO.len());
+
+    for &kind in FileKind::ALL {
+        assert!(declared(kind));
+
+        let entries = KIND_INFO.iter().filter(|info| info.kind == kind).count();
+        assert_eq!(entries, 1, "{kind:?} must have exactly one KIND_INFO entry");
+
+        assert!(kind.mime_type().contains('/'), "{kind:?}");
+        assert!(!kind.extensions().is_empty(), "{kind:?}");
+        assert!(!kind.description().is_empty(), "{kind:?}");
+    }
+}
+
+#[test]
+fn test_kind_info_png() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::Png.mime_type(), "image/png");
+    assert_eq!(FileKind::Png.extensions(), &["png"]);
+    assert_eq!(FileKind::Png.description(), "Portable Network Graphics");
+}
+
+#[test]
+fn test_kind_info_reverse_lookup() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::from_mime("image/png"), Some(FileKind::Png));
+    assert_eq!(
+        FileKind::from_mime("Application/PDF; charset=binary"),
+        Some(FileKind::PDF)
+    );
+    assert_eq!(FileKind::from_mime("application/x-nothing"), None);
+
+    assert_eq!(FileKind::from_extension("gif"), Some(FileKind::GIF));
+    assert_eq!(FileKind::from_extension(".JPEG"), Some(FileKind::Jpg));
+    assert_eq!(FileKind::from_extension(""), None);
+
+    for &kind in FileKind::ALL {
+        let by_ext = FileKind::from_extension(kind.extensions()[0]).unwrap();
+        assert_eq!(by_ext.extensions()[0], kind.extensions()[0]);
+    }
+}
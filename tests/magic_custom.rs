@@ -1,6 +1,7 @@
 #[test]
 fn test_match_types_custom() {
     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom, match_types_custom};
+    use magical_rs::magical::match_rules::Strength;
     #[derive(Debug, Clone, Copy, PartialEq)]
     enum FileKind {
         Png,
@@ -13,6 +14,7 @@ fn test_match_types_custom() {
         offsets: &[0],
         max_bytes_read: 2048,
         kind: FileKind::Png,
+        strength: Strength::Auto,
         rules: CustomMatchRules::Default,
     };
 
@@ -29,6 +31,7 @@ fn test_match_types_custom() {
 #[test]
 fn test_with_custom_rules() {
     use magical_rs::magical::magic_custom::{MagicCustom, match_types_custom};
+    use magical_rs::magical::match_rules::Strength;
     use magical_rs::with_fn_matches;
 
     #[derive(Debug, Clone, Copy, PartialEq)]
@@ -46,6 +49,7 @@ fn test_with_custom_rules() {
         offsets: &[],
         max_bytes_read: 2048,
         kind: ShoujuFile::MahouShouju,
+        strength: Strength::Auto,
         rules: with_fn_matches!(is_shoujo_girl),
     };
 
@@ -131,3 +135,165 @@ fn test_with_all_matches() {
     assert_eq!(result, CuteGirlKind::UnknownFallback);
     assert_ne!(result, CuteGirlKind::ShoujoFile);
 }
+
+#[test]
+fn test_match_all_types_custom() {
+    use magical_rs::magical::magic_custom::{
+        CustomMatchRules, MagicCustom, match_all_types_custom, match_types_custom,
+    };
+    use magical_rs::magical::match_rules::Strength;
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum ShoujoKind {
+        Shoujo,
+        MahouShoujo,
+        Unknown,
+    }
+
+    fn is_mahou_shoujo(bytes: &[u8]) -> bool {
+        bytes.ends_with(b"Mahou")
+    }
+
+    static RULES: &[MagicCustom<ShoujoKind>] = &[
+        MagicCustom {
+            signatures: &[b"Shoujo"],
+            offsets: &[0],
+            max_bytes_read: 32,
+            kind: ShoujoKind::Shoujo,
+            strength: Strength::Auto,
+            rules: CustomMatchRules::Default,
+        },
+        MagicCustom {
+            signatures: &[],
+            offsets: &[],
+            max_bytes_read: 32,
+            kind: ShoujoKind::MahouShoujo,
+            strength: Strength::Auto,
+            rules: CustomMatchRules::WithFn(is_mahou_shoujo),
+        },
+    ];
+
+    let data = b"Shoujo Mahou";
+
+    assert_eq!(
+        match_types_custom(data, RULES, ShoujoKind::Unknown),
+        ShoujoKind::Shoujo
+    );
+    assert_eq!(
+        match_all_types_custom(data, RULES)
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [ShoujoKind::Shoujo, ShoujoKind::MahouShoujo]
+    );
+    assert!(
+        match_all_types_custom(b"Nothing", RULES)
+            .into_iter()
+            .next()
+            .is_none()
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_match_types_custom_ranked() {
+    use magical_rs::magical::magic_custom::{
+        CustomMatchRules, MagicCustom, match_types_custom_ranked,
+    };
+    use magical_rs::magical::match_rules::Strength;
+    use magical_rs::{all_matches, magic_custom};
+
+    #[derive(Debug, Clone, Copy, PartialEq)]
+    enum ShoujoKind {
+        Shoujo,
+        MahouShoujo,
+        Idol,
+    }
+
+    fn is_shoujo(bytes: &[u8]) -> bool {
+        bytes.starts_with(b"Shoujo")
+    }
+
+    fn is_mahou(bytes: &[u8]) -> bool {
+        bytes.ends_with(b"Mahou")
+    }
+
+    let rules: &[MagicCustom<ShoujoKind>] = &[
+        magic_custom!(
+            signatures: [b"Sh"],
+            offsets: [0],
+            max_bytes_read: 32,
+            kind: ShoujoKind::Shoujo,
+            rules: CustomMatchRules::Default
+        ),
+        magic_custom!(
+            signatures: [],
+            offsets: [],
+            max_bytes_read: 32,
+            kind: ShoujoKind::MahouShoujo,
+            rules: all_matches!(is_shoujo, is_mahou)
+        ),
+        magic_custom!(
+            signatures: [b"Shoujo"],
+            offsets: [0],
+            max_bytes_read: 32,
+            kind: ShoujoKind::Idol,
+            strength: Strength::Fixed(5),
+            rules: CustomMatchRules::Default
+        ),
+        magic_custom!(
+            signatures: [b"Shoujo"],
+            offsets: [0],
+            max_bytes_read: 32,
+            kind: ShoujoKind::Shoujo,
+            rules: CustomMatchRules::Default
+        ),
+    ];
+
+    let ranked = match_types_custom_ranked(b"Shoujo Mahou", rules);
+    let kinds: Vec<ShoujoKind> = ranked.iter().map(|m| m.kind).collect();
+    let confidences: Vec<u8> = ranked.iter().map(|m| m.confidence).collect();
+
+    assert_eq!(
+        kinds,
+        vec![
+            ShoujoKind::MahouShoujo,
+            ShoujoKind::Shoujo,
+            ShoujoKind::Idol
+        ]
+    );
+    assert_eq!(confidences, vec![70, 60, 5]);
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_match_types_custom_ranked_alternatives() {
+    use magical_rs::magic_custom;
+    use magical_rs::magical::magic_custom::{
+        CustomMatchRules, MagicCustom, match_types_custom_ranked,
+    };
+
+    // Same signature length, but one rule may match at four places.
+    let rules: &[MagicCustom<&str>] = &[
+        magic_custom!(
+            signatures: [b"KIRA"],
+            offsets: [0, 4, 8, 12],
+            max_bytes_read: 16,
+            kind: "anywhere",
+            rules: CustomMatchRules::Default
+        ),
+        magic_custom!(
+            signatures: [b"KIRA"],
+            offsets: [0],
+            max_bytes_read: 16,
+            kind: "start",
+            rules: CustomMatchRules::Default
+        ),
+    ];
+
+    let ranked = match_types_custom_ranked(b"KIRA KIRA", rules);
+
+    assert_eq!(ranked[0].kind, "start");
+    assert_eq!(ranked[0].confidence, 40);
+    assert_eq!(ranked[1].kind, "anywhere");
+    assert_eq!(ranked[1].confidence, 38);
+}
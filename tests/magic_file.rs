@@ -0,0 +1,266 @@
+#![cfg(feature = "std")]
+
+#[test]
+fn test_magic_file_numbers_and_continuations() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    let magic = MagicFile::parse(
+        r"
+# ELF, trimmed down from file(1)'s magic.
+0       string      \177ELF     ELF
+>4      byte        1           32-bit
+>4      byte        2           64-bit
+>5      byte        1           LSB
+>>16    leshort     2           executable
+>>16    leshort     3           shared object
+>5      byte        2           MSB
+>>16    beshort     2           executable
+>18     leshort&0xff    x       \b, machine %#x
+!:mime  application/x-executable
+",
+    )
+    .unwrap();
+
+    let mut elf = vec![0u8; 64];
+    elf[..4].copy_from_slice(b"\x7FELF");
+    elf[4] = 2;
+    elf[5] = 1;
+    elf[16] = 3;
+    elf[18] = 0x3E;
+
+    let found = magic.match_types(&elf).unwrap();
+    assert_eq!(
+        found.description,
+        "ELF 64-bit LSB shared object, machine 0x3e"
+    );
+    assert_eq!(found.mime.as_deref(), Some("application/x-executable"));
+    assert_eq!(found.line, 3);
+
+    elf[5] = 2;
+    elf[16] = 0;
+    elf[17] = 2;
+
+    let found = magic.match_types(&elf).unwrap();
+    assert_eq!(found.description, "ELF 64-bit MSB executable, machine 0x3e");
+
+    assert!(magic.match_types(b"\x7FEL").is_none());
+}
+
+#[test]
+fn test_magic_file_unsigned_types() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    /* The top bit set: a large unsigned value, a negative signed one. */
+    for (name, bytes) in [
+        ("byte", &b"\x80"[..]),
+        ("short", b"\x00\x80"),
+        ("long", b"\x00\x00\x00\x80"),
+        ("quad", b"\x00\x00\x00\x00\x00\x00\x00\x80"),
+        ("beshort", b"\x80\x00"),
+        ("belong", b"\x80\x00\x00\x00"),
+        ("bequad", b"\x80\x00\x00\x00\x00\x00\x00\x00"),
+        ("leshort", b"\x00\x80"),
+        ("lelong", b"\x00\x00\x00\x80"),
+        ("lequad", b"\x00\x00\x00\x00\x00\x00\x00\x80"),
+    ] {
+        let unsigned = MagicFile::parse(&format!("0 u{name} >0x7f unsigned")).unwrap();
+        let signed = MagicFile::parse(&format!("0 {name} >0x7f signed")).unwrap();
+
+        assert_eq!(
+            unsigned.match_types(bytes).unwrap().description,
+            "unsigned",
+            "u{name}"
+        );
+        assert!(signed.match_types(bytes).is_none(), "{name}");
+    }
+}
+
+#[test]
+fn test_magic_file_offsets() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    let magic = MagicFile::parse(
+        r"
+0       string      MZ
+>(0x3c.l)   string  PE\0\0      PE executable
+>>&0    leshort     0x8664      (x86-64)
+0       string      SEIFUKU     Uniform catalogue
+>-4     string      END!        \b, complete
+",
+    )
+    .unwrap();
+
+    let mut pe = vec![0u8; 0x90];
+    pe[..2].copy_from_slice(b"MZ");
+    pe[0x3C] = 0x80;
+    pe[0x80..0x84].copy_from_slice(b"PE\0\0");
+    pe[0x84..0x86].copy_from_slice(&0x8664u16.to_le_bytes());
+
+    let found = magic.match_types(&pe).unwrap();
+    assert_eq!(found.description, "PE executable (x86-64)");
+
+    let found = magic.match_types(b"SEIFUKU....END!").unwrap();
+    assert_eq!(found.description, "Uniform catalogue, complete");
+
+    let found = magic.match_types(b"SEIFUKU....").unwrap();
+    assert_eq!(found.description, "Uniform catalogue");
+}
+
+#[test]
+fn test_magic_file_search_regex_and_default() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    let magic = MagicFile::parse(
+        r"
+0       search/64/c     \<svg        SVG image
+!:mime  image/svg+xml
+!:ext   svg/svgz
+0       regex/3l    ^[A-Z][a-z]+:\ [0-9]{1,3}$   Idol score sheet
+>0      string      Miku        \b, by %s
+>0      default     x           \b, by someone else
+",
+    )
+    .unwrap();
+
+    let found = magic
+        .match_types(b"<?xml version=\"1.0\"?>\n<SVG width=\"10\">")
+        .unwrap();
+    assert_eq!(found.description, "SVG image");
+    assert_eq!(found.extensions, ["svg", "svgz"]);
+
+    let found = magic.match_types(b"Miku: 100\nRin: 99\n").unwrap();
+    assert_eq!(found.description, "Idol score sheet, by Miku: 100");
+
+    let found = magic.match_types(b"Rin: 99\n").unwrap();
+    assert_eq!(found.description, "Idol score sheet, by someone else");
+
+    assert!(magic.match_types(b"Rin: 1000\n").is_none());
+    assert_eq!(magic.match_all_types(b"<svg/>").len(), 1);
+}
+
+#[test]
+fn test_magic_file_strength() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    let magic = MagicFile::parse(
+        "
+0   belong  0xCAFEBABE  Four bytes
+0   byte    0xCA        One byte
+!:strength  +50
+0   string  \\xCA       Other byte
+!:strength  /2
+",
+    )
+    .unwrap();
+
+    let found = magic.match_all_types(&[0xCA, 0xFE, 0xBA, 0xBE]);
+    let strengths: Vec<u8> = found.iter().map(|found| found.strength).collect();
+
+    assert_eq!(strengths, [40, 60, 5]);
+    assert_eq!(magic.max_bytes_read(), 64);
+}
+
+#[test]
+fn test_magic_file_reports_unsupported_lines() {
+    use magical_rs::magical::magic_file::{MagicFile, MagicFileErrorKind};
+
+    let source = "
+0       string      KAWAII      Cute file
+>4      pstring     x           \\b, named %s
+>>0     byte        x           never loaded
+>6      byte        1           \\b, v1
+!:apple ????CUTE
+0       ledate      x           Dated file
+0       lelong      0x1         One
+>>4     byte        x           missing parent
+0       bogus       x           Broken
+";
+
+    let errors = MagicFile::parse(source).unwrap_err();
+    let lines: Vec<usize> = errors.iter().map(|error| error.line).collect();
+    assert_eq!(lines, [3, 6, 7, 9, 10]);
+
+    assert!(matches!(errors[0].kind, MagicFileErrorKind::Unsupported(_)));
+    assert!(matches!(errors[1].kind, MagicFileErrorKind::Unsupported(_)));
+    assert!(matches!(errors[2].kind, MagicFileErrorKind::Unsupported(_)));
+    assert!(matches!(errors[3].kind, MagicFileErrorKind::Invalid(_)));
+    assert!(matches!(errors[4].kind, MagicFileErrorKind::Invalid(_)));
+    assert_eq!(errors[0].to_string(), "line 3: unsupported type `pstring`");
+
+    let (magic, lossy_errors) = MagicFile::parse_lossy(source);
+    assert_eq!(lossy_errors, errors);
+    assert_eq!(magic.len(), 2);
+
+    let found = magic.match_types(b"KAWAII\x02").unwrap();
+    assert_eq!(found.description, "Cute file");
+
+    let found = magic.match_types(b"KAWAII\x01").unwrap();
+    assert_eq!(found.description, "Cute file, v1");
+}
+
+#[test]
+fn test_magic_file_hostile_input() {
+    use magical_rs::magical::magic_file::MagicFile;
+
+    // A search range close to `usize::MAX`.
+    let magic = MagicFile::parse("0 search/0xffffffffffffffff ABC found").unwrap();
+    assert_eq!(magic.match_types(b"..ABC").unwrap().description, "found");
+
+    // Widths and precisions are capped instead of padding to exabytes.
+    let magic = MagicFile::parse("0 byte x %999999999999999999d %.999999999999s").unwrap();
+    let description = magic.match_types(b"\x07").unwrap().description;
+    assert!(description.len() < 256, "{}", description.len());
+    assert!(description.trim_start().starts_with('7'));
+
+    // Deeply nested regular expressions are rejected instead of overflowing the stack.
+    for pattern in [
+        format!("{}a{}", "(".repeat(50_000), ")".repeat(50_000)),
+        format!("a{}", "*".repeat(50_000)),
+    ] {
+        let errors = MagicFile::parse(&format!("0 regex {pattern} nested")).unwrap_err();
+        assert!(
+            errors[0].to_string().contains("nested too deeply"),
+            "{}",
+            errors[0]
+        );
+    }
+
+    let pattern = format!("{}a{}", "(".repeat(50), ")".repeat(50));
+    let magic = MagicFile::parse(&format!("0 regex {pattern} nested")).unwrap();
+    assert_eq!(magic.match_types(b"a").unwrap().description, "nested");
+}
+
+#[cfg(feature = "magical_dyn")]
+#[test]
+fn test_magic_file_into_dyn_rules() {
+    use magical_rs::magical::dyn_magic::{DynMagicCustom, match_dyn_types_as};
+    use magical_rs::magical::magic_file::{MagicFile, MagicMatch};
+
+    let mut rules = MagicFile::parse(
+        "
+0   string  MAHOU   Magical girl record
+!:mime      application/x-mahou
+",
+    )
+    .unwrap()
+    .into_dyn_rules();
+
+    rules.push(DynMagicCustom::new(
+        |bytes: &[u8]| bytes.starts_with(b"IDOL"),
+        MagicMatch {
+            description: "Idol record".to_owned(),
+            mime: None,
+            extensions: Vec::new(),
+            strength: 0,
+            line: 0,
+        },
+        4,
+    ));
+
+    let found = match_dyn_types_as::<MagicMatch>(b"MAHOU", &rules).unwrap();
+    assert_eq!(found.description, "Magical girl record");
+    assert_eq!(found.mime.as_deref(), Some("application/x-mahou"));
+
+    let found = match_dyn_types_as::<MagicMatch>(b"IDOL", &rules).unwrap();
+    assert_eq!(found.description, "Idol record");
+}
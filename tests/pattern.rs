@@ -0,0 +1,102 @@
+#[test]
+fn test_parse_hex_pattern() {
+    use magical_rs::magical::pattern::{hex_pattern_len, parse_hex_pattern};
+
+    assert_eq!(hex_pattern_len("FF D8 FF ??"), 4);
+    assert_eq!(hex_pattern_len("ffd8"), 2);
+    assert_eq!(hex_pattern_len(""), 0);
+
+    let (bytes, mask) = parse_hex_pattern::<4>("FF d8 E? ??");
+    assert_eq!(bytes, [0xFF, 0xD8, 0xE0, 0x00]);
+    assert_eq!(mask, [0xFF, 0xFF, 0xF0, 0x00]);
+}
+
+#[test]
+fn test_masked_signature() {
+    use magical_rs::magical::pattern::MaskedSignature;
+    use magical_rs::masked;
+
+    const RIFF_WEBP: MaskedSignature = masked!("52 49 46 46 ?? ?? ?? ?? 57 45 42 50");
+
+    assert_eq!(RIFF_WEBP.len(), 12);
+    assert_eq!(RIFF_WEBP.significant_len(), 8);
+    assert_eq!(RIFF_WEBP.exact_first_byte(), Some(b'R'));
+
+    assert!(RIFF_WEBP.matches_at(b"RIFF\x10\x20\x30\x40WEBPVP8 ", 0));
+    assert!(RIFF_WEBP.matches_at(b"..RIFF\0\0\0\0WEBP", 2));
+    assert!(!RIFF_WEBP.matches_at(b"RIFF\0\0\0\0WAVE", 0));
+    assert!(!RIFF_WEBP.matches_at(b"RIFF", 0));
+    assert!(!RIFF_WEBP.matches_at(b"RIFF\0\0\0\0WEBP", usize::MAX));
+}
+
+#[test]
+fn test_builtin_masked_jpeg() {
+    use magical_rs::magical::magic::FileKind;
+
+    /* JFIF, EXIF and raw quantization table markers. */
+    for marker in [0xE0, 0xE1, 0xDB, 0xEE] {
+        let bytes = [0xFF, 0xD8, 0xFF, marker, 0x00, 0x10];
+        assert_eq!(FileKind::match_types(&bytes), Some(FileKind::Jpg));
+    }
+
+    assert_eq!(FileKind::match_types(&[0xFF, 0xD8, 0xFF]), None);
+}
+
+#[test]
+fn test_magic_custom_masked() {
+    use magical_rs::magical::magic_custom::match_types_custom;
+    use magical_rs::magical::match_rules::Strength;
+    use magical_rs::{magic_custom, masked_matches};
+
+    #[derive(Clone, Copy, Debug, PartialEq, Eq)]
+    enum ShoujoKind {
+        Jpeg,
+        Wave,
+        Unknown,
+    }
+
+    let rules = [
+        magic_custom!(
+            masked: ["FF D8 FF ??"],
+            offsets: [0],
+            max_bytes_read: 4,
+            kind: ShoujoKind::Jpeg
+        ),
+        magic_custom!(
+            masked: ["57 41 56 45"],
+            offsets: [8],
+            max_bytes_read: 12,
+            kind: ShoujoKind::Wave,
+            strength: Strength::Fixed(90)
+        ),
+        magic_custom!(
+            signatures: [],
+            offsets: [0, 4],
+            max_bytes_read: 12,
+            kind: ShoujoKind::Unknown,
+            rules: masked_matches!("00 ?? 00 ??", "AB CD")
+        ),
+    ];
+
+    assert_eq!(rules[0].strength(), 30);
+    assert_eq!(rules[1].strength(), 90);
+    // Two significant bytes, at four possible positions.
+    assert_eq!(rules[2].strength(), 18);
+
+    assert_eq!(
+        match_types_custom(&[0xFF, 0xD8, 0xFF, 0xE1], &rules, ShoujoKind::Unknown),
+        ShoujoKind::Jpeg
+    );
+    assert_eq!(
+        match_types_custom(b"RIFF\0\0\0\0WAVE", &rules, ShoujoKind::Jpeg),
+        ShoujoKind::Wave
+    );
+    assert_eq!(
+        match_types_custom(b"....\xAB\xCD", &rules, ShoujoKind::Jpeg),
+        ShoujoKind::Unknown
+    );
+    assert_eq!(
+        match_types_custom(b"nothing", &rules, ShoujoKind::Jpeg),
+        ShoujoKind::Jpeg
+    );
+}
@@ -0,0 +1,105 @@
+#![cfg(feature = "std")]
+
+#[test]
+fn test_read_header_from_short_stream() {
+    use magical_rs::magical::bytes_read::{DEFAULT_MAX_BYTES_READ, read_header_from};
+    use std::io::Cursor;
+
+    let header = read_header_from(Cursor::new(b"GIF89a"), DEFAULT_MAX_BYTES_READ).unwrap();
+    assert_eq!(header, b"GIF89a");
+
+    let header = read_header_from(Cursor::new(b"GIF89a"), 3).unwrap();
+    assert_eq!(header, b"GIF");
+
+    let header = read_header_from(std::io::empty(), DEFAULT_MAX_BYTES_READ).unwrap();
+    assert!(header.is_empty());
+}
+
+#[test]
+fn test_read_ranges_from_keeps_offsets() {
+    use magical_rs::magical::bytes_read::read_ranges_from;
+    use std::io::Cursor;
+
+    let data: Vec<u8> = (0..=255).collect();
+
+    let bytes = read_ranges_from(Cursor::new(&data), &[0..4, 100..102, 200..203]).unwrap();
+
+    assert_eq!(bytes.len(), 203);
+    assert_eq!(bytes[..4], [0, 1, 2, 3]);
+    assert_eq!(bytes[100..102], [100, 101]);
+    assert_eq!(bytes[200..203], [200, 201, 202]);
+    assert!(bytes[4..100].iter().all(|&byte| byte == 0));
+
+    let bytes = read_ranges_from(Cursor::new(&data), &[250..300, 400..500]).unwrap();
+    assert_eq!(bytes.len(), 256);
+
+    assert!(
+        read_ranges_from(Cursor::new(&data), &[])
+            .unwrap()
+            .is_empty()
+    );
+}
+
+#[test]
+fn test_signature_ranges_are_merged() {
+    use magical_rs::magical::bytes_read::{ISO_OFFSETS, signature_ranges};
+
+    let ranges = signature_ranges();
+
+    assert_eq!(ranges[0].start, 0);
+    assert!(ranges.windows(2).all(|pair| pair[0].end < pair[1].start));
+
+    for &offset in ISO_OFFSETS {
+        assert!(ranges.iter().any(|range| range.contains(&offset)));
+    }
+}
+
+#[test]
+fn test_detect_reader_matches_full_read() {
+    use magical_rs::magical::bytes_read::{detect_reader, read_file_header, with_bytes_read};
+    use magical_rs::magical::magic::FileKind;
+    use std::fs::File;
+
+    for file in [
+        "tests/1.png",
+        "tests/2.iso",
+        "tests/3.class",
+        "tests/4.webp",
+    ] {
+        let header = read_file_header(file, with_bytes_read()).unwrap();
+
+        assert_eq!(
+            detect_reader(File::open(file).unwrap()).unwrap(),
+            FileKind::match_types(&header)
+        );
+    }
+}
+
+#[test]
+fn test_detect_reader_large_stream() {
+    use magical_rs::magical::bytes_read::detect_reader;
+    use magical_rs::magical::magic::FileKind;
+    use std::io::Cursor;
+
+    /* Far past every window of the built-in rules, btrfs at 64 KiB included. */
+    let mut ts = vec![0xFF; 188 * 1100];
+    ts.chunks_mut(188)
+        .for_each(|packet| packet[..4].copy_from_slice(b"\x47\x40\x00\x10"));
+
+    assert!(ts.len() > 1 << 16);
+    assert_eq!(FileKind::match_types(&ts), Some(FileKind::MpegTs));
+    assert_eq!(
+        detect_reader(Cursor::new(ts)).unwrap(),
+        Some(FileKind::MpegTs)
+    );
+}
+
+#[test]
+fn test_detect_path() {
+    use magical_rs::magical::bytes_read::detect_path;
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(detect_path("tests/1.png").unwrap(), Some(FileKind::Png));
+    assert_eq!(detect_path("tests/2.iso").unwrap(), Some(FileKind::ISO));
+    assert!(detect_path("tests/does_not_exist").is_err());
+}
@@ -0,0 +1,76 @@
+#[test]
+fn test_riff_form_types() {
+    use magical_rs::magical::magic::FileKind;
+
+    for (form_type, kind) in [
+        (b"WAVE", FileKind::Wav),
+        (b"AVI ", FileKind::Avi),
+        (b"ACON", FileKind::AniCursor),
+        (b"WEBP", FileKind::WEBP),
+        (b"RMID", FileKind::Rmi),
+        (b"QLCM", FileKind::Qcp),
+        (b"CDR9", FileKind::CorelDraw),
+        (b"cdrA", FileKind::CorelDraw),
+    ] {
+        let mut file = b"RIFF\x1C\0\0\0".to_vec();
+        file.extend_from_slice(form_type);
+        file.extend_from_slice(&[0; 24]);
+
+        assert_eq!(FileKind::match_types(&file), Some(kind));
+    }
+
+    let webp = std::fs::read("tests/4.webp").unwrap();
+    assert_eq!(FileKind::match_types(&webp), Some(FileKind::WEBP));
+}
+
+#[test]
+fn test_riff_containers() {
+    use magical_rs::magical::ext_fn::riff::{RiffContainer, RiffHeader, riff_kind};
+    use magical_rs::magical::magic::FileKind;
+
+    let rifx = RiffHeader::parse(b"RIFX\0\0\x01\0WAVEfmt ").unwrap();
+    assert_eq!(rifx.container, RiffContainer::Rifx);
+    assert_eq!(rifx.declared_len, 0x108);
+    assert_eq!(rifx.kind(), Some(FileKind::Wav));
+
+    let mut rf64 = b"RF64\xFF\xFF\xFF\xFFWAVEds64\x1C\0\0\0".to_vec();
+    rf64.extend_from_slice(&0x1_0000_0000u64.to_le_bytes());
+    rf64.extend_from_slice(&[0; 20]);
+
+    let header = RiffHeader::parse(&rf64).unwrap();
+    assert_eq!(header.container, RiffContainer::Rf64);
+    assert_eq!(header.declared_len, 0x1_0000_0008);
+    assert_eq!(riff_kind(&rf64), Some(FileKind::Wav));
+
+    // The real size is in `ds64`, which must come first.
+    assert_eq!(riff_kind(b"RF64\xFF\xFF\xFF\xFFWAVEfmt \x10\0\0\0"), None);
+    // WebP is always little-endian.
+    assert_eq!(riff_kind(b"RIFX\0\0\0\x1CWEBPVP8 "), None);
+}
+
+#[test]
+fn test_riff_chunk_size() {
+    use magical_rs::magical::ext_fn::riff::{RiffHeader, is_truncated, riff_kind};
+    use magical_rs::magical::magic::FileKind;
+
+    // The chunk size must cover the form type and something after it.
+    assert_eq!(riff_kind(b"RIFF\0\0\0\0WAVE"), None);
+    assert_eq!(riff_kind(b"RIFF\x04\0\0\0AVI "), None);
+    assert_eq!(riff_kind(b"RIFF\x05\0\0\0AVI "), Some(FileKind::Avi));
+    assert_eq!(riff_kind(b"RIFF\x05\0\0\0"), None);
+
+    let mut wav = b"RIFF\x14\0\0\0WAVEdata\x08\0\0\0".to_vec();
+    wav.extend_from_slice(&[0x80; 8]);
+
+    assert!(!is_truncated(&wav));
+    assert!(is_truncated(&wav[..wav.len() - 1]));
+
+    // Trailing bytes are fine.
+    wav.extend_from_slice(b"ID3");
+    assert!(!is_truncated(&wav));
+
+    let header = RiffHeader::parse(&wav).unwrap();
+    assert!(header.is_truncated(27));
+    assert!(!header.is_truncated(28));
+    assert!(!is_truncated(b"not a riff file"));
+}
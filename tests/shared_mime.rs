@@ -0,0 +1,190 @@
+#![cfg(feature = "std")]
+
+const DATABASE: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE mime-info [
+  <!ELEMENT mime-info (mime-type)+>
+]>
+<mime-info xmlns="http://www.freedesktop.org/standards/shared-mime-info">
+  <!-- Trimmed down from freedesktop.org.xml. -->
+  <mime-type type="image/png">
+    <comment>PNG image</comment>
+    <comment xml:lang="fr">image PNG</comment>
+    <magic priority="50">
+      <match type="string" value="\x89PNG" offset="0"/>
+    </magic>
+    <glob pattern="*.png"/>
+  </mime-type>
+  <mime-type type="application/x-java">
+    <comment>Java class</comment>
+    <alias type="application/java"/>
+    <magic priority="50">
+      <match type="big32" value="0xcafebabe" offset="0">
+        <match type="big16" value="0x0000" offset="4"/>
+        <match type="big16" value="45" offset="6"/>
+      </match>
+    </magic>
+  </mime-type>
+  <mime-type type="application/x-mahou">
+    <comment>Magical girl &amp; mascot record</comment>
+    <sub-class-of type="text/plain"/>
+    <magic priority="80">
+      <match type="string" value="MAHOU" mask="0xDFDFDFDFDF" offset="0:8"/>
+    </magic>
+    <magic priority="20">
+      <match type="little16" value="0x4D41" offset="0"/>
+    </magic>
+    <glob pattern="*.mahou" weight="60"/>
+    <glob pattern="Makefile.[!a]*" case-sensitive="true"/>
+    <glob pattern="*.tar.mahou" weight="60"/>
+  </mime-type>
+</mime-info>
+"#;
+
+#[test]
+fn test_shared_mime_magic() {
+    use magical_rs::magical::shared_mime::SharedMimeInfo;
+
+    let database = SharedMimeInfo::parse(DATABASE).unwrap();
+    assert_eq!(database.len(), 3);
+
+    let found = database
+        .match_types(&std::fs::read("tests/1.png").unwrap())
+        .unwrap();
+    assert_eq!(found.mime, "image/png");
+    assert_eq!(found.comment.as_deref(), Some("PNG image"));
+
+    let found = database
+        .match_types(&std::fs::read("tests/3.class").unwrap())
+        .unwrap();
+    assert_eq!(found.mime, "application/x-java");
+    assert_eq!(found.aliases, ["application/java"]);
+    assert!(
+        database
+            .match_types(b"\xCA\xFE\xBA\xBE\x00\x01\x00\x2D")
+            .is_some()
+    );
+    assert!(
+        database
+            .match_types(b"\xCA\xFE\xBA\xBE\x00\x01\x00\x02")
+            .is_none()
+    );
+
+    let found = database.match_types(b"...mahou").unwrap();
+    assert_eq!(
+        found.comment.as_deref(),
+        Some("Magical girl & mascot record")
+    );
+    assert_eq!(found.magic_priority(b"...mahou"), Some(80));
+    assert_eq!(found.magic_priority(b"AM"), Some(20));
+    assert_eq!(found.sub_class_of, ["text/plain"]);
+
+    assert!(database.match_types(b".........MAHOU").is_none());
+    assert_eq!(database.max_bytes_read(), 13);
+}
+
+#[test]
+fn test_shared_mime_priorities() {
+    use magical_rs::magical::shared_mime::SharedMimeInfo;
+
+    let database = SharedMimeInfo::parse(
+        r#"<mime-info>
+  <mime-type type="text/x-low"><magic priority="10"><match type="byte" value="0x41" offset="0"/></magic></mime-type>
+  <mime-type type="text/x-high"><magic priority="90"><match type="byte" value="0x41" offset="0"/></magic></mime-type>
+  <mime-type type="text/x-default"><magic><match type="host16" value="0x4241" offset="0"/></magic></mime-type>
+</mime-info>"#,
+    )
+    .unwrap();
+
+    let found: Vec<&str> = database
+        .match_all_types(b"AB")
+        .iter()
+        .map(|kind| kind.mime.as_str())
+        .collect();
+
+    assert_eq!(found, ["text/x-high", "text/x-default", "text/x-low"]);
+    assert_eq!(database.match_types(b"AB").unwrap().mime, "text/x-high");
+}
+
+#[test]
+fn test_shared_mime_huge_range() {
+    use magical_rs::magical::shared_mime::SharedMimeInfo;
+
+    // Only offsets inside the input are tried, not all 2^64 of them.
+    let database = SharedMimeInfo::parse(
+        r#"<mime-info>
+  <mime-type type="text/x-far"><magic><match type="string" value="KYUN" offset="0:18446744073709551615"/></magic></mime-type>
+</mime-info>"#,
+    )
+    .unwrap();
+
+    assert_eq!(
+        database.match_types(b"moe moe KYUN").unwrap().mime,
+        "text/x-far"
+    );
+    assert!(database.match_types(b"moe moe kyun").is_none());
+    assert!(database.match_types(b"KYU").is_none());
+    assert!(database.match_types(b"").is_none());
+}
+
+#[test]
+fn test_shared_mime_globs() {
+    use magical_rs::magical::shared_mime::SharedMimeInfo;
+
+    let database = SharedMimeInfo::parse(DATABASE).unwrap();
+    let glob = |name| database.match_glob(name).map(|kind| kind.mime.as_str());
+
+    assert_eq!(glob("Sakura.PNG"), Some("image/png"));
+    assert_eq!(glob("card.tar.mahou"), Some("application/x-mahou"));
+    assert_eq!(glob("Makefile.mahou"), Some("application/x-mahou"));
+    assert_eq!(glob("Makefile.in"), Some("application/x-mahou"));
+    assert_eq!(glob("Makefile.am"), None);
+    assert_eq!(glob("makefile.in"), None);
+    assert_eq!(glob("png"), None);
+}
+
+#[test]
+fn test_shared_mime_errors() {
+    use magical_rs::magical::shared_mime::SharedMimeInfo;
+
+    let error =
+        SharedMimeInfo::parse("<mime-info>\n  <mime-type type=\"a/b\">\n</mime-info>").unwrap_err();
+    assert_eq!(error.line, 3);
+
+    let error = SharedMimeInfo::parse(
+        "<mime-info>\n<mime-type type=\"a/b\"><magic>\n<match type=\"float\" value=\"1\" offset=\"0\"/>\n</magic></mime-type></mime-info>",
+    )
+    .unwrap_err();
+    assert_eq!(error.line, 3);
+    assert_eq!(error.to_string(), "line 3: unknown match type `float`");
+
+    for invalid in [
+        r#"<match type="byte" value="0x100" offset="0"/>"#,
+        r#"<match type="string" value="AB" mask="0xFF" offset="0"/>"#,
+        r#"<match type="string" value="AB" offset="4:2"/>"#,
+        r#"<match type="big16" offset="0"/>"#,
+    ] {
+        let source = format!(
+            "<mime-info><mime-type type=\"a/b\"><magic>{invalid}</magic></mime-type></mime-info>"
+        );
+
+        assert!(SharedMimeInfo::parse(&source).is_err(), "{invalid}");
+    }
+
+    assert!(SharedMimeInfo::parse("<not-mime-info/>").is_err());
+}
+
+#[cfg(feature = "magical_dyn")]
+#[test]
+fn test_shared_mime_into_dyn_rules() {
+    use magical_rs::magical::dyn_magic::match_dyn_types_as;
+    use magical_rs::magical::shared_mime::{MimeType, SharedMimeInfo};
+
+    let rules = SharedMimeInfo::parse(DATABASE).unwrap().into_dyn_rules();
+    assert_eq!(rules.len(), 4);
+
+    let found = match_dyn_types_as::<MimeType>(b"MAHOU", &rules).unwrap();
+    assert_eq!(found.mime, "application/x-mahou");
+
+    let found = match_dyn_types_as::<MimeType>(b"\x89PNG", &rules).unwrap();
+    assert_eq!(found.mime, "image/png");
+}
@@ -0,0 +1,146 @@
+#[test]
+fn test_dmg_trailer_is_not_matched_at_offset_zero() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut head = b"koly".to_vec();
+    head.resize(512, 0);
+
+    assert_eq!(FileKind::match_types(&head), None);
+    assert_eq!(FileKind::match_types_with_tail(&head, &[]), None);
+}
+
+#[test]
+fn test_dmg_trailer() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut file = vec![0x00; 4096];
+    let trailer = file.len() - 512;
+    file[trailer..trailer + 4].copy_from_slice(b"koly");
+
+    assert_eq!(FileKind::match_types(&file), None);
+    assert_eq!(
+        FileKind::match_types_with_tail(&file[..1024], &file[file.len() - 512..]),
+        Some(FileKind::AppleDiskImage)
+    );
+}
+
+#[test]
+fn test_zip_end_of_central_directory() {
+    use magical_rs::magical::magic::FileKind;
+
+    // Self-extracting archive: a stub, then the archive, then the EOCD record.
+    let mut file = b"stub".to_vec();
+    file.resize(256, 0);
+    file.extend_from_slice(&[0x50, 0x4B, 0x05, 0x06]);
+    file.extend_from_slice(&[0x00; 18]);
+
+    assert_eq!(FileKind::match_types(&file), None);
+    assert_eq!(
+        FileKind::match_types_with_tail(&file, &file),
+        Some(FileKind::PkgZip)
+    );
+}
+
+#[test]
+fn test_header_wins_over_trailer() {
+    use magical_rs::magical::magic::FileKind;
+
+    let mut file = vec![0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+    file.resize(256, 0);
+    file.extend_from_slice(b"TAG");
+    file.extend_from_slice(&[0x20; 125]);
+
+    assert_eq!(
+        FileKind::match_types_with_tail(&file, &file),
+        Some(FileKind::Png)
+    );
+}
+
+#[test]
+fn test_short_tail() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::match_types_with_tail(&[], &[]), None);
+    assert_eq!(FileKind::match_types_with_tail(b"TAG", b"TAG"), None);
+}
+
+#[test]
+fn test_custom_from_end() {
+    use magical_rs::magical::magic_custom::{
+        CustomMatchRules, MagicCustom, match_types_custom, match_types_custom_with_tail,
+    };
+    use magical_rs::magical::match_rules::Strength;
+
+    #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+    enum ShoujoKind {
+        Header,
+        Trailer,
+        Unknown,
+    }
+
+    let rules = [
+        MagicCustom {
+            signatures: &[b"MAHOU"],
+            offsets: &[0],
+            max_bytes_read: 5,
+            kind: ShoujoKind::Header,
+            strength: Strength::Auto,
+            rules: CustomMatchRules::Default,
+        },
+        MagicCustom {
+            signatures: &[b"END!"],
+            offsets: &[8, 4],
+            max_bytes_read: 8,
+            kind: ShoujoKind::Trailer,
+            strength: Strength::Auto,
+            rules: CustomMatchRules::FromEnd,
+        },
+    ];
+
+    let data = b"shoujo...END!";
+    let padded = b"shoujo...END!1234";
+
+    assert_eq!(
+        match_types_custom(data, &rules, ShoujoKind::Unknown),
+        ShoujoKind::Unknown
+    );
+    assert_eq!(
+        match_types_custom_with_tail(data, data, &rules, ShoujoKind::Unknown),
+        ShoujoKind::Trailer
+    );
+    assert_eq!(
+        match_types_custom_with_tail(padded, padded, &rules, ShoujoKind::Unknown),
+        ShoujoKind::Trailer
+    );
+    assert_eq!(
+        match_types_custom_with_tail(b"MAHOU END!", b"MAHOU END!", &rules, ShoujoKind::Unknown),
+        ShoujoKind::Header
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_read_file_head_tail() {
+    use magical_rs::magical::bytes_read::{
+        read_file_head_tail, with_bytes_read, with_tail_bytes_read,
+    };
+    use magical_rs::magical::magic::FileKind;
+
+    let len = usize::try_from(std::fs::metadata("tests/2.iso").unwrap().len()).unwrap();
+    let (head, tail) = read_file_head_tail("tests/2.iso", with_bytes_read(), 512).unwrap();
+
+    assert_eq!(head.len(), with_bytes_read().min(len));
+    assert_eq!(tail.len(), 512.min(len));
+    assert_eq!(
+        FileKind::match_types_with_tail(&head, &tail),
+        Some(FileKind::ISO)
+    );
+
+    let (head, tail) = read_file_head_tail("tests/1.png", 16, with_tail_bytes_read()).unwrap();
+
+    assert_eq!(head.len(), 16);
+    assert_eq!(
+        FileKind::match_types_with_tail(&head, &tail),
+        Some(FileKind::Png)
+    );
+}
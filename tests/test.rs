@@ -68,3 +68,48 @@ fn test_webp_detect() {
     );
     assert!(FileKind::match_types(&header_bytes).is_some());
 }
+
+#[test]
+fn test_match_all_types() {
+    use magical_rs::magical::magic::FileKind;
+
+    let png_header = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+    let kinds: Vec<FileKind> = FileKind::match_all_types(png_header).into_iter().collect();
+    assert_eq!(kinds, [FileKind::Png]);
+
+    let kinds: Vec<FileKind> = FileKind::match_all_types(b"BM").into_iter().collect();
+    assert_eq!(kinds.first(), FileKind::match_types(b"BM").as_ref());
+
+    assert!(
+        FileKind::match_all_types(&[0x00, 0x01, 0x02])
+            .into_iter()
+            .next()
+            .is_none()
+    );
+}
+
+#[test]
+#[cfg(feature = "std")]
+fn test_match_types_ranked() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::match_rules::MAX_STRENGTH;
+    use magical_rs::magical::signatures::SIGNATURE_KIND;
+
+    let png_header = &[0x89, 0x50, 0x4E, 0x47, 0x0D, 0x0A, 0x1A, 0x0A];
+    let ranked = FileKind::match_types_ranked(png_header);
+
+    assert_eq!(ranked.len(), 1);
+    assert_eq!(ranked[0].kind, FileKind::Png);
+    assert_eq!(ranked[0].confidence, 80);
+
+    let weak = FileKind::match_types_ranked(b"MZ is not always an executable");
+    assert_eq!(weak[0].kind, FileKind::MSDOS);
+    assert_eq!(weak[0].confidence, 20);
+
+    assert!(
+        SIGNATURE_KIND
+            .iter()
+            .all(|magic| magic.strength() <= MAX_STRENGTH)
+    );
+    assert!(FileKind::match_types_ranked(&[0x00, 0x01, 0x02]).is_empty());
+}
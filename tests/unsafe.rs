@@ -4,6 +4,7 @@ fn test_unsafe_magic_custom() {
     use core::slice;
     use magical_rs::magical::magic_custom::match_types_custom;
     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
+    use magical_rs::magical::match_rules::Strength;
 
     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
     enum MagicKind {
@@ -29,6 +30,7 @@ fn test_unsafe_magic_custom() {
         offsets: &[],
         max_bytes_read: 200,
         kind: MagicKind::MoeMoe,
+        strength: Strength::Auto,
         rules: CustomMatchRules::WithFnUnsafe {
             func: is_shoujo_girl,
         },
@@ -46,6 +48,7 @@ fn test_any_unsafe_fn() {
     use core::slice;
     use magical_rs::magical::magic_custom::match_types_custom;
     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
+    use magical_rs::magical::match_rules::Strength;
 
     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
     enum MagicKind {
@@ -76,6 +79,7 @@ fn test_any_unsafe_fn() {
         offsets: &[],
         max_bytes_read: 200,
         kind: MagicKind::MoeMoe,
+        strength: Strength::Auto,
         /*
          * We just need to clarify that, even though these two
          * functions are logically contradictory, they can still
@@ -96,6 +100,7 @@ fn test_all_unsafe_fn() {
     use core::slice;
     use magical_rs::magical::magic_custom::match_types_custom;
     use magical_rs::magical::magic_custom::{CustomMatchRules, MagicCustom};
+    use magical_rs::magical::match_rules::Strength;
 
     #[derive(Clone, Copy, PartialEq, Eq, Debug)]
     enum MagicKind {
@@ -126,6 +131,7 @@ fn test_all_unsafe_fn() {
         offsets: &[],
         max_bytes_read: 200,
         kind: MagicKind::MoeMoe,
+        strength: Strength::Auto,
         rules: CustomMatchRules::AllMatchesUnsafe(&[is_shoujo_girl, is_not_shoujo_girl]),
     }];
 
@@ -0,0 +1,142 @@
+#[test]
+fn test_validate_png() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::validate::Validation;
+
+    let png = std::fs::read("tests/1.png").unwrap();
+
+    assert_eq!(FileKind::Png.validate(&png), Some(Validation::Valid));
+    assert_eq!(FileKind::match_types_strict(&png), Some(FileKind::Png));
+    assert_eq!(
+        FileKind::Png.validate(&png[..30]),
+        Some(Validation::Truncated)
+    );
+
+    let mut damaged = png;
+    damaged[20] ^= 0x01;
+    assert_eq!(
+        FileKind::Png.validate(&damaged),
+        Some(Validation::Malformed)
+    );
+    assert_eq!(FileKind::match_types(&damaged), Some(FileKind::Png));
+    assert_eq!(FileKind::match_types_strict(&damaged), None);
+
+    let forged = b"\x89PNG\r\n\x1A\n<html><body>moe moe kyun</body></html>";
+    assert_eq!(FileKind::Png.validate(forged), Some(Validation::Malformed));
+}
+
+#[test]
+fn test_validate_gif_and_bitmap() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::validate::Validation;
+
+    // Global color table of 2 entries, then an image descriptor.
+    let mut gif = b"GIF87a\x01\x00\x01\x00\x80\x00\x00".to_vec();
+    gif.extend_from_slice(&[0xFF; 6]);
+    assert_eq!(FileKind::GIF.validate(&gif), Some(Validation::Truncated));
+
+    gif.push(b',');
+    assert_eq!(FileKind::GIF.validate(&gif), Some(Validation::Valid));
+
+    let mut bitmap = b"BM\x3A\0\0\0\0\0\0\0\x36\0\0\0\x28\0\0\0".to_vec();
+    bitmap.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0, 1, 0, 24, 0]);
+    assert_eq!(FileKind::Bitmap.validate(&bitmap), Some(Validation::Valid));
+    assert_eq!(
+        FileKind::Bitmap.validate(&bitmap[..20]),
+        Some(Validation::Truncated)
+    );
+
+    // A DIB header size no variant has.
+    bitmap[14] = 0x29;
+    assert_eq!(
+        FileKind::Bitmap.validate(&bitmap),
+        Some(Validation::Malformed)
+    );
+    assert_eq!(FileKind::match_types_strict(&bitmap), None);
+
+    // The pixel data would start inside the DIB header.
+    bitmap[14] = 0x28;
+    bitmap[10] = 0x20;
+    assert_eq!(
+        FileKind::Bitmap.validate(&bitmap),
+        Some(Validation::Malformed)
+    );
+}
+
+#[test]
+fn test_validate_elf() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::validate::Validation;
+
+    let mut elf = b"\x7FELF\x02\x01\x01\0\0\0\0\0\0\0\0\0\x02\0\x3E\0\x01\0\0\0".to_vec();
+    assert_eq!(FileKind::ELF.validate(&elf), Some(Validation::Valid));
+    assert_eq!(
+        FileKind::ELF.validate(&elf[..16]),
+        Some(Validation::Truncated)
+    );
+
+    let mut big_endian = b"\x7FELF\x01\x02\x01\0\0\0\0\0\0\0\0\0\0\x02\0\x08\0\0\0\x01".to_vec();
+    assert_eq!(FileKind::ELF.validate(&big_endian), Some(Validation::Valid));
+
+    big_endian[5] = 0x03;
+    assert_eq!(
+        FileKind::ELF.validate(&big_endian),
+        Some(Validation::Malformed)
+    );
+
+    elf[20] = 0x02;
+    assert_eq!(FileKind::ELF.validate(&elf), Some(Validation::Malformed));
+}
+
+#[test]
+fn test_validate_zip_and_sqlite() {
+    use magical_rs::magical::magic::FileKind;
+    use magical_rs::magical::validate::Validation;
+
+    let mut zip = b"PK\x03\x04\x14\0\0\0\x08\0".to_vec();
+    zip.extend_from_slice(&[0; 16]);
+    zip.extend_from_slice(b"\x07\0\0\0moe.txt");
+    assert_eq!(FileKind::PkgZip.validate(&zip), Some(Validation::Valid));
+    assert_eq!(FileKind::Docx.validate(&zip), Some(Validation::Valid));
+    assert_eq!(
+        FileKind::PkgZip.validate(&zip[..32]),
+        Some(Validation::Truncated)
+    );
+
+    // An unknown compression method.
+    zip[8] = 0x42;
+    assert_eq!(FileKind::PkgZip.validate(&zip), Some(Validation::Malformed));
+
+    let mut sqlite = b"SQLite format 3\0\x10\0\x01\x01\0\x40\x20\x20".to_vec();
+    sqlite.extend_from_slice(&[0; 76]);
+    assert_eq!(FileKind::SQLite.validate(&sqlite), Some(Validation::Valid));
+    assert_eq!(
+        FileKind::match_types_strict(&sqlite),
+        Some(FileKind::SQLite)
+    );
+
+    // 65536 bytes pages are stored as 1.
+    sqlite[16..18].copy_from_slice(&[0, 1]);
+    assert_eq!(FileKind::SQLite.validate(&sqlite), Some(Validation::Valid));
+
+    sqlite[16..18].copy_from_slice(&[0x0C, 0]);
+    assert_eq!(
+        FileKind::SQLite.validate(&sqlite),
+        Some(Validation::Malformed)
+    );
+    assert_eq!(
+        FileKind::SQLite.validate(&sqlite[..20]),
+        Some(Validation::Truncated)
+    );
+}
+
+#[test]
+fn test_validate_unsupported_kinds() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::PDF.validate(b"%PDF-1.7"), None);
+    assert_eq!(
+        FileKind::match_types_strict(b"%PDF-1.7"),
+        Some(FileKind::PDF)
+    );
+}
@@ -0,0 +1,92 @@
+/// An EBML header with a `DocType` element, then a `Segment` of unknown size.
+fn ebml(doc_type: &[u8]) -> Vec<u8> {
+    let mut file = b"\x1A\x45\xDF\xA3".to_vec();
+    file.push(0x80 | u8::try_from(doc_type.len() + 7).unwrap());
+    file.extend_from_slice(b"\x42\x86\x81\x01");
+    file.extend_from_slice(b"\x42\x82");
+    file.push(0x80 | u8::try_from(doc_type.len()).unwrap());
+    file.extend_from_slice(doc_type);
+    file.extend_from_slice(b"\x18\x53\x80\x67\x01\xFF\xFF\xFF\xFF\xFF\xFF\xFF");
+    file
+}
+
+#[test]
+fn test_video_ebml_doc_type() {
+    use magical_rs::magical::magic::FileKind;
+
+    assert_eq!(FileKind::match_types(&ebml(b"webm")), Some(FileKind::Webm));
+    assert_eq!(
+        FileKind::match_types(&ebml(b"matroska")),
+        Some(FileKind::MatroskaMediaContainer)
+    );
+    assert_eq!(
+        FileKind::match_all_types(&ebml(b"webm\0\0"))
+            .into_iter()
+            .collect::<Vec<_>>(),
+        [FileKind::Webm]
+    );
+
+    // The header is cut before the `DocType`.
+    assert_eq!(
+        FileKind::match_types(&ebml(b"webm")[..6]),
+        Some(FileKind::MatroskaMediaContainer)
+    );
+}
+
+#[test]
+fn test_video_mpeg_streams() {
+    use magical_rs::magical::ext_fn::video::is_mpeg_ts;
+    use magical_rs::magical::magic::FileKind;
+
+    for (stride, sync) in [(188, 0), (192, 4), (204, 0)] {
+        let mut ts = vec![0; stride * 5];
+        ts.iter_mut()
+            .skip(sync)
+            .step_by(stride)
+            .for_each(|byte| *byte = 0x47);
+
+        assert_eq!(
+            FileKind::match_types(&ts),
+            Some(FileKind::MpegTs),
+            "{stride}"
+        );
+
+        // A last packet cut short by the bytes read does not count.
+        assert_eq!(
+            FileKind::match_types(&ts[..stride * 3 + sync]),
+            Some(FileKind::MpegTs),
+            "{stride}"
+        );
+
+        // Nor does anything past the first packets, like the gaps of a sparse buffer.
+        let mut sparse = ts[..stride].repeat(8);
+        sparse.resize(1 << 16, 0);
+        assert!(is_mpeg_ts(&sparse), "{stride}");
+
+        ts[sync + stride * 3] = 0x48;
+        assert_eq!(FileKind::match_types(&ts), None, "{stride}");
+    }
+
+    let mpeg2 = b"\0\0\x01\xBA\x44\0\x04\0\x04\x01\x01\x89\xC3\xF8";
+    let mpeg1 = b"\0\0\x01\xBA\x21\0\x01\0\x01\x80\x1B\x91";
+    assert_eq!(FileKind::match_types(mpeg2), Some(FileKind::MpegPs));
+    assert_eq!(FileKind::match_types(mpeg1), Some(FileKind::MpegPs));
+    assert_eq!(FileKind::match_types(b"\0\0\x01\xBA\0\0\0\0"), None);
+}
+
+#[test]
+fn test_video_asf_and_flv() {
+    use magical_rs::magical::magic::FileKind;
+
+    let asf = b"\x30\x26\xB2\x75\x8E\x66\xCF\x11\xA6\xD9\x00\xAA\x00\x62\xCE\x6C\x5A\x14";
+    assert_eq!(FileKind::match_types(asf), Some(FileKind::Asf));
+
+    let flv = b"FLV\x01\x05\0\0\0\x09\0\0\0\0";
+    assert_eq!(FileKind::match_types(flv), Some(FileKind::FlashVideo));
+
+    // Reserved flags, another version, or a body inside the header.
+    assert_eq!(FileKind::match_types(b"FLV\x01\x85\0\0\0\x09"), None);
+    assert_eq!(FileKind::match_types(b"FLV\x02\x05\0\0\0\x09"), None);
+    assert_eq!(FileKind::match_types(b"FLV\x01\x05\0\0\0\x03"), None);
+    assert_eq!(FileKind::match_types(b"FLV is a text file"), None);
+}
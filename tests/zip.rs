@@ -0,0 +1,209 @@
+/// Builds a ZIP archive of stored entries. With `streamed`, local headers have the
+/// data descriptor flag and no sizes, like archives written to a pipe.
+fn zip(entries: &[(&str, &[u8])], streamed: bool) -> Vec<u8> {
+    let mut archive = Vec::new();
+    let mut central = Vec::new();
+
+    for (name, data) in entries {
+        let offset = u32::try_from(archive.len()).unwrap();
+        let size = u32::try_from(data.len()).unwrap();
+        let name_len = u16::try_from(name.len()).unwrap();
+        let (flags, local_size) = if streamed { (8u16, 0) } else { (0, size) };
+
+        archive.extend_from_slice(b"PK\x03\x04\x14\0");
+        archive.extend_from_slice(&flags.to_le_bytes());
+        archive.extend_from_slice(&[0; 10]);
+        archive.extend_from_slice(&local_size.to_le_bytes());
+        archive.extend_from_slice(&local_size.to_le_bytes());
+        archive.extend_from_slice(&name_len.to_le_bytes());
+        archive.extend_from_slice(&[0; 2]);
+        archive.extend_from_slice(name.as_bytes());
+        archive.extend_from_slice(data);
+
+        central.extend_from_slice(b"PK\x01\x02\x14\0\x14\0");
+        central.extend_from_slice(&flags.to_le_bytes());
+        central.extend_from_slice(&[0; 10]);
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&size.to_le_bytes());
+        central.extend_from_slice(&name_len.to_le_bytes());
+        central.extend_from_slice(&[0; 12]);
+        central.extend_from_slice(&offset.to_le_bytes());
+        central.extend_from_slice(name.as_bytes());
+    }
+
+    let count = u16::try_from(entries.len()).unwrap();
+    let central_offset = u32::try_from(archive.len()).unwrap();
+    let central_size = u32::try_from(central.len()).unwrap();
+
+    archive.extend_from_slice(&central);
+    archive.extend_from_slice(b"PK\x05\x06\0\0\0\0");
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&count.to_le_bytes());
+    archive.extend_from_slice(&central_size.to_le_bytes());
+    archive.extend_from_slice(&central_offset.to_le_bytes());
+    archive.extend_from_slice(&[0; 2]);
+
+    archive
+}
+
+#[test]
+fn test_zip_office_open_xml() {
+    use magical_rs::magical::magic::FileKind;
+
+    let content_types: &[u8] = b"<?xml version=\"1.0\"?><Types/>";
+
+    for (entry, kind) in [
+        ("word/document.xml", FileKind::Docx),
+        ("xl/workbook.xml", FileKind::Xlsx),
+        ("ppt/presentation.xml", FileKind::Pptx),
+    ] {
+        let archive = zip(
+            &[
+                ("[Content_Types].xml", content_types),
+                ("_rels/.rels", b"<Relationships/>"),
+                (entry, b"<document/>"),
+            ],
+            false,
+        );
+
+        assert_eq!(FileKind::match_types(&archive), Some(kind));
+    }
+}
+
+#[test]
+fn test_zip_mimetype_entry() {
+    use magical_rs::magical::ext_fn::zip::zip_kind;
+    use magical_rs::magical::magic::FileKind;
+
+    for (mimetype, kind) in [
+        ("application/epub+zip", Some(FileKind::Epub)),
+        (
+            "application/vnd.oasis.opendocument.text",
+            Some(FileKind::OpenDocumentText),
+        ),
+        (
+            "application/vnd.oasis.opendocument.spreadsheet",
+            Some(FileKind::OpenDocumentSpreadsheet),
+        ),
+        (
+            "application/vnd.oasis.opendocument.presentation",
+            Some(FileKind::OpenDocumentPresentation),
+        ),
+        ("application/vnd.oasis.opendocument.graphics", None),
+    ] {
+        let archive = zip(
+            &[
+                ("mimetype", mimetype.as_bytes()),
+                ("META-INF/container.xml", b"<container/>"),
+            ],
+            false,
+        );
+
+        assert_eq!(zip_kind(&archive), kind, "{mimetype}");
+        assert_eq!(
+            FileKind::match_types(&archive),
+            Some(kind.unwrap_or(FileKind::PkgZip))
+        );
+    }
+}
+
+#[test]
+fn test_zip_packages() {
+    use magical_rs::magical::ext_fn::zip::zip_kind;
+    use magical_rs::magical::magic::FileKind;
+
+    let manifest: (&str, &[u8]) = ("META-INF/MANIFEST.MF", b"Manifest-Version: 1.0\r\n");
+
+    for (entries, kind) in [
+        (
+            vec![manifest, ("moe/Kyun.class", b"\xCA\xFE\xBA\xBE")],
+            FileKind::Jar,
+        ),
+        (
+            vec![
+                manifest,
+                ("AndroidManifest.xml", b"\x03\0"),
+                ("classes.dex", b"dex\n035\0"),
+            ],
+            FileKind::Apk,
+        ),
+        (
+            vec![
+                manifest,
+                ("META-INF/mozilla.rsa", b"\x30"),
+                ("manifest.json", b"{}"),
+            ],
+            FileKind::Xpi,
+        ),
+        (vec![("install.rdf", b"<RDF/>")], FileKind::Xpi),
+        (
+            vec![
+                ("[Content_Types].xml", b"<Types/>"),
+                ("Sakura.Kawaii.nuspec", b"<package/>"),
+            ],
+            FileKind::Nupkg,
+        ),
+        (
+            vec![
+                ("extension.vsixmanifest", b"<PackageManifest/>"),
+                ("[Content_Types].xml", b"<Types/>"),
+            ],
+            FileKind::Vsix,
+        ),
+    ] {
+        let archive = zip(&entries, false);
+
+        assert_eq!(zip_kind(&archive), Some(kind));
+        assert_eq!(FileKind::match_types(&archive), Some(kind));
+    }
+}
+
+#[test]
+fn test_zip_generic_archive() {
+    use magical_rs::magical::ext_fn::zip::zip_kind;
+    use magical_rs::magical::magic::FileKind;
+
+    let archive = zip(
+        &[("moe.txt", b"moe moe kyun"), ("lib/word/readme.txt", b"")],
+        false,
+    );
+
+    assert_eq!(zip_kind(&archive), None);
+    assert_eq!(FileKind::match_types(&archive), Some(FileKind::PkgZip));
+    assert_eq!(zip_kind(b"PK\x03\x04\x14\0"), None);
+    assert_eq!(
+        FileKind::match_types(b"PK\x03\x04\x14\0"),
+        Some(FileKind::PkgZip)
+    );
+}
+
+#[test]
+fn test_zip_central_directory() {
+    use magical_rs::magical::ext_fn::zip::zip_kind;
+    use magical_rs::magical::magic::FileKind;
+
+    let archive = zip(
+        &[
+            ("[Content_Types].xml", b"<Types/>"),
+            ("word/document.xml", b"<document/>"),
+        ],
+        true,
+    );
+
+    // Local headers stop at the first streamed entry, the central directory has them all.
+    assert_eq!(zip_kind(&archive), Some(FileKind::Docx));
+    assert_eq!(zip_kind(&archive[..archive.len() - 1]), None);
+
+    // The word entry is far past the head, only the central directory reaches it.
+    let filler = vec![0x20; 4096];
+    let archive = zip(
+        &[
+            ("filler.bin", &filler),
+            ("word/document.xml", b"<document/>"),
+        ],
+        false,
+    );
+
+    assert_eq!(zip_kind(&archive[..2048]), None);
+    assert_eq!(zip_kind(&archive), Some(FileKind::Docx));
+}